@@ -1,64 +1,478 @@
-use clap::Parser;
-use anyhow::{Result};
-use serde_json::{Value};
-use crate::{file};
-use crate::fake::fake_definition::FakeDefinition;
+use std::fs;
+use std::path::{Path, PathBuf};
+use clap::{Parser, Subcommand, ValueEnum};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use fake_cli::{FakeDefinitionElement, GenerationContext, Generator, GeneratorBuilder, Registry, TypeInfo};
+use fake_cli::import::json_schema::{import_json_schema, ImportOptions};
+use fake_cli::import::openapi::{import_openapi, import_openapi_dataset, parse_openapi, OpenApiSchemas};
+use fake_cli::import::sql::import_sql;
+use fake_cli::schema::definition::definition_schema;
+use fake_cli::schema::output::{output_schema, typescript};
 
 /// `Args` struct is used to parse command line arguments.
 /// The `json` field corresponds to the JSON input file.
+/// The `schema` field is a JSON Schema file generated from directly instead of `json`, as `import jsonschema` imports it.
 /// The `count` field specifies how many times the fake data generation should be run.
+/// The `sort_keys` field sorts the generated keys alphabetically instead of keeping the definition order.
+/// The `out_dir` field is the directory a dataset writes one `<entity>.json` file per entity to.
+/// The `seed` field seeds the random values, so that the same seed generates the same data.
+/// The `set` field holds the `key=value` variables substituted for `${key}` in the definition, before the environment.
+/// The `optional_probability` field is the probability the properties of `schema` which are not required are generated
+/// with, instead of always generating them.
+/// The `command` field is the subcommand run instead of generating fake data, in which case `json` is not required.
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    #[arg(short, long)]
-    json: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(short, long, required_unless_present = "schema", conflicts_with = "schema")]
+    json: Option<String>,
+
+    #[arg(long)]
+    schema: Option<PathBuf>,
 
     #[arg(short, long, default_value_t = 1)]
     count: usize,
+
+    #[arg(long)]
+    sort_keys: bool,
+
+    #[arg(short, long)]
+    out_dir: Option<PathBuf>,
+
+    #[arg(long)]
+    seed: Option<u64>,
+
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_variable)]
+    set: Vec<(String, String)>,
+
+    #[arg(long, requires = "schema", value_parser = parse_probability)]
+    optional_probability: Option<f64>,
+}
+
+/// `Command` is a subcommand of the application.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List every fake type with its category and description
+    ListTypes {
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Describe the parameters, supported locales and an example of a fake type
+    Describe {
+        fake_type: String,
+
+        #[arg(long, value_enum, default_value_t = Format::Table)]
+        format: Format,
+    },
+    /// Print a JSON Schema
+    Schema {
+        #[command(subcommand)]
+        schema: SchemaCommand,
+    },
+    /// Print a definition imported from a schema written for another tool, to edit and generate from
+    Import {
+        #[command(subcommand)]
+        import: ImportCommand,
+    },
+}
+
+/// `ImportCommand` is the kind of schema the `import` subcommand imports.
+#[derive(Subcommand, Debug)]
+enum ImportCommand {
+    /// A JSON Schema of an object, whose $refs within the same file become named definitions
+    Jsonschema {
+        file: PathBuf,
+
+        /// Generate the properties which are not required with this probability, from 0 to 1, instead of always
+        #[arg(long, value_parser = parse_probability)]
+        optional_probability: Option<f64>,
+    },
+    /// The object schemas under components.schemas of an OpenAPI 3 document in YAML or JSON, as a dataset of an entity
+    /// per schema, or as a definition file per schema with --out-dir
+    Openapi {
+        file: PathBuf,
+
+        #[arg(long)]
+        operations: bool,
+
+        #[arg(short, long)]
+        out_dir: Option<PathBuf>,
+
+        /// Generate the properties which are not required with this probability, from 0 to 1, instead of always
+        #[arg(long, value_parser = parse_probability)]
+        optional_probability: Option<f64>,
+    },
+    /// The CREATE TABLE statements of a SQL script, as a dataset of an entity per table whose foreign keys are refs
+    Sql {
+        file: PathBuf,
+    },
 }
 
+/// `SchemaCommand` is the JSON Schema the `schema` subcommand prints.
+#[derive(Subcommand, Debug)]
+enum SchemaCommand {
+    /// The JSON Schema of the definition files, for editors to validate and autocomplete them
+    Definition,
+    /// The JSON Schema of the data a definition file generates, or TypeScript interfaces of it with --ts
+    Output {
+        #[arg(short, long)]
+        json: String,
+
+        #[arg(long)]
+        ts: bool,
+
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_variable)]
+        set: Vec<(String, String)>,
+    },
+}
+
+/// `Format` is how a subcommand prints its output: an aligned table for people, or JSON for tools.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Table,
+    Json,
+}
+
+/// The seed the example of a fake type is generated with, so that `describe` always prints the same example.
+const EXAMPLE_SEED: u64 = 1;
+
 /// The `start` function is the entry point to the application. It parses the command line arguments,
-/// loads the JSON file specified by the arguments, and then calls the `output_json` function to
-/// generate and print the fake data.
+/// builds a `Generator` from the JSON file specified by the arguments, and then calls the `output_json`
+/// function to generate and print the fake data, or the `output_dataset` function when the file defines a dataset.
 pub fn start() -> Result<()> {
     let args = Args::parse();
-    let fake_definition_json = file::load_json(args.json)?;
-    output_json(&fake_definition_json, args.count)
+    match &args.command {
+        Some(Command::ListTypes { format }) => println!("{}", list_types(Registry::builtin(), *format)?),
+        Some(Command::Describe { fake_type, format }) => println!("{}", describe_type(Registry::builtin(), fake_type, *format)?),
+        Some(Command::Schema { schema: SchemaCommand::Definition }) => println!("{}", serde_json::to_string_pretty(&definition_schema(Registry::builtin()))?),
+        Some(Command::Schema { schema: SchemaCommand::Output { json, ts, set } }) => println!("{}", describe_output(json, *ts, set)?),
+        Some(Command::Import { import: ImportCommand::Jsonschema { file, optional_probability } }) => {
+            let options = ImportOptions { optional_probability: *optional_probability };
+            println!("{}", serde_json::to_string_pretty(&import_json_schema(&load_json(file)?, &options)?)?)
+        }
+        Some(Command::Import { import: ImportCommand::Openapi { file, operations, out_dir, optional_probability } }) => {
+            let options = ImportOptions { optional_probability: *optional_probability };
+            import_openapi_file(file, *operations, out_dir.as_deref(), &options)?
+        }
+        Some(Command::Import { import: ImportCommand::Sql { file } }) => println!("{}", serde_json::to_string_pretty(&import_sql(&read_file(file)?)?)?),
+        None => generate(&args)?,
+    }
+
+    Ok(())
 }
 
-/// Takes a JSON value (`fake_definition_json`) and the number of times (`count`) to generate fake data.
-/// Depending on the `count`, it decides whether to generate a single set of fake data
-/// or multiple sets.
-fn output_json(fake_definition_json: &Value, count: usize) -> Result<()> {
-    match count {
-        1 => output_single_fake_definition(fake_definition_json),
-        _ => output_multiply_fake_definition(fake_definition_json, count)
+/// Builds a `Generator` from the JSON file or the JSON Schema file of `args` and prints the fake data it generates.
+fn generate(args: &Args) -> Result<()> {
+    let mut generator = match (&args.json, &args.schema) {
+        (_, Some(schema)) => {
+            let options = ImportOptions { optional_probability: args.optional_probability };
+            generator_builder(args.seed, &args.set).from_value(&import_json_schema(&load_json(schema)?, &options)?)?
+        }
+        (Some(json), None) => build_generator(json, args.seed, &args.set)?,
+        (None, None) => Err(anyhow!("--json or --schema is required to generate fake data"))?,
+    };
+
+    match generator.is_dataset() {
+        true => output_dataset(&mut generator, args),
+        false => output_json(&mut generator, args),
     }
 }
 
-/// Generates a single set of fake data from the given JSON value. The resulting fake data is then
-/// converted to a pretty-printed JSON string and printed to stdout.
-fn output_single_fake_definition(fake_definition_json: &Value) -> Result<()> {
-    let fake_definition = FakeDefinition::from_json(fake_definition_json)?;
-    let json = serde_json::to_string_pretty(&fake_definition.to_value())?;
-    println!("{}", json);
+/// Builds a `Generator` from the JSON file `json`, with the `--set` variables `set`.
+fn build_generator(json: &str, seed: Option<u64>, set: &[(String, String)]) -> Result<Generator> {
+    generator_builder(seed, set).from_path(json)
+}
+
+/// Returns a `GeneratorBuilder` with the seed `seed` and the `--set` variables `set`.
+fn generator_builder(seed: Option<u64>, set: &[(String, String)]) -> GeneratorBuilder {
+    let mut builder = Generator::builder();
+    if let Some(seed) = seed {
+        builder = builder.seed(seed);
+    }
+    for (key, value) in set {
+        builder = builder.variable(key, value);
+    }
+    builder
+}
+
+/// Loads the JSON file `path`, such as a schema to import.
+fn load_json(path: &Path) -> Result<Value> {
+    serde_json::from_str(&read_file(path)?).map_err(|error| anyhow!("{}: {}", path.display(), error))
+}
+
+/// Reads the text file `path`, naming it in the error when it can not be read.
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|error| anyhow!("{}: {}", path.display(), error))
+}
+
+/// Describes the data the JSON file `json` generates as a JSON Schema, or as TypeScript interfaces with `ts`.
+/// Both are named after the file, e.g. `Customer` for `customer.json`.
+fn describe_output(json: &str, ts: bool, set: &[(String, String)]) -> Result<String> {
+    let generator = build_generator(json, None, set)?;
+    let name = PathBuf::from(json).file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let schema = output_schema(&generator, &name);
+
+    match ts {
+        true => Ok(typescript(&schema, &name).trim_end().to_string()),
+        false => Ok(serde_json::to_string_pretty(&schema)?),
+    }
+}
+
+/// Imports the schemas of the OpenAPI document `file`, or the request bodies and responses of its operations with
+/// `operations`. With `out_dir`, each schema is written to its own `<name>.json` definition file, otherwise a dataset
+/// of an entity per schema is printed to stdout.
+fn import_openapi_file(file: &Path, operations: bool, out_dir: Option<&Path>, options: &ImportOptions) -> Result<()> {
+    let document = parse_openapi(&read_file(file)?)?;
+    let schemas = match operations {
+        true => OpenApiSchemas::Operations,
+        false => OpenApiSchemas::Components,
+    };
+
+    match out_dir {
+        Some(out_dir) => {
+            fs::create_dir_all(out_dir)?;
+            for (name, definition) in import_openapi(&document, schemas, options)? {
+                fs::write(out_dir.join(format!("{}.json", name)), serde_json::to_string_pretty(&definition)?)?;
+            }
+        }
+        None => println!("{}", serde_json::to_string_pretty(&import_openapi_dataset(&document, schemas, options)?)?),
+    }
+
     Ok(())
 }
 
-/// The function generates `count` sets of fake data from the given JSON value. Each set of fake data
-/// is added to a vector. The vector is then converted to a pretty-printed JSON string and printed to stdout.
-fn output_multiply_fake_definition(fake_definition_json: &Value, count: usize) -> Result<()> {
-    let fake_definitions = (1..=count)
-        .map(|_| FakeDefinition::from_json(fake_definition_json))
-        .collect::<Result<Vec<FakeDefinition>, _>>()?;
+/// Lists the name, category and description of every fake type of `registry`.
+fn list_types(registry: &Registry, format: Format) -> Result<String> {
+    match format {
+        Format::Table => {
+            let rows = registry.type_infos().map(|info| vec![info.name.clone(), info.category.clone(), info.description.clone()]).collect();
+            Ok(format_table(&["NAME", "CATEGORY", "DESCRIPTION"], rows))
+        }
+        Format::Json => {
+            let types: Vec<Value> = registry
+                .type_infos()
+                .map(|info| json!({ "name": info.name, "category": info.category, "description": info.description }))
+                .collect();
+            Ok(serde_json::to_string_pretty(&types)?)
+        }
+    }
+}
+
+/// Describes the fake type `fake_type` of `registry`, including the output of its example.
+fn describe_type(registry: &Registry, fake_type: &str, format: Format) -> Result<String> {
+    let info = registry
+        .type_info(fake_type)
+        .ok_or(anyhow!("{} is not a fake type. run list-types to see every fake type", fake_type))?;
+    let example_output = example_output(registry, info)?;
+
+    match format {
+        Format::Table => {
+            let rows = info
+                .params
+                .iter()
+                .map(|param| {
+                    let required = if param.required { "yes" } else { "no" };
+                    let default = param.default.as_ref().map(Value::to_string).unwrap_or_default();
+                    let values = match (param.minimum, param.maximum) {
+                        (Some(minimum), Some(maximum)) => format!("{} to {}", minimum, maximum),
+                        (Some(minimum), None) => format!("{} or more", minimum),
+                        _ => param.values.join(" | "),
+                    };
+                    let description = match param.deprecated {
+                        true => format!("Deprecated. {}", param.description),
+                        false => param.description.clone(),
+                    };
+                    vec![param.name.clone(), param.param_type.to_string(), required.to_string(), default, values, description]
+                })
+                .collect();
 
-    let values: Vec<_> = fake_definitions
-        .iter()
-        .map(|fake_definition| { fake_definition.to_value() })
+            let mut lines = vec![format!("{} ({})", info.name, info.category), info.description.clone(), String::new()];
+            match info.params.is_empty() {
+                true => lines.push("Parameters: none".to_string()),
+                false => lines.push(format_table(&["PARAMETER", "TYPE", "REQUIRED", "DEFAULT", "VALUES", "DESCRIPTION"], rows)),
+            }
+            lines.push(String::new());
+            lines.push(format!("Locales: {}", if info.locales.is_empty() { "-".to_string() } else { info.locales.join(", ") }));
+            lines.push(format!("Example: {}", info.example));
+            lines.push(format!("Output:  {}", example_output));
+            Ok(lines.join("\n"))
+        }
+        Format::Json => {
+            let mut value = serde_json::to_value(info)?;
+            value["example_output"] = example_output;
+            Ok(serde_json::to_string_pretty(&value)?)
+        }
+    }
+}
+
+/// Returns the output of the example of a fake type, generated with a fixed seed unless it is given.
+fn example_output(registry: &Registry, info: &TypeInfo) -> Result<Value> {
+    match &info.example_output {
+        Some(example_output) => Ok(example_output.clone()),
+        None => FakeDefinitionElement::generate_with_registry(&info.example, registry)?.to_value(&mut GenerationContext::with_seed(EXAMPLE_SEED)),
+    }
+}
+
+/// Formats `rows` as a table with a header, padding every column but the last to its widest cell.
+fn format_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let rows: Vec<Vec<String>> = std::iter::once(headers.iter().map(|header| header.to_string()).collect()).chain(rows).collect();
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
         .collect();
 
-    let json = serde_json::to_string_pretty(&values)?;
-    println!("{}", json);
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(column, cell)| match column + 1 == row.len() {
+                    true => cell.clone(),
+                    false => format!("{}{}", cell, " ".repeat(widths[column] - cell.chars().count())),
+                })
+                .collect();
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Generates the records of every entity of a dataset. With `out_dir`, each entity is written to its own
+/// `<entity>.json` file, otherwise all entities are printed to stdout as a single JSON object keyed by entity.
+fn output_dataset(generator: &mut Generator, args: &Args) -> Result<()> {
+    if args.count != 1 {
+        Err(anyhow!("count option can not be used with a dataset. please set count on each entity"))?
+    }
+
+    match &args.out_dir {
+        Some(out_dir) => {
+            fs::create_dir_all(out_dir)?;
+            for (name, records) in generator.generate_dataset()? {
+                let value = sort_keys_if(Value::Array(records), args.sort_keys);
+                fs::write(out_dir.join(format!("{}.json", name)), serde_json::to_string_pretty(&value)?)?;
+            }
+        }
+        None => {
+            let value = generator.generate()?;
+            println!("{}", serde_json::to_string_pretty(&sort_keys_if(value, args.sort_keys))?);
+        }
+    }
 
     Ok(())
 }
+
+/// Generates fake data `count` times. A single set of fake data is printed as it is, while multiple sets are
+/// printed as an array. When `sort_keys` is set, the keys of the generated data are sorted alphabetically.
+fn output_json(generator: &mut Generator, args: &Args) -> Result<()> {
+    let value = match args.count {
+        1 => generator.generate()?,
+        _ => Value::Array(generator.generate_n(args.count)?),
+    };
+
+    let value = sort_keys_if(value, args.sort_keys);
+
+    let json = serde_json::to_string_pretty(&value)?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Parses a `--set key=value` variable.
+fn parse_variable(variable: &str) -> std::result::Result<(String, String), String> {
+    match variable.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("{} should be written as key=value", variable)),
+    }
+}
+
+/// Parses a probability from 0 to 1.
+fn parse_probability(probability: &str) -> std::result::Result<f64, String> {
+    match probability.parse::<f64>() {
+        Ok(probability) if (0.0..=1.0).contains(&probability) => Ok(probability),
+        _ => Err(format!("{} should be a number from 0 to 1", probability)),
+    }
+}
+
+/// Sorts the keys of the given value when `sort_keys` is set.
+fn sort_keys_if(value: Value, sort_keys: bool) -> Value {
+    match sort_keys {
+        true => sort_value_keys(value),
+        false => value,
+    }
+}
+
+/// Recursively sorts the keys of every object in the given value alphabetically.
+fn sort_value_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().map(|(k, v)| (k, sort_value_keys(v))).collect())
+        }
+        Value::Array(array) => Value::Array(array.into_iter().map(sort_value_keys).collect()),
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{describe_type, format_table, list_types, parse_probability, parse_variable, sort_value_keys, Format};
+    use fake_cli::Registry;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_sort_value_keys() {
+        let value = json!({ "b": 1, "a": [{ "d": 2, "c": 3 }] });
+
+        let sorted = sort_value_keys(value);
+
+        assert_eq!(serde_json::to_string(&sorted).unwrap(), r#"{"a":[{"c":3,"d":2}],"b":1}"#, "Keys should be sorted alphabetically");
+    }
+
+    #[test]
+    fn test_parse_variable() {
+        assert_eq!(parse_variable("url=https://example.com?a=1").unwrap(), ("url".to_string(), "https://example.com?a=1".to_string()));
+        assert!(parse_variable("domain").is_err(), "Should return an error without =");
+        assert!(parse_variable("=value").is_err(), "Should return an error for an empty key");
+    }
+
+    #[test]
+    fn test_parse_probability() {
+        assert_eq!(parse_probability("0.25"), Ok(0.25));
+        assert!(parse_probability("1.5").is_err(), "Should return an error for a probability over 1");
+        assert!(parse_probability("half").is_err());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let table = format_table(&["NAME", "DESCRIPTION"], vec![vec!["word".to_string(), "A word".to_string()], vec!["first_name".to_string(), "".to_string()]]);
+
+        assert_eq!(table, "NAME        DESCRIPTION\nword        A word\nfirst_name", "Columns should be aligned and lines trimmed");
+    }
+
+    #[test]
+    fn test_list_types() {
+        let table = list_types(Registry::builtin(), Format::Table).unwrap();
+        assert!(table.starts_with("NAME"));
+        assert!(table.lines().any(|line| line.starts_with("password") && line.contains("internet")));
+
+        let types: Value = serde_json::from_str(&list_types(Registry::builtin(), Format::Json).unwrap()).unwrap();
+        assert_eq!(types.as_array().unwrap().len(), Registry::builtin().fake_types().count(), "Every fake type should be listed");
+        assert_eq!(types[0], json!({ "name": "word", "category": "lorem", "description": "A lorem ipsum word" }));
+    }
+
+    #[test]
+    fn test_describe_type() {
+        let table = describe_type(Registry::builtin(), "boolean", Format::Table).unwrap();
+        assert!(table.starts_with("boolean (boolean)"));
+        assert!(table.lines().any(|line| line.starts_with("ratio") && line.contains("0 to 100") && line.contains("50")), "Should describe the ratio: {}", table);
+
+        let sentence: Value = serde_json::from_str(&describe_type(Registry::builtin(), "sentence", Format::Json).unwrap()).unwrap();
+        assert_eq!(sentence["params"][1]["name"], json!("min"));
+        assert!(sentence["example_output"].is_string(), "Should generate the example");
+        assert_eq!(describe_type(Registry::builtin(), "sentence", Format::Json).unwrap(), describe_type(Registry::builtin(), "sentence", Format::Json).unwrap(), "The example should be the same every time");
+
+        assert!(describe_type(Registry::builtin(), "undefined", Format::Table).is_err(), "Should return an error for an unknown fake type");
+    }
+}
@@ -7,5 +7,32 @@ pub mod fake_type;
 /// The `fake_definition` module provides the `FakeDefinition` type and associated functionality. A `FakeDefinition` represents an entire set of fake data, derived from one or more `FakeType`s.
 pub mod fake_definition;
 
-/// This private `fake_definition_element` module provides the functionality for parsing and handling elements of a `FakeDefinition`.
-mod fake_definition_element;
\ No newline at end of file
+/// The `dataset` module provides the `Dataset` type, a collection of named entities whose records can refer to each other.
+pub mod dataset;
+
+/// The `registry` module provides the `Registry` mapping every `fake_type` name to the constructor of its definition element, where downstream crates register their own fake types.
+pub mod registry;
+
+/// The `type_info` module provides the `TypeInfo` metadata of a registered fake type, such as its parameters and an example.
+pub mod type_info;
+
+/// The `resolver` module resolves the `$ref`s of a definition to its named `definitions` and to other definition files.
+pub mod resolver;
+
+/// The `substitution` module substitutes `${VAR}` placeholders in a definition with environment variables and `--set` values.
+pub mod substitution;
+
+/// The `generation_context` module provides the `GenerationContext`, the state shared while generating a `FakeDefinition`, such as the values of the fields generated so far.
+pub mod generation_context;
+
+/// The `expression` module provides the `Expression` language used to compute fields from other fields.
+pub mod expression;
+
+/// The `record` module provides the `Record` type, the named fields shared by a `FakeDefinition` and a `Map`, with the record-level options such as `unique_by`.
+pub mod record;
+
+/// The `dependency` module orders the fields of a `FakeDefinition` or `Map` so that fields referring to other fields are generated after them.
+mod dependency;
+
+/// The `fake_definition_element` module provides the functionality for parsing and handling elements of a `FakeDefinition`.
+pub mod fake_definition_element;
\ No newline at end of file
@@ -0,0 +1,208 @@
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::fake::dependency::topological_order;
+use crate::fake::fake_definition::FakeDefinition;
+use crate::fake::generation_context::GenerationContext;
+use crate::fake::registry::Registry;
+
+/// `Dataset` is a collection of named entities, each generating `count` records from its own `FakeDefinition`.
+///
+/// Entities can refer to the records of other entities with the `ref` fake type (e.g. `orders.user_id` referring
+/// to `users.id`), so entities are generated in dependency order and circular references are reported as an error.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::dataset::Dataset;
+/// # use fake_cli::GenerationContext;
+/// # use serde_json::json;
+/// let dataset = Dataset::from_json(&json!({
+///     "entities": {
+///         "users": { "count": 2, "definition": { "id": { "fake_type": "digit", "lang": "EN" } } },
+///         "orders": { "count": 5, "definition": { "user_id": { "fake_type": "ref", "ref": "users.id" } } }
+///     }
+/// })).unwrap();
+/// let records = dataset.generate(&mut GenerationContext::new()).unwrap();
+/// println!("Fake orders: {:?}", records["orders"]);
+/// ```
+#[derive(Debug)]
+pub struct Dataset {
+    entities: IndexMap<String, DatasetEntity>,
+    generation_order: Vec<usize>,
+}
+
+/// `DatasetEntity` is a single entity of a `Dataset`: the number of records and their definition.
+#[derive(Debug)]
+struct DatasetEntity {
+    count: usize,
+    fake_definition: FakeDefinition,
+}
+
+/// `DatasetEntityParams` are the settings of an entity in a dataset definition.
+#[derive(Deserialize)]
+struct DatasetEntityParams {
+    count: usize,
+    definition: Value,
+}
+
+impl Dataset {
+    /// Returns whether `json` is a dataset definition, i.e. it has an `entities` object which is not itself a field definition.
+    pub fn is_dataset(json: &Value) -> bool {
+        match json.get("entities") {
+            Some(Value::Object(entities)) => !entities.get("fake_type").is_some_and(Value::is_string),
+            _ => false,
+        }
+    }
+
+    pub fn from_json(dataset_json: &Value) -> Result<Self> {
+        Dataset::from_json_with_registry(dataset_json, Registry::builtin())
+    }
+
+    /// Parses a `Dataset` from JSON with the fake types registered in `registry`.
+    pub fn from_json_with_registry(dataset_json: &Value, registry: &Registry) -> Result<Self> {
+        let entities_json = dataset_json
+            .get("entities")
+            .and_then(Value::as_object)
+            .ok_or(anyhow!("INVALID JSON FORMAT: dataset should have an entities map"))?;
+
+        let mut entities = IndexMap::new();
+        for (name, entity_json) in entities_json {
+            let params = DatasetEntityParams::deserialize(entity_json).map_err(|e| anyhow!("entity: {}, {}", name, e))?;
+            let fake_definition = FakeDefinition::from_json_with_registry(&params.definition, registry).map_err(|e| anyhow!("entity: {}, {}", name, e))?;
+
+            entities.insert(name.clone(), DatasetEntity { count: params.count, fake_definition });
+        }
+
+        let generation_order = Dataset::generation_order(&entities)?;
+        Ok(Self { entities, generation_order })
+    }
+
+    /// Generates the records of every entity in dependency order. The records are returned in the definition order
+    /// of the entities, and are also recorded in `context`.
+    pub fn generate(&self, context: &mut GenerationContext) -> Result<IndexMap<String, Vec<Value>>> {
+        for &index in &self.generation_order {
+            let (name, entity) = self.entities.get_index(index).ok_or(anyhow!("undefined entity index {}", index))?;
+            let records = (0..entity.count)
+                .map(|record_index| {
+                    context.set_record_index(record_index);
+                    entity.fake_definition.to_value(context)
+                })
+                .collect::<Result<Vec<Value>>>()
+                .map_err(|e| anyhow!("entity: {}, {}", name, e))?;
+
+            context.insert_entity(name.clone(), records);
+        }
+
+        self.entities
+            .keys()
+            .map(|name| {
+                let records = context.entity(name).cloned().ok_or(anyhow!("entity: {}, is not generated", name))?;
+                Ok((name.clone(), records))
+            })
+            .collect()
+    }
+
+    /// Returns the JSON Schema of the generated records of every entity, keyed by entity. The schema of the records of
+    /// each entity is under `definitions`, so that a `ref` refers to the schema of the referenced field.
+    pub fn output_schema(&self) -> Value {
+        let mut properties = serde_json::Map::new();
+        let mut definitions = serde_json::Map::new();
+        for (name, entity) in &self.entities {
+            let items = json!({ "$ref": format!("#/definitions/{}", name.replace('~', "~0").replace('/', "~1")) });
+            properties.insert(name.clone(), json!({ "type": "array", "items": items, "minItems": entity.count, "maxItems": entity.count }));
+            definitions.insert(name.clone(), entity.fake_definition.output_schema());
+        }
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": self.entities.keys().collect::<Vec<&String>>(),
+            "additionalProperties": false,
+            "definitions": definitions
+        })
+    }
+
+    fn generation_order(entities: &IndexMap<String, DatasetEntity>) -> Result<Vec<usize>> {
+        let mut dependencies = Vec::with_capacity(entities.len());
+        for (name, entity) in entities {
+            let entity_dependencies = entity
+                .fake_definition
+                .entity_references()
+                .iter()
+                .map(|reference| entities.get_index_of(reference.as_str()).ok_or(anyhow!("entity: {}, refers to undefined entity {}", name, reference)))
+                .collect::<Result<Vec<usize>>>()?;
+            dependencies.push(entity_dependencies);
+        }
+
+        topological_order(&dependencies).map_err(|cycle| {
+            let names: Vec<&str> = cycle.iter().map(|i| entities.get_index(*i).unwrap().0.as_str()).collect();
+            anyhow!("circular reference between entities: {}", names.join(" -> "))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dataset;
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::json;
+
+    #[test]
+    fn test_dataset_generate() {
+        let dataset_json = json!({
+            "entities": {
+                "orders": {
+                    "count": 6,
+                    "definition": {
+                        "id": { "fake_type": "digit", "lang": "EN" },
+                        "user_id": { "fake_type": "ref", "ref": "users.id", "cardinality": "1:N" }
+                    }
+                },
+                "users": {
+                    "count": 3,
+                    "definition": {
+                        "id": { "fake_type": "number_with_format", "lang": "EN", "format": "U-####" }
+                    }
+                }
+            }
+        });
+
+        let dataset = Dataset::from_json(&dataset_json).unwrap();
+        let records = dataset.generate(&mut GenerationContext::new()).unwrap();
+
+        assert_eq!(records.keys().collect::<Vec<_>>(), vec!["orders", "users"], "Entities should keep the definition order");
+        assert_eq!(records["orders"].len(), 6);
+        assert_eq!(records["users"].len(), 3);
+        for user in &records["users"] {
+            let referenced = records["orders"].iter().filter(|order| order["user_id"] == user["id"]).count();
+            assert_eq!(referenced, 2, "Every user should be referenced by the same number of orders");
+        }
+    }
+
+    #[test]
+    fn test_dataset_from_json_with_invalid_references() {
+        let undefined = json!({
+            "entities": {
+                "orders": { "count": 1, "definition": { "user_id": { "fake_type": "ref", "ref": "users.id" } } }
+            }
+        });
+        assert!(Dataset::from_json(&undefined).is_err(), "Should return an error for an undefined entity");
+
+        let circular = json!({
+            "entities": {
+                "a": { "count": 1, "definition": { "b_id": { "fake_type": "ref", "ref": "b.id" } } },
+                "b": { "count": 1, "definition": { "a_id": { "fake_type": "ref", "ref": "a.id" } } }
+            }
+        });
+        assert!(Dataset::from_json(&circular).is_err(), "Should return an error for circular references");
+    }
+
+    #[test]
+    fn test_dataset_is_dataset() {
+        assert!(Dataset::is_dataset(&json!({ "entities": { "users": { "count": 1, "definition": {} } } })));
+        assert!(!Dataset::is_dataset(&json!({ "entities": { "fake_type": "word", "lang": "EN" } })), "A field named entities should not be a dataset");
+        assert!(!Dataset::is_dataset(&json!({ "name": { "fake_type": "word", "lang": "EN" } })));
+    }
+}
@@ -0,0 +1,167 @@
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use serde_json::Value;
+use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
+
+/// Returns the indexes of `fake_definition_elements` in the order they have to be generated.
+///
+/// A field which refers to a sibling field (e.g. through a template placeholder) is generated after it.
+/// Fields without dependencies keep their definition order. Circular references are reported as an error.
+pub fn evaluation_order(fake_definition_elements: &IndexMap<String, FakeDefinitionElement>) -> Result<Vec<usize>> {
+    let dependencies: Vec<Vec<usize>> = fake_definition_elements
+        .values()
+        .map(|fake_definition_element| {
+            fake_definition_element
+                .references()
+                .iter()
+                .filter_map(|reference| fake_definition_elements.get_index_of(reference.as_str()))
+                .collect()
+        })
+        .collect();
+
+    topological_order(&dependencies).map_err(|cycle| {
+        let keys: Vec<&str> = cycle.iter().map(|i| fake_definition_elements.get_index(*i).unwrap().0.as_str()).collect();
+        anyhow!("circular reference between fields: {}", keys.join(" -> "))
+    })
+}
+
+/// Sorts the indexes `0..dependencies.len()` so that every index comes after the indexes it depends on.
+/// Indexes without dependencies keep their order. On a cycle, returns the indexes forming it.
+pub fn topological_order(dependencies: &[Vec<usize>]) -> std::result::Result<Vec<usize>, Vec<usize>> {
+    let mut order = Vec::with_capacity(dependencies.len());
+    let mut states = vec![VisitState::Unvisited; dependencies.len()];
+    let mut path = Vec::new();
+    for index in 0..dependencies.len() {
+        visit(index, dependencies, &mut states, &mut path, &mut order)?;
+    }
+
+    Ok(order)
+}
+
+/// Returns the names referred to by `fake_definition_elements` which are not one of their own keys,
+/// i.e. the names which have to be resolved by an enclosing map.
+pub fn unresolved_references(fake_definition_elements: &IndexMap<String, FakeDefinitionElement>) -> Vec<String> {
+    let mut references: Vec<String> = Vec::new();
+    for reference in fake_definition_elements.values().flat_map(|e| e.references()) {
+        if !fake_definition_elements.contains_key(reference.as_str()) && !references.contains(&reference) {
+            references.push(reference);
+        }
+    }
+
+    references
+}
+
+/// Generates the values of `fake_definition_elements` in the given `order` within a new scope of `context`,
+/// and returns them in their definition order. Omitted fields are left out, and are `null` to the fields referring to them.
+pub fn generate_fields(fake_definition_elements: &IndexMap<String, FakeDefinitionElement>, order: &[usize], context: &mut GenerationContext) -> Result<serde_json::Map<String, Value>> {
+    context.push_scope();
+    let values = generate_values(fake_definition_elements, order, context);
+    context.pop_scope();
+
+    Ok(fake_definition_elements
+        .keys()
+        .zip(values?)
+        .filter_map(|(key, value)| value.map(|value| (key.clone(), value)))
+        .collect())
+}
+
+fn generate_values(fake_definition_elements: &IndexMap<String, FakeDefinitionElement>, order: &[usize], context: &mut GenerationContext) -> Result<Vec<Option<Value>>> {
+    let mut values = vec![None; fake_definition_elements.len()];
+    for &index in order {
+        let (key, fake_definition_element) = fake_definition_elements.get_index(index).ok_or(anyhow!("undefined field index {}", index))?;
+        context.push_path(key.clone());
+        let value = fake_definition_element.to_field_value(context);
+        context.pop_path();
+        let value = value?;
+        context.insert(key.clone(), value.clone().unwrap_or(Value::Null));
+        values[index] = value;
+    }
+
+    Ok(values)
+}
+
+#[derive(Clone, PartialEq)]
+enum VisitState {
+    Unvisited,
+    Visiting,
+    Visited,
+}
+
+/// Depth-first topological sort. On a cycle, returns the indexes forming it.
+fn visit(index: usize, dependencies: &[Vec<usize>], states: &mut [VisitState], path: &mut Vec<usize>, order: &mut Vec<usize>) -> std::result::Result<(), Vec<usize>> {
+    match states[index] {
+        VisitState::Visited => return Ok(()),
+        VisitState::Visiting => {
+            let start = path.iter().position(|i| *i == index).unwrap_or(0);
+            let mut cycle = path[start..].to_vec();
+            cycle.push(index);
+            return Err(cycle);
+        }
+        VisitState::Unvisited => (),
+    }
+
+    states[index] = VisitState::Visiting;
+    path.push(index);
+    for dependency in &dependencies[index] {
+        visit(*dependency, dependencies, states, path, order)?;
+    }
+    path.pop();
+    states[index] = VisitState::Visited;
+    order.push(index);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluation_order, generate_fields, unresolved_references};
+    use crate::fake::fake_definition_element::FakeDefinitionElement;
+    use crate::fake::generation_context::GenerationContext;
+    use indexmap::IndexMap;
+    use serde_json::{json, Value};
+
+    fn fields(json: Value) -> IndexMap<String, FakeDefinitionElement> {
+        json.as_object()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), FakeDefinitionElement::generate(v).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluation_order() {
+        let fields = fields(json!({
+            "email": { "fake_type": "template", "template": "{{user}}@example.com" },
+            "user": { "fake_type": "template", "template": "{{name | lower}}" },
+            "name": { "fake_type": "constant", "value": "Taro" }
+        }));
+
+        assert_eq!(evaluation_order(&fields).unwrap(), vec![2, 1, 0], "Referenced fields should be generated first");
+
+        let value = generate_fields(&fields, &[2, 1, 0], &mut GenerationContext::new()).unwrap();
+        assert_eq!(Value::Object(value), json!({ "email": "taro@example.com", "user": "taro", "name": "Taro" }), "Values should keep the definition order");
+    }
+
+    #[test]
+    fn test_evaluation_order_with_cycle() {
+        let fields = fields(json!({
+            "a": { "fake_type": "template", "template": "{{b}}" },
+            "b": { "fake_type": "template", "template": "{{a}}" }
+        }));
+
+        let order = evaluation_order(&fields);
+        assert!(order.is_err(), "Should return an error for circular references");
+        assert!(order.unwrap_err().to_string().contains("a -> b -> a"), "Error should name the cycle");
+    }
+
+    #[test]
+    fn test_unresolved_references() {
+        let fields = fields(json!({
+            "a": { "fake_type": "template", "template": "{{b}} {{parent}}" },
+            "b": { "fake_type": "constant", "value": 1 }
+        }));
+
+        assert_eq!(unresolved_references(&fields), vec!["parent".to_string()]);
+    }
+}
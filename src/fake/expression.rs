@@ -0,0 +1,483 @@
+use std::cmp::Ordering;
+use std::fmt;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use crate::fake::expression::function::Function;
+use crate::fake::generation_context::GenerationContext;
+
+/// The `parser` module turns the text of an expression into its syntax tree.
+mod parser;
+
+/// The `function` module provides the built-in functions, such as `round` or `days_between`.
+mod function;
+
+/// `ValueType` is the type of a value, as far as it is known before generating.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueType {
+    Number,
+    String,
+    Boolean,
+    Array,
+    Object,
+    Null,
+    Any,
+}
+
+/// `Expression` is a parsed expression over the values of other fields, such as `price * quantity`,
+/// `end_date > start_date` or `years_between(birth_date, today())`.
+///
+/// It supports number, string, boolean and `null` literals, field paths (e.g. `address.city`),
+/// arithmetic (`+ - * / %`), string concatenation (`+` with a string), comparison (`== != < <= > >=`),
+/// logical operators (`&& || !`, or `and or not`) and the built-in functions of the `function` module.
+/// Dates are `YYYY-MM-DD` strings, so they compare in chronological order.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::expression::Expression;
+/// # use fake_cli::GenerationContext;
+/// # use serde_json::json;
+/// let expression = Expression::parse("price * quantity").unwrap();
+/// let mut context = GenerationContext::new();
+/// context.push_scope();
+/// context.insert("price".to_string(), json!(120));
+/// context.insert("quantity".to_string(), json!(3));
+/// assert_eq!(expression.evaluate(&mut context).unwrap(), json!(360));
+/// ```
+#[derive(Debug)]
+pub struct Expression {
+    source: String,
+    node: Node,
+}
+
+/// `Node` is a node of the syntax tree of an `Expression`.
+#[derive(Debug, PartialEq)]
+enum Node {
+    Literal(Value),
+    Path(Vec<String>),
+    Unary(UnaryOperator, Box<Node>),
+    Binary(BinaryOperator, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UnaryOperator {
+    Negate,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    And,
+    Or,
+}
+
+impl Expression {
+    /// Returns the source the expression was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Parses `source`. Returns an error on a syntax error, an undefined function or a wrong number of arguments.
+    pub fn parse(source: &str) -> Result<Self> {
+        let node = parser::parse(source).map_err(|e| anyhow!("expression \"{}\", {}", source, e))?;
+        Ok(Self { source: source.to_string(), node })
+    }
+
+    /// Returns the names of the fields the expression refers to (the first segment of each path).
+    pub fn references(&self) -> Vec<String> {
+        let mut references = Vec::new();
+        self.node.collect_references(&mut references);
+        references
+    }
+
+    /// Infers the type of the expression from the types of the fields it refers to, given by `field_type`.
+    /// Returns an error when an operator or function is applied to values of the wrong type.
+    pub fn infer(&self, field_type: &dyn Fn(&str) -> Option<ValueType>) -> Result<ValueType> {
+        self.node.infer(field_type).map_err(|e| anyhow!("expression \"{}\", {}", self.source, e))
+    }
+
+    /// Evaluates the expression against the values already generated in `context`.
+    pub fn evaluate(&self, context: &mut GenerationContext) -> Result<Value> {
+        self.node.evaluate(context).map_err(|e| anyhow!("expression \"{}\", {}", self.source, e))
+    }
+}
+
+impl Node {
+    fn collect_references(&self, references: &mut Vec<String>) {
+        match self {
+            Node::Literal(_) => (),
+            Node::Path(path) => {
+                if let Some(first) = path.first() {
+                    if !references.contains(first) {
+                        references.push(first.clone());
+                    }
+                }
+            }
+            Node::Unary(_, operand) => operand.collect_references(references),
+            Node::Binary(_, left, right) => {
+                left.collect_references(references);
+                right.collect_references(references);
+            }
+            Node::Call(_, arguments) => arguments.iter().for_each(|argument| argument.collect_references(references)),
+        }
+    }
+
+    fn infer(&self, field_type: &dyn Fn(&str) -> Option<ValueType>) -> Result<ValueType> {
+        match self {
+            Node::Literal(value) => Ok(ValueType::of(value)),
+            Node::Path(path) => Ok(match path.as_slice() {
+                [name] => field_type(name).or_else(|| variable_type(name)).unwrap_or(ValueType::Any),
+                _ => ValueType::Any,
+            }),
+            Node::Unary(operator, operand) => {
+                let operand_type = operand.infer(field_type)?;
+                let expected = match operator {
+                    UnaryOperator::Negate => ValueType::Number,
+                    UnaryOperator::Not => ValueType::Boolean,
+                };
+                match operand_type.is(expected) {
+                    true => Ok(expected),
+                    false => Err(anyhow!("operator {} expects a {} but got a {}", operator, expected, operand_type)),
+                }
+            }
+            Node::Binary(operator, left, right) => operator.infer(left.infer(field_type)?, right.infer(field_type)?),
+            Node::Call(function, arguments) => {
+                let argument_types = arguments.iter().map(|argument| argument.infer(field_type)).collect::<Result<Vec<ValueType>>>()?;
+                function.infer(&argument_types)
+            }
+        }
+    }
+
+    fn evaluate(&self, context: &mut GenerationContext) -> Result<Value> {
+        match self {
+            Node::Literal(value) => Ok(value.clone()),
+            Node::Path(path) => match context.lookup(path) {
+                Some(value) => Ok(value.clone()),
+                None => match path.as_slice() {
+                    [name] if GenerationContext::is_variable(name) => Ok(context.variable(name).unwrap_or(Value::Null)),
+                    _ => Err(anyhow!("{} is undefined", path.join("."))),
+                },
+            },
+            Node::Unary(UnaryOperator::Negate, operand) => {
+                let value = operand.evaluate(context)?;
+                match (value.as_i64(), value.as_f64()) {
+                    (Some(number), _) if number != i64::MIN => Ok(Value::from(-number)),
+                    (_, Some(number)) => number_value(-number),
+                    _ => Err(anyhow!("operator - expects a number but got {}", value)),
+                }
+            }
+            Node::Unary(UnaryOperator::Not, operand) => {
+                let value = operand.evaluate(context)?;
+                Ok(Value::Bool(!boolean(&value, "!")?))
+            }
+            Node::Binary(BinaryOperator::And, left, right) => {
+                match boolean(&left.evaluate(context)?, "&&")? {
+                    true => Ok(Value::Bool(boolean(&right.evaluate(context)?, "&&")?)),
+                    false => Ok(Value::Bool(false)),
+                }
+            }
+            Node::Binary(BinaryOperator::Or, left, right) => {
+                match boolean(&left.evaluate(context)?, "||")? {
+                    true => Ok(Value::Bool(true)),
+                    false => Ok(Value::Bool(boolean(&right.evaluate(context)?, "||")?)),
+                }
+            }
+            Node::Binary(operator, left, right) => {
+                let left = left.evaluate(context)?;
+                let right = right.evaluate(context)?;
+                operator.apply(&left, &right)
+            }
+            Node::Call(function, arguments) => {
+                let arguments = arguments.iter().map(|argument| argument.evaluate(context)).collect::<Result<Vec<Value>>>()?;
+                function.call(&arguments, context.rng())
+            }
+        }
+    }
+}
+
+impl BinaryOperator {
+    fn infer(&self, left: ValueType, right: ValueType) -> Result<ValueType> {
+        let mismatch = || anyhow!("operator {} can not be applied to a {} and a {}", self, left, right);
+
+        match self {
+            BinaryOperator::Add => match (left, right) {
+                (ValueType::String, _) | (_, ValueType::String) => Ok(ValueType::String),
+                (ValueType::Number, ValueType::Number) => Ok(ValueType::Number),
+                _ if left.is(ValueType::Number) && right.is(ValueType::Number) => Ok(ValueType::Any),
+                _ => Err(mismatch()),
+            },
+            BinaryOperator::Subtract | BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Remainder => {
+                match left.is(ValueType::Number) && right.is(ValueType::Number) {
+                    true => Ok(ValueType::Number),
+                    false => Err(mismatch()),
+                }
+            }
+            BinaryOperator::Equal | BinaryOperator::NotEqual => Ok(ValueType::Boolean),
+            BinaryOperator::Less | BinaryOperator::LessEqual | BinaryOperator::Greater | BinaryOperator::GreaterEqual => {
+                let comparable = [ValueType::Number, ValueType::String].iter().any(|t| left.is(*t) && right.is(*t));
+                match comparable {
+                    true => Ok(ValueType::Boolean),
+                    false => Err(mismatch()),
+                }
+            }
+            BinaryOperator::And | BinaryOperator::Or => match left.is(ValueType::Boolean) && right.is(ValueType::Boolean) {
+                true => Ok(ValueType::Boolean),
+                false => Err(mismatch()),
+            },
+        }
+    }
+
+    fn apply(&self, left: &Value, right: &Value) -> Result<Value> {
+        let mismatch = || anyhow!("operator {} can not be applied to {} and {}", self, left, right);
+
+        match self {
+            BinaryOperator::Add if left.is_string() || right.is_string() => Ok(Value::String(text(left) + &text(right))),
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Remainder => {
+                match (left.is_number(), right.is_number()) {
+                    (true, true) => self.arithmetic(left, right),
+                    _ => Err(mismatch()),
+                }
+            }
+            BinaryOperator::Equal => Ok(Value::Bool(equals(left, right))),
+            BinaryOperator::NotEqual => Ok(Value::Bool(!equals(left, right))),
+            BinaryOperator::Less | BinaryOperator::LessEqual | BinaryOperator::Greater | BinaryOperator::GreaterEqual => {
+                let ordering = compare(left, right).ok_or_else(mismatch)?;
+                Ok(Value::Bool(match self {
+                    BinaryOperator::Less => ordering == Ordering::Less,
+                    BinaryOperator::LessEqual => ordering != Ordering::Greater,
+                    BinaryOperator::Greater => ordering == Ordering::Greater,
+                    _ => ordering != Ordering::Less,
+                }))
+            }
+            BinaryOperator::And | BinaryOperator::Or => Ok(Value::Bool(boolean(left, &self.to_string())? && boolean(right, &self.to_string())?)),
+        }
+    }
+
+    /// Applies an arithmetic operator. Integers stay integers unless the result is fractional or overflows.
+    fn arithmetic(&self, left: &Value, right: &Value) -> Result<Value> {
+        if let (Some(l), Some(r)) = (left.as_i64(), right.as_i64()) {
+            let integer = match self {
+                BinaryOperator::Add => l.checked_add(r),
+                BinaryOperator::Subtract => l.checked_sub(r),
+                BinaryOperator::Multiply => l.checked_mul(r),
+                BinaryOperator::Divide if l.checked_rem(r) == Some(0) => l.checked_div(r),
+                BinaryOperator::Remainder if r == -1 => Some(0),
+                BinaryOperator::Remainder => l.checked_rem(r),
+                _ => None,
+            };
+            if let Some(integer) = integer {
+                return Ok(Value::from(integer));
+            }
+        }
+
+        let l = left.as_f64().unwrap_or_default();
+        let r = right.as_f64().unwrap_or_default();
+        if r == 0.0 && matches!(self, BinaryOperator::Divide | BinaryOperator::Remainder) {
+            Err(anyhow!("division by zero"))?
+        }
+
+        number_value(match self {
+            BinaryOperator::Add => l + r,
+            BinaryOperator::Subtract => l - r,
+            BinaryOperator::Multiply => l * r,
+            BinaryOperator::Divide => l / r,
+            _ => l % r,
+        })
+    }
+}
+
+impl ValueType {
+    /// Returns the type of `value`.
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Number(_) => ValueType::Number,
+            Value::String(_) => ValueType::String,
+            Value::Bool(_) => ValueType::Boolean,
+            Value::Array(_) => ValueType::Array,
+            Value::Object(_) => ValueType::Object,
+            Value::Null => ValueType::Null,
+        }
+    }
+
+    /// Returns whether a value of this type can be used where `expected` is expected.
+    /// `Any` is only known when generating, so it is accepted everywhere.
+    pub fn is(&self, expected: ValueType) -> bool {
+        *self == expected || *self == ValueType::Any || expected == ValueType::Any
+    }
+
+    /// Returns the JSON Schema of the values of this type, which accepts any value for `Any`.
+    pub fn output_schema(&self) -> Value {
+        match self {
+            ValueType::Any => json!({}),
+            value_type => json!({ "type": value_type.to_string() }),
+        }
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ValueType::Number => "number",
+            ValueType::String => "string",
+            ValueType::Boolean => "boolean",
+            ValueType::Array => "array",
+            ValueType::Object => "object",
+            ValueType::Null => "null",
+            ValueType::Any => "any",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnaryOperator::Negate => write!(f, "-"),
+            UnaryOperator::Not => write!(f, "!"),
+        }
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Remainder => "%",
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::Less => "<",
+            BinaryOperator::LessEqual => "<=",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::GreaterEqual => ">=",
+            BinaryOperator::And => "&&",
+            BinaryOperator::Or => "||",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Returns the type of a context variable.
+fn variable_type(name: &str) -> Option<ValueType> {
+    match name {
+        "index" | "record_index" | "seed" => Some(ValueType::Number),
+        "path" | "parent_path" => Some(ValueType::String),
+        "array_index" => Some(ValueType::Any),
+        _ => None,
+    }
+}
+
+fn boolean(value: &Value, operator: &str) -> Result<bool> {
+    value.as_bool().ok_or(anyhow!("operator {} expects a boolean but got {}", operator, value))
+}
+
+fn number_value(number: f64) -> Result<Value> {
+    serde_json::Number::from_f64(number).map(Value::Number).ok_or(anyhow!("{} is not a valid number", number))
+}
+
+/// Renders a value for string concatenation. Strings are inserted as is, `null` as an empty string, other values as JSON.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Compares two values for equality, comparing numbers by their value regardless of integer or float.
+fn equals(left: &Value, right: &Value) -> bool {
+    match (left.as_f64(), right.as_f64()) {
+        (Some(l), Some(r)) => l == r,
+        _ => left == right,
+    }
+}
+
+/// Orders two numbers or two strings. Returns `None` for any other values.
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64()?.partial_cmp(&r.as_f64()?),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Expression, ValueType};
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::{json, Value};
+
+    fn evaluate(source: &str, fields: Value) -> Value {
+        let mut context = GenerationContext::new();
+        context.push_scope();
+        for (key, value) in fields.as_object().unwrap() {
+            context.insert(key.clone(), value.clone());
+        }
+
+        Expression::parse(source).unwrap().evaluate(&mut context).unwrap()
+    }
+
+    #[test]
+    fn test_expression_evaluate() {
+        assert_eq!(evaluate("price * quantity + 1", json!({ "price": 120, "quantity": 3 })), json!(361));
+        assert_eq!(evaluate("(1 + 2) * 3 - 10 / 4", json!({})), json!(6.5));
+        assert_eq!(evaluate("7 % 3 == 1 && !(2 > 3)", json!({})), json!(true));
+        assert_eq!(evaluate("first + ' ' + last + 1", json!({ "first": "Taro", "last": "Yamada" })), json!("Taro Yamada1"));
+        assert_eq!(evaluate("age >= 18 or name == null", json!({ "age": 20, "name": "Taro" })), json!(true));
+        assert_eq!(evaluate("address.city + '/' + index", json!({ "address": { "city": "Tokyo" } })), json!("Tokyo/0"));
+        assert_eq!(evaluate("end_date > start_date", json!({ "start_date": "2024-01-31", "end_date": "2024-02-01" })), json!(true));
+        assert_eq!(evaluate("a / -1", json!({ "a": i64::MIN })), json!(9223372036854775808.0), "Should fall back to a float instead of overflowing");
+        assert_eq!(evaluate("a % -1", json!({ "a": i64::MIN })), json!(0));
+        assert_eq!(evaluate("7 % -1", json!({})), json!(0));
+    }
+
+    #[test]
+    fn test_expression_evaluate_fail() {
+        let mut context = GenerationContext::new();
+
+        assert!(Expression::parse("1 / 0").unwrap().evaluate(&mut context).is_err(), "Should return an error for a division by zero");
+        assert!(Expression::parse("undefined_field + 1").unwrap().evaluate(&mut context).is_err(), "Should return an error for an undefined field");
+        assert!(Expression::parse("1 < 'a'").unwrap().evaluate(&mut context).is_err(), "Should return an error for incomparable values");
+    }
+
+    #[test]
+    fn test_expression_references() {
+        let expression = Expression::parse("price * quantity + price + address.zip + round(tax)").unwrap();
+
+        assert_eq!(expression.references(), vec!["price", "quantity", "address", "tax"]);
+    }
+
+    #[test]
+    fn test_expression_infer() {
+        let field_type = |name: &str| match name {
+            "price" => Some(ValueType::Number),
+            "name" => Some(ValueType::String),
+            "active" => Some(ValueType::Boolean),
+            _ => None,
+        };
+        let infer = |source: &str| Expression::parse(source).unwrap().infer(&field_type);
+
+        assert_eq!(infer("price * 2").unwrap(), ValueType::Number);
+        assert_eq!(infer("name + price").unwrap(), ValueType::String);
+        assert_eq!(infer("price > 10 && active").unwrap(), ValueType::Boolean);
+        assert_eq!(infer("parent_field * 2").unwrap(), ValueType::Number, "Unknown fields should be accepted");
+        assert!(infer("name * 2").is_err(), "Should return an error for arithmetic on a string");
+        assert!(infer("price > name").is_err(), "Should return an error for comparing a number with a string");
+        assert!(infer("!price").is_err(), "Should return an error for negating a number");
+        assert!(infer("active || 1").is_err(), "Should return an error for a non-boolean operand");
+    }
+}
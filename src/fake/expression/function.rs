@@ -0,0 +1,344 @@
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{anyhow, Result};
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde_json::Value;
+use crate::fake::expression::{number_value, text, ValueType};
+
+/// The years of the dates the date functions return, which are written as `YYYY-MM-DD` so that they can be read back.
+const YEARS: RangeInclusive<i64> = 0..=9999;
+
+/// `Function` is a built-in function of an expression.
+///
+/// * Numbers: `abs(n)`, `round(n)`, `floor(n)`, `ceil(n)`, `min(a, b)`, `max(a, b)`, `random()`
+/// * Strings: `len(s)`, `lower(s)`, `upper(s)`, `str(value)`
+/// * Conditions: `if(condition, then, else)`
+/// * Dates (`YYYY-MM-DD` strings): `today()`, `date(year, month, day)`, `year(d)`, `month(d)`, `day(d)`,
+///   `add_days(d, n)`, `add_months(d, n)`, `add_years(d, n)`, `days_between(from, to)`, `years_between(from, to)`
+///   and `age(birth_date)`
+///
+/// `date`, `add_days`, `add_months` and `add_years` return an error when the resulting date is not within the years 0
+/// to 9999.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    Abs,
+    Round,
+    Floor,
+    Ceil,
+    Min,
+    Max,
+    Random,
+    Len,
+    Lower,
+    Upper,
+    Str,
+    If,
+    Today,
+    Date,
+    Year,
+    Month,
+    Day,
+    AddDays,
+    AddMonths,
+    AddYears,
+    DaysBetween,
+    YearsBetween,
+    Age,
+}
+
+impl Function {
+    /// Looks up the function `name`. Returns an error when it is undefined or takes a different number of arguments.
+    pub fn new(name: &str, arity: usize) -> Result<Self> {
+        let function = match name {
+            "abs" => Function::Abs,
+            "round" => Function::Round,
+            "floor" => Function::Floor,
+            "ceil" => Function::Ceil,
+            "min" => Function::Min,
+            "max" => Function::Max,
+            "random" => Function::Random,
+            "len" => Function::Len,
+            "lower" => Function::Lower,
+            "upper" => Function::Upper,
+            "str" => Function::Str,
+            "if" => Function::If,
+            "today" => Function::Today,
+            "date" => Function::Date,
+            "year" => Function::Year,
+            "month" => Function::Month,
+            "day" => Function::Day,
+            "add_days" => Function::AddDays,
+            "add_months" => Function::AddMonths,
+            "add_years" => Function::AddYears,
+            "days_between" => Function::DaysBetween,
+            "years_between" => Function::YearsBetween,
+            "age" => Function::Age,
+            _ => Err(anyhow!("undefined function {}", name))?,
+        };
+
+        let parameters = function.parameters();
+        if parameters.len() != arity {
+            Err(anyhow!("function {} takes {} arguments but got {}", name, parameters.len(), arity))?
+        }
+
+        Ok(function)
+    }
+
+    /// Returns the types of the parameters.
+    fn parameters(&self) -> Vec<ValueType> {
+        use ValueType::{Any, Boolean, Number, String};
+        match self {
+            Function::Abs | Function::Round | Function::Floor | Function::Ceil => vec![Number],
+            Function::Min | Function::Max => vec![Number, Number],
+            Function::Len | Function::Lower | Function::Upper => vec![String],
+            Function::Str => vec![Any],
+            Function::If => vec![Boolean, Any, Any],
+            Function::Today | Function::Random => vec![],
+            Function::Date => vec![Number, Number, Number],
+            Function::Year | Function::Month | Function::Day | Function::Age => vec![String],
+            Function::AddDays | Function::AddMonths | Function::AddYears => vec![String, Number],
+            Function::DaysBetween | Function::YearsBetween => vec![String, String],
+        }
+    }
+
+    /// Checks the types of the arguments and returns the type of the result.
+    pub fn infer(&self, arguments: &[ValueType]) -> Result<ValueType> {
+        for (index, (argument, parameter)) in arguments.iter().zip(self.parameters()).enumerate() {
+            if !argument.is(parameter) {
+                Err(anyhow!("argument {} of {} should be a {} but got a {}", index + 1, self, parameter, argument))?
+            }
+        }
+
+        Ok(match self {
+            Function::Abs | Function::Round | Function::Floor | Function::Ceil | Function::Min | Function::Max | Function::Random | Function::Len => ValueType::Number,
+            Function::Lower | Function::Upper | Function::Str => ValueType::String,
+            Function::If if arguments[1] == arguments[2] => arguments[1],
+            Function::If => ValueType::Any,
+            Function::Today | Function::Date | Function::AddDays | Function::AddMonths | Function::AddYears => ValueType::String,
+            Function::Year | Function::Month | Function::Day | Function::DaysBetween | Function::YearsBetween | Function::Age => ValueType::Number,
+        })
+    }
+
+    /// Calls the function with the evaluated arguments. `random()` draws from `rng`.
+    pub fn call(&self, arguments: &[Value], rng: &mut StdRng) -> Result<Value> {
+        let number = |index: usize| arguments[index].as_f64().ok_or(anyhow!("argument {} of {} should be a number but got {}", index + 1, self, arguments[index]));
+        let integer = |index: usize| arguments[index].as_i64().ok_or(anyhow!("argument {} of {} should be an integer but got {}", index + 1, self, arguments[index]));
+        let string = |index: usize| arguments[index].as_str().ok_or(anyhow!("argument {} of {} should be a string but got {}", index + 1, self, arguments[index]));
+        let date = |index: usize| string(index).and_then(CivilDate::parse);
+        let out_of_range = || anyhow!("the date {} returns is out of range", self);
+
+        match self {
+            Function::Abs => match arguments[0].as_i64() {
+                Some(n) if n != i64::MIN => Ok(Value::from(n.abs())),
+                _ => number_value(number(0)?.abs()),
+            },
+            Function::Round => integer_value(number(0)?.round()),
+            Function::Floor => integer_value(number(0)?.floor()),
+            Function::Ceil => integer_value(number(0)?.ceil()),
+            Function::Min => Ok(if number(0)? <= number(1)? { arguments[0].clone() } else { arguments[1].clone() }),
+            Function::Max => Ok(if number(0)? >= number(1)? { arguments[0].clone() } else { arguments[1].clone() }),
+            Function::Random => number_value(rng.gen::<f64>()),
+            Function::Len => Ok(Value::from(string(0)?.chars().count())),
+            Function::Lower => Ok(Value::String(string(0)?.to_lowercase())),
+            Function::Upper => Ok(Value::String(string(0)?.to_uppercase())),
+            Function::Str => Ok(Value::String(text(&arguments[0]))),
+            Function::If => match arguments[0].as_bool() {
+                Some(true) => Ok(arguments[1].clone()),
+                Some(false) => Ok(arguments[2].clone()),
+                None => Err(anyhow!("argument 1 of if should be a boolean but got {}", arguments[0])),
+            },
+            Function::Today => Ok(CivilDate::today().to_value()),
+            Function::Date => Ok(CivilDate::new(integer(0)?, integer(1)?, integer(2)?)?.to_value()),
+            Function::Year => Ok(Value::from(date(0)?.year)),
+            Function::Month => Ok(Value::from(date(0)?.month)),
+            Function::Day => Ok(Value::from(date(0)?.day)),
+            Function::AddDays => Ok(date(0)?.add_days(integer(1)?).ok_or_else(out_of_range)?.to_value()),
+            Function::AddMonths => Ok(date(0)?.add_months(integer(1)?).ok_or_else(out_of_range)?.to_value()),
+            Function::AddYears => {
+                let months = integer(1)?.checked_mul(12).ok_or_else(out_of_range)?;
+                Ok(date(0)?.add_months(months).ok_or_else(out_of_range)?.to_value())
+            }
+            Function::DaysBetween => Ok(Value::from(date(1)?.days() - date(0)?.days())),
+            Function::YearsBetween => Ok(Value::from(date(0)?.years_until(&date(1)?))),
+            Function::Age => Ok(Value::from(date(0)?.years_until(&CivilDate::today()))),
+        }
+    }
+}
+
+impl std::fmt::Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = format!("{:?}", self);
+        let mut snake_case = String::new();
+        for (index, c) in name.chars().enumerate() {
+            if c.is_uppercase() && index > 0 {
+                snake_case.push('_');
+            }
+            snake_case.push(c.to_ascii_lowercase());
+        }
+        write!(f, "{}", snake_case)
+    }
+}
+
+fn integer_value(number: f64) -> Result<Value> {
+    match number.is_finite() && number.abs() < i64::MAX as f64 {
+        true => Ok(Value::from(number as i64)),
+        false => number_value(number),
+    }
+}
+
+/// `CivilDate` is a date of the proleptic Gregorian calendar.
+#[derive(Debug, PartialEq)]
+struct CivilDate {
+    year: i64,
+    month: i64,
+    day: i64,
+}
+
+impl CivilDate {
+    /// Creates a date. Returns an error for an invalid date, or a year which is not within `YEARS`.
+    fn new(year: i64, month: i64, day: i64) -> Result<Self> {
+        match YEARS.contains(&year) && (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day) {
+            true => Ok(Self { year, month, day }),
+            false => Err(anyhow!("{:04}-{:02}-{:02} is not a valid date", year, month, day)),
+        }
+    }
+
+    /// Parses a `YYYY-MM-DD` date. A time following the date (e.g. `2024-01-31T12:00:00Z`) is ignored.
+    fn parse(value: &str) -> Result<Self> {
+        let error = || anyhow!("{} is not a YYYY-MM-DD date", value);
+        let date = value.get(..10).ok_or_else(error)?;
+        let mut parts = date.split('-').map(|part| part.parse::<i64>().map_err(|_| error()));
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(year), Some(month), Some(day), None) => CivilDate::new(year?, month?, day?),
+            _ => Err(error()),
+        }
+    }
+
+    fn today() -> Self {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+        CivilDate::from_days((seconds / 86_400) as i64)
+    }
+
+    /// Returns the number of days since 1970-01-01.
+    fn days(&self) -> i64 {
+        // Howard Hinnant's days_from_civil algorithm.
+        let year = if self.month <= 2 { self.year - 1 } else { self.year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((self.month + 9) % 12) + 2) / 5 + self.day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Returns the date `days` days after 1970-01-01.
+    fn from_days(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days algorithm.
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Self { year, month, day }
+    }
+
+    /// Adds `days`. Returns `None` when the resulting year is not within `YEARS`.
+    fn add_days(&self, days: i64) -> Option<Self> {
+        // Days beyond ten thousand years of 1970 are out of range anyway, and would overflow `from_days`.
+        let date = CivilDate::from_days(self.days().checked_add(days).filter(|days| days.unsigned_abs() < 10_000 * 366)?);
+        YEARS.contains(&date.year).then_some(date)
+    }
+
+    /// Adds `months`, clamping the day to the length of the resulting month (e.g. 01-31 + 1 month is 02-28).
+    /// Returns `None` when the resulting year is not within `YEARS`.
+    fn add_months(&self, months: i64) -> Option<Self> {
+        let month_index = (self.year * 12 + self.month - 1).checked_add(months)?;
+        let year = month_index.div_euclid(12);
+        let month = month_index.rem_euclid(12) + 1;
+        YEARS.contains(&year).then(|| Self { year, month, day: self.day.min(days_in_month(year, month)) })
+    }
+
+    /// Returns the number of full years from this date until `other`.
+    fn years_until(&self, other: &CivilDate) -> i64 {
+        let years = other.year - self.year;
+        match (other.month, other.day) < (self.month, self.day) {
+            true => years - 1,
+            false => years,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:04}-{:02}-{:02}", self.year, self.month, self.day))
+    }
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CivilDate, Function};
+    use crate::fake::expression::ValueType;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use serde_json::json;
+
+    fn call(name: &str, arguments: &[serde_json::Value]) -> serde_json::Value {
+        Function::new(name, arguments.len()).unwrap().call(arguments, &mut StdRng::seed_from_u64(0)).unwrap()
+    }
+
+    #[test]
+    fn test_function_call() {
+        assert_eq!(call("round", &[json!(2.5)]), json!(3));
+        assert_eq!(call("max", &[json!(2), json!(3.5)]), json!(3.5));
+        assert_eq!(call("upper", &[json!("abc")]), json!("ABC"));
+        assert_eq!(call("if", &[json!(false), json!(1), json!("no")]), json!("no"));
+        assert_eq!(call("add_days", &[json!("2024-02-28"), json!(2)]), json!("2024-03-01"));
+        assert_eq!(call("add_months", &[json!("2023-01-31"), json!(1)]), json!("2023-02-28"));
+        assert_eq!(call("add_years", &[json!("2024-02-29"), json!(-1)]), json!("2023-02-28"));
+        assert_eq!(call("days_between", &[json!("2023-12-31"), json!("2024-03-01")]), json!(61));
+        assert_eq!(call("years_between", &[json!("2000-06-15"), json!("2024-06-14")]), json!(23));
+        assert_eq!(call("month", &[json!("2024-07-04T10:00:00Z")]), json!(7));
+        assert!((0.0..1.0).contains(&call("random", &[]).as_f64().unwrap()));
+    }
+
+    #[test]
+    fn test_function_call_fail() {
+        assert!(Function::new("date", 3).unwrap().call(&[json!(2023), json!(2), json!(29)], &mut StdRng::seed_from_u64(0)).is_err(), "Should return an error for an invalid date");
+        let date = Function::new("date", 3).unwrap().call(&[json!(99999), json!(1), json!(1)], &mut StdRng::seed_from_u64(0)).unwrap_err();
+        assert_eq!(date.to_string(), "99999-01-01 is not a valid date", "Should return an error for a year which can not be written as YYYY");
+        assert!(Function::new("date", 3).unwrap().call(&[json!(-1), json!(1), json!(1)], &mut StdRng::seed_from_u64(0)).is_err());
+        assert!(Function::new("year", 1).unwrap().call(&[json!("yesterday")], &mut StdRng::seed_from_u64(0)).is_err(), "Should return an error for a non-date string");
+
+        let add_days = Function::new("add_days", 2).unwrap().call(&[json!("2024-01-01"), json!(i64::MAX)], &mut StdRng::seed_from_u64(0)).unwrap_err();
+        assert_eq!(add_days.to_string(), "the date add_days returns is out of range", "Should return an error instead of overflowing");
+        assert!(Function::new("add_years", 2).unwrap().call(&[json!("2024-01-01"), json!(i64::MAX / 2)], &mut StdRng::seed_from_u64(0)).is_err(), "Should return an error instead of overflowing");
+        assert!(Function::new("add_months", 2).unwrap().call(&[json!("2024-01-01"), json!(i64::MIN)], &mut StdRng::seed_from_u64(0)).is_err());
+        assert_eq!(call("add_days", &[json!("9999-12-30"), json!(1)]), json!("9999-12-31"));
+    }
+
+    #[test]
+    fn test_function_infer() {
+        assert_eq!(Function::new("age", 1).unwrap().infer(&[ValueType::String]).unwrap(), ValueType::Number);
+        assert_eq!(Function::new("if", 3).unwrap().infer(&[ValueType::Boolean, ValueType::String, ValueType::String]).unwrap(), ValueType::String);
+        assert!(Function::new("add_days", 2).unwrap().infer(&[ValueType::String, ValueType::String]).is_err());
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(CivilDate::parse("1970-01-01").unwrap().days(), 0);
+        assert_eq!(CivilDate::from_days(19_782), CivilDate::new(2024, 2, 29).unwrap());
+        assert_eq!(CivilDate::from_days(-1), CivilDate::new(1969, 12, 31).unwrap());
+        assert!(CivilDate::parse("2024-13-01").is_err());
+    }
+}
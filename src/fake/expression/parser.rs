@@ -0,0 +1,265 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use crate::fake::expression::function::Function;
+use crate::fake::expression::{BinaryOperator, Node, UnaryOperator};
+
+/// `Token` is a lexical token of an expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(Value),
+    Identifier(String),
+    Operator(&'static str),
+    LeftParenthesis,
+    RightParenthesis,
+    Comma,
+}
+
+/// The operators, longest first so that `<=` is not read as `<`.
+const OPERATORS: [&str; 15] = ["==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "<", ">", "!", "="];
+
+/// Parses the text of an expression into its syntax tree.
+pub fn parse(source: &str) -> Result<Node> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser { tokens, position: 0 };
+    let node = parser.or()?;
+
+    match parser.peek() {
+        None => Ok(node),
+        Some(token) => Err(anyhow!("unexpected {}", describe(token))),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < chars.len() {
+        let c = chars[position];
+        let rest: String = chars[position..].iter().collect();
+
+        if c.is_whitespace() {
+            position += 1;
+        } else if c.is_ascii_digit() {
+            let length = chars[position..].iter().take_while(|c| c.is_ascii_digit() || **c == '.').count();
+            let number: String = chars[position..position + length].iter().collect();
+            let value = match number.parse::<i64>() {
+                Ok(integer) => Value::from(integer),
+                Err(_) => number.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number).ok_or(anyhow!("invalid number {}", number))?,
+            };
+            tokens.push(Token::Literal(value));
+            position += length;
+        } else if c == '\'' || c == '"' {
+            let length = chars[position + 1..].iter().position(|d| *d == c).ok_or(anyhow!("unclosed string"))?;
+            tokens.push(Token::Literal(Value::String(chars[position + 1..position + 1 + length].iter().collect())));
+            position += length + 2;
+        } else if c.is_alphabetic() || c == '_' {
+            let length = chars[position..].iter().take_while(|c| c.is_alphanumeric() || **c == '_' || **c == '.').count();
+            let identifier: String = chars[position..position + length].iter().collect();
+            tokens.push(match identifier.as_str() {
+                "true" => Token::Literal(Value::Bool(true)),
+                "false" => Token::Literal(Value::Bool(false)),
+                "null" => Token::Literal(Value::Null),
+                "and" => Token::Operator("&&"),
+                "or" => Token::Operator("||"),
+                "not" => Token::Operator("!"),
+                _ => Token::Identifier(identifier),
+            });
+            position += length;
+        } else if c == '(' {
+            tokens.push(Token::LeftParenthesis);
+            position += 1;
+        } else if c == ')' {
+            tokens.push(Token::RightParenthesis);
+            position += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            position += 1;
+        } else {
+            let operator = OPERATORS.iter().find(|operator| rest.starts_with(**operator)).ok_or(anyhow!("unexpected character {}", c))?;
+            if *operator == "=" {
+                Err(anyhow!("unexpected =, use == for comparison"))?
+            }
+            tokens.push(Token::Operator(operator));
+            position += operator.len();
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser, one method per precedence level from the loosest to the tightest.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    /// Consumes the next token when it is one of `operators`.
+    fn operator(&mut self, operators: &[&str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Operator(operator)) if operators.contains(operator) => {
+                let operator = *operator;
+                self.position += 1;
+                Some(operator)
+            }
+            _ => None,
+        }
+    }
+
+    fn binary(&mut self, operators: &[&str], operand: fn(&mut Parser) -> Result<Node>) -> Result<Node> {
+        let mut node = operand(self)?;
+        while let Some(operator) = self.operator(operators) {
+            let right = operand(self)?;
+            node = Node::Binary(binary_operator(operator), Box::new(node), Box::new(right));
+        }
+
+        Ok(node)
+    }
+
+    fn or(&mut self) -> Result<Node> {
+        self.binary(&["||"], Parser::and)
+    }
+
+    fn and(&mut self) -> Result<Node> {
+        self.binary(&["&&"], Parser::equality)
+    }
+
+    fn equality(&mut self) -> Result<Node> {
+        self.binary(&["==", "!="], Parser::comparison)
+    }
+
+    fn comparison(&mut self) -> Result<Node> {
+        self.binary(&["<", "<=", ">", ">="], Parser::additive)
+    }
+
+    fn additive(&mut self) -> Result<Node> {
+        self.binary(&["+", "-"], Parser::multiplicative)
+    }
+
+    fn multiplicative(&mut self) -> Result<Node> {
+        self.binary(&["*", "/", "%"], Parser::unary)
+    }
+
+    fn unary(&mut self) -> Result<Node> {
+        match self.operator(&["-", "!"]) {
+            Some("-") => Ok(Node::Unary(UnaryOperator::Negate, Box::new(self.unary()?))),
+            Some(_) => Ok(Node::Unary(UnaryOperator::Not, Box::new(self.unary()?))),
+            None => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Node> {
+        match self.next() {
+            Some(Token::Literal(value)) => Ok(Node::Literal(value)),
+            Some(Token::Identifier(name)) if self.peek() == Some(&Token::LeftParenthesis) => {
+                self.position += 1;
+                let arguments = self.arguments()?;
+                let function = Function::new(&name, arguments.len())?;
+                Ok(Node::Call(function, arguments))
+            }
+            Some(Token::Identifier(name)) => {
+                let path: Vec<String> = name.split('.').map(str::to_string).collect();
+                if path.iter().any(String::is_empty) {
+                    Err(anyhow!("invalid field path {}", name))?
+                }
+                Ok(Node::Path(path))
+            }
+            Some(Token::LeftParenthesis) => {
+                let node = self.or()?;
+                match self.next() {
+                    Some(Token::RightParenthesis) => Ok(node),
+                    _ => Err(anyhow!("missing )")),
+                }
+            }
+            Some(token) => Err(anyhow!("unexpected {}", describe(&token))),
+            None => Err(anyhow!("unexpected end of expression")),
+        }
+    }
+
+    /// Parses the arguments of a function call, after the opening parenthesis.
+    fn arguments(&mut self) -> Result<Vec<Node>> {
+        let mut arguments = Vec::new();
+        if self.peek() == Some(&Token::RightParenthesis) {
+            self.position += 1;
+            return Ok(arguments);
+        }
+
+        loop {
+            arguments.push(self.or()?);
+            match self.next() {
+                Some(Token::Comma) => (),
+                Some(Token::RightParenthesis) => return Ok(arguments),
+                _ => Err(anyhow!("missing ) after the function arguments"))?,
+            }
+        }
+    }
+}
+
+fn binary_operator(operator: &str) -> BinaryOperator {
+    match operator {
+        "+" => BinaryOperator::Add,
+        "-" => BinaryOperator::Subtract,
+        "*" => BinaryOperator::Multiply,
+        "/" => BinaryOperator::Divide,
+        "%" => BinaryOperator::Remainder,
+        "==" => BinaryOperator::Equal,
+        "!=" => BinaryOperator::NotEqual,
+        "<" => BinaryOperator::Less,
+        "<=" => BinaryOperator::LessEqual,
+        ">" => BinaryOperator::Greater,
+        ">=" => BinaryOperator::GreaterEqual,
+        "&&" => BinaryOperator::And,
+        _ => BinaryOperator::Or,
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Literal(value) => value.to_string(),
+        Token::Identifier(name) => name.clone(),
+        Token::Operator(operator) => operator.to_string(),
+        Token::LeftParenthesis => "(".to_string(),
+        Token::RightParenthesis => ")".to_string(),
+        Token::Comma => ",".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::fake::expression::{BinaryOperator, Node};
+    use serde_json::json;
+
+    #[test]
+    fn test_parse() {
+        let node = parse("a + 2 * b.c").unwrap();
+
+        assert_eq!(node, Node::Binary(
+            BinaryOperator::Add,
+            Box::new(Node::Path(vec!["a".to_string()])),
+            Box::new(Node::Binary(BinaryOperator::Multiply, Box::new(Node::Literal(json!(2))), Box::new(Node::Path(vec!["b".to_string(), "c".to_string()])))),
+        ), "Multiplication should bind tighter than addition");
+    }
+
+    #[test]
+    fn test_parse_fail() {
+        assert!(parse("a +").is_err(), "Should return an error for a missing operand");
+        assert!(parse("(a + b").is_err(), "Should return an error for a missing )");
+        assert!(parse("a = b").is_err(), "Should return an error for an assignment");
+        assert!(parse("'abc").is_err(), "Should return an error for an unclosed string");
+        assert!(parse("a b").is_err(), "Should return an error for a missing operator");
+        assert!(parse("undefined_function(a)").is_err(), "Should return an error for an undefined function");
+        assert!(parse("round(a, b)").is_err(), "Should return an error for a wrong number of arguments");
+    }
+}
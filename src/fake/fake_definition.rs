@@ -1,27 +1,66 @@
 use anyhow::{anyhow, Result};
-use std::collections::BTreeMap;
+use indexmap::IndexMap;
 use serde_json::{Value};
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::fake_type::template::Template;
+use crate::fake::generation_context::GenerationContext;
+use crate::fake::record::{Record, RecordOptions};
+use crate::fake::registry::Registry;
 
 /// `FakeDefinition` is a container for a collection of `FakeDefinitionElement`s.
 /// It can be constructed from JSON and provides the ability to convert back to a `Value`.
+/// The elements keep the order they are defined in, so the generated keys come out in the same order.
+/// Elements referring to other fields are generated after the fields they refer to.
+/// A top-level `unique_by` list makes the listed combinations of fields unique across every generated record,
+/// and a top-level `constraints` list of boolean expressions holds for every generated record.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::{FakeDefinition, GenerationContext};
+/// # use serde_json::json;
 /// // Create a new instance of FakeDefinition from JSON
-/// let fd = FakeDefinition::from_json(&Value::Object(map!{
-///     "name" => Value::String("John Doe".to_string()),
+/// let fd = FakeDefinition::from_json(&json!({
+///     "name": { "fake_type": "name", "lang": "EN" },
 /// })).unwrap();
-/// let fd_value = fd.to_value();
+/// let fd_value = fd.to_value(&mut GenerationContext::new()).unwrap();
 /// println!("Fake Definition value: {:?}", fd_value);
 /// ```
 #[derive(Debug)]
-pub struct FakeDefinition(BTreeMap<String, FakeDefinitionElement>);
+pub struct FakeDefinition {
+    record: Record,
+}
 
 impl FakeDefinition {
+    /// Creates a new `FakeDefinition`. Returns an error when the elements refer to each other circularly,
+    /// refer to a field which is not defined, or when the record options are invalid.
+    pub fn new(fake_definition_elements: IndexMap<String, FakeDefinitionElement>, options: RecordOptions) -> Result<Self> {
+        FakeDefinition::new_with_registry(fake_definition_elements, options, Registry::builtin())
+    }
+
+    /// Creates a new `FakeDefinition` whose templates may refer to the fake types registered in `registry`.
+    pub fn new_with_registry(fake_definition_elements: IndexMap<String, FakeDefinitionElement>, options: RecordOptions, registry: &Registry) -> Result<Self> {
+        let record = Record::new(fake_definition_elements, options)?;
+
+        let undefined_references: Vec<String> = record
+            .references()
+            .into_iter()
+            .filter(|reference| !GenerationContext::is_variable(reference) && !Template::is_inline_generator(reference, registry))
+            .collect();
+        if !undefined_references.is_empty() {
+            Err(anyhow!("undefined field referred: {}", undefined_references.join(", ")))?
+        }
+
+        Ok(Self { record })
+    }
+
     pub fn from_json(fake_definition_json: &Value) -> Result<Self> {
-        let mut btree_map = BTreeMap::new();
+        FakeDefinition::from_json_with_registry(fake_definition_json, Registry::builtin())
+    }
+
+    /// Parses a `FakeDefinition` from JSON with the fake types registered in `registry`.
+    pub fn from_json_with_registry(fake_definition_json: &Value, registry: &Registry) -> Result<Self> {
+        let mut index_map = IndexMap::new();
 
         let fake_definition_element_map = match fake_definition_json {
             Value::Object(map) => Ok(map),
@@ -30,30 +69,42 @@ impl FakeDefinition {
         }?;
 
         for (fake_definition_element_key, fake_definition_element_value) in fake_definition_element_map {
-            let fake_definition_element = FakeDefinitionElement::generate(fake_definition_element_value)?;
-            btree_map.insert(fake_definition_element_key.clone(), fake_definition_element);
+            if RecordOptions::is_option(fake_definition_element_key, fake_definition_element_value) {
+                continue;
+            }
+
+            let fake_definition_element = FakeDefinitionElement::generate_with_registry(fake_definition_element_value, registry)?;
+            index_map.insert(fake_definition_element_key.clone(), fake_definition_element);
         }
 
-        Ok(Self(btree_map))
+        FakeDefinition::new_with_registry(index_map, RecordOptions::from_json(fake_definition_element_map)?, registry)
     }
 
-    pub fn to_value(&self) -> Value {
-        let mut map = serde_json::Map::new();
-        for (k, fake_definition_element) in &self.0 {
-            map.insert(k.clone(), fake_definition_element.to_value());
-        }
+    /// Returns the names of the dataset entities the elements sample values from.
+    pub fn entity_references(&self) -> Vec<String> {
+        self.record.entity_references()
+    }
+
+    /// Returns the JSON Schema of the generated records.
+    pub fn output_schema(&self) -> Value {
+        self.record.output_schema()
+    }
 
-        Value::Object(map)
+    pub fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::Object(self.record.generate(context)?))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
+    use indexmap::IndexMap;
     use super::FakeDefinition;
     use crate::fake::fake_definition_element::FakeDefinitionElement;
     use serde_json::{Value};
     use crate::fake::fake_type::constant::Constant;
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::record::RecordOptions;
+    use std::collections::HashSet;
 
     #[test]
     fn test_fake_definition_from_json() {
@@ -85,11 +136,195 @@ mod tests {
 
     #[test]
     fn test_fake_definition_to_value() {
-        let mut fd = BTreeMap::new();
+        let mut fd = IndexMap::new();
         fd.insert("name".to_string(), FakeDefinitionElement::Constant(Constant::new("constant".to_string(), Value::String("John Doe".to_string()))));
 
-        let fd_value = FakeDefinition(fd).to_value();
+        let fd_value = FakeDefinition::new(fd, RecordOptions::default()).unwrap().to_value(&mut GenerationContext::new()).unwrap();
 
         assert!(fd_value.is_object(), "Generated value should be an object");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_fake_definition_to_value_preserves_order() {
+        let fake_definition_json: Value = serde_json::from_str(r#"{
+            "zeta": { "fake_type": "constant", "value": 1 },
+            "alpha": { "fake_type": "constant", "value": 2 },
+            "mu": { "fake_type": "map", "z": { "fake_type": "constant", "value": 3 }, "a": { "fake_type": "constant", "value": 4 } }
+        }"#).unwrap();
+
+        let fd_value = FakeDefinition::from_json(&fake_definition_json).unwrap().to_value(&mut GenerationContext::new()).unwrap();
+
+        assert_eq!(serde_json::to_string(&fd_value).unwrap(), r#"{"zeta":1,"alpha":2,"mu":{"z":3,"a":4}}"#, "Generated keys should keep the definition order");
+    }
+
+    #[test]
+    fn test_fake_definition_to_value_with_template() {
+        let fake_definition_json = serde_json::json!({
+            "email": { "fake_type": "template", "template": "{{first_name | lower}}.{{last_name | lower}}@example.com" },
+            "first_name": { "fake_type": "constant", "value": "Taro" },
+            "profile": {
+                "fake_type": "map",
+                "display_name": { "fake_type": "template", "template": "{{last_name}} {{first_name}}" },
+                "tag": { "fake_type": "template", "template": "{{word | upper}}" }
+            },
+            "last_name": { "fake_type": "constant", "value": "Yamada" }
+        });
+
+        let fd_value = FakeDefinition::from_json(&fake_definition_json).unwrap().to_value(&mut GenerationContext::new()).unwrap();
+
+        assert_eq!(fd_value["email"], "taro.yamada@example.com", "Templates should refer to sibling fields");
+        assert_eq!(fd_value["profile"]["display_name"], "Yamada Taro", "Templates should refer to ancestor fields");
+        assert!(fd_value["profile"]["tag"].as_str().is_some_and(|tag| !tag.is_empty()), "Templates should render inline generators");
+    }
+
+    #[test]
+    fn test_fake_definition_from_json_with_invalid_template_references() {
+        let undefined = serde_json::json!({
+            "email": { "fake_type": "template", "template": "{{undefined_field}}@example.com" }
+        });
+        assert!(FakeDefinition::from_json(&undefined).is_err(), "Should return an error for an undefined field");
+
+        let circular = serde_json::json!({
+            "a": { "fake_type": "template", "template": "{{b}}" },
+            "b": { "fake_type": "map", "c": { "fake_type": "template", "template": "{{a}}" } }
+        });
+        assert!(FakeDefinition::from_json(&circular).is_err(), "Should return an error for circular references");
+    }
+
+    #[test]
+    fn test_fake_definition_to_value_with_unique() {
+        let fake_definition_json = serde_json::json!({
+            "id": { "fake_type": "digit", "lang": "EN", "unique": true },
+            "flag": { "fake_type": "boolean", "lang": "EN", "ratio": 50 },
+            "profile": {
+                "fake_type": "map",
+                "unique_by": [["a", "b"]],
+                "a": { "fake_type": "boolean", "lang": "EN", "ratio": 50 },
+                "b": { "fake_type": "boolean", "lang": "EN", "ratio": 50 }
+            },
+            "unique_by": [["flag", "id"]]
+        });
+        let fd = FakeDefinition::from_json(&fake_definition_json).unwrap();
+        let mut context = GenerationContext::new();
+
+        let values: Vec<Value> = (0..4).map(|_| fd.to_value(&mut context).unwrap()).collect();
+        let ids: HashSet<String> = values.iter().map(|v| v["id"].to_string()).collect();
+        let profiles: HashSet<String> = values.iter().map(|v| v["profile"].to_string()).collect();
+
+        assert_eq!(ids.len(), 4, "Unique fields should not repeat across records");
+        assert_eq!(profiles.len(), 4, "unique_by combinations should not repeat across records");
+        assert!(values.iter().all(|v| v.get("unique_by").is_none()), "unique_by should not be generated as a field");
+        assert!(fd.to_value(&mut context).is_err(), "Should return an error when the unique_by combinations are exhausted");
+    }
+
+    #[test]
+    fn test_fake_definition_from_json_with_invalid_unique() {
+        let undefined = serde_json::json!({
+            "id": { "fake_type": "digit", "lang": "EN" },
+            "unique_by": [["undefined_field"]]
+        });
+        assert!(FakeDefinition::from_json(&undefined).is_err(), "Should return an error for an undefined unique_by field");
+
+        let not_boolean = serde_json::json!({ "id": { "fake_type": "digit", "lang": "EN", "unique": "yes" } });
+        assert!(FakeDefinition::from_json(&not_boolean).is_err(), "Should return an error for a non-boolean unique");
+    }
+
+    #[test]
+    fn test_fake_definition_to_value_with_constraints() {
+        let fake_definition_json = serde_json::json!({
+            "budget": { "fake_type": "constant", "value": 6 },
+            "price": {
+                "fake_type": "map",
+                "min": { "fake_type": "digit", "lang": "EN" },
+                "max": { "fake_type": "digit", "lang": "EN" },
+                "constraints": ["min <= max", "max <= budget"]
+            },
+            "constraints": ["price.max - price.min >= 2"]
+        });
+        let fd = FakeDefinition::from_json(&fake_definition_json).unwrap();
+        let mut context = GenerationContext::new();
+
+        for _ in 0..20 {
+            let v = fd.to_value(&mut context).unwrap();
+            assert!(v["price"]["max"].as_u64().unwrap() >= v["price"]["min"].as_u64().unwrap() + 2, "The top-level constraint should hold: {}", v);
+            assert!(v["price"]["max"].as_u64() <= v["budget"].as_u64(), "The map constraint should see the parent fields: {}", v);
+            assert!(v.get("constraints").is_none() && v["price"].get("constraints").is_none(), "constraints should not be generated as a field");
+        }
+
+        let undefined = serde_json::json!({ "a": { "fake_type": "digit", "lang": "EN" }, "constraints": ["a < b"] });
+        assert!(FakeDefinition::from_json(&undefined).is_err(), "Should return an error for a constraint on an undefined field");
+    }
+
+    #[test]
+    fn test_fake_definition_to_value_with_context_variables() {
+        let fake_definition_json = serde_json::json!({
+            "username": { "fake_type": "template", "template": "user_{{index}}" },
+            "pages": {
+                "fake_type": "array",
+                "count": 2,
+                "items": { "fake_type": "template", "template": "{{record_index}}-{{index}} {{path}}" }
+            }
+        });
+        let fd = FakeDefinition::from_json(&fake_definition_json).unwrap();
+        let mut context = GenerationContext::new();
+        context.set_record_index(3);
+
+        let fd_value = fd.to_value(&mut context).unwrap();
+
+        assert_eq!(fd_value, serde_json::json!({ "username": "user_3", "pages": ["3-0 pages.0", "3-1 pages.1"] }));
+    }
+
+    #[test]
+    fn test_fake_definition_to_value_with_seed() {
+        let fake_definition_json = serde_json::json!({
+            "name": { "fake_type": "name", "lang": "EN" },
+            "tags": { "fake_type": "array", "min_count": 1, "max_count": 5, "items": { "fake_type": "word", "lang": "EN" } },
+            "active": { "fake_type": "boolean", "lang": "EN", "ratio": 50 }
+        });
+        let generate = |seed: u64| {
+            let fd = FakeDefinition::from_json(&fake_definition_json).unwrap();
+            let mut context = GenerationContext::with_seed(seed);
+            (0..5).map(|_| fd.to_value(&mut context).unwrap()).collect::<Vec<Value>>()
+        };
+
+        assert_eq!(generate(42), generate(42), "The same seed should generate the same data");
+    }
+
+    #[test]
+    fn test_fake_definition_to_value_with_expr() {
+        let fake_definition_json = serde_json::json!({
+            "total": { "fake_type": "expr", "expr": "price * quantity" },
+            "price": { "fake_type": "constant", "value": 120 },
+            "quantity": { "fake_type": "digit", "lang": "EN" },
+            "birth_date": { "fake_type": "constant", "value": "2000-02-29" },
+            "age": { "fake_type": "expr", "expr": "years_between(birth_date, '2018-02-28')" },
+            "is_adult": { "fake_type": "expr", "expr": "age >= 18" },
+            "label": { "fake_type": "expr", "expr": "'#' + index + ' ' + upper(str(is_adult))" }
+        });
+
+        let fd_value = FakeDefinition::from_json(&fake_definition_json).unwrap().to_value(&mut GenerationContext::new()).unwrap();
+
+        assert_eq!(fd_value["total"], fd_value["quantity"].as_i64().unwrap() * 120, "Expressions should refer to fields defined after them");
+        assert_eq!(fd_value["age"], 17);
+        assert_eq!(fd_value["is_adult"], false);
+        assert_eq!(fd_value["label"], "#0 FALSE");
+    }
+
+    #[test]
+    fn test_fake_definition_from_json_with_invalid_expr() {
+        let type_error = serde_json::json!({
+            "name": { "fake_type": "name", "lang": "EN" },
+            "total": { "fake_type": "expr", "expr": "name * 2" }
+        });
+        assert!(FakeDefinition::from_json(&type_error).is_err(), "Should return an error for a type error at definition time");
+
+        let undefined = serde_json::json!({ "total": { "fake_type": "expr", "expr": "price * 2" } });
+        assert!(FakeDefinition::from_json(&undefined).is_err(), "Should return an error for an undefined field");
+
+        let circular = serde_json::json!({
+            "a": { "fake_type": "expr", "expr": "b + 1" },
+            "b": { "fake_type": "expr", "expr": "a + 1" }
+        });
+        assert!(FakeDefinition::from_json(&circular).is_err(), "Should return an error for circular references");
+    }
+}
@@ -1,9 +1,22 @@
-use std::collections::{BTreeMap, VecDeque};
-use serde_json::{Value};
-use crate::fake::fake_type::array::Array;
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use crate::fake::fake_type::array::{Array, ArrayLength};
 use crate::fake::fake_type::map::Map;
+use crate::fake::fake_type::tuple::Tuple;
+use crate::fake::fake_type::template::Template;
+use crate::fake::fake_type::reference::Reference;
+use crate::fake::fake_type::sequence::{Sequence, SequenceState};
+use crate::fake::fake_type::params::{from_setting, ArrayParams, ConditionalParams, ConstantParams, ExprParams, ReferenceParams, SequenceParams, TemplateParams};
+use crate::fake::fake_type::expr::Expr;
+use crate::fake::fake_type::conditional::Conditional;
+use crate::fake::fake_type::custom::Custom;
+use crate::fake::registry::Registry;
+use crate::fake::expression::ValueType;
+use crate::fake::fake_type::unique::Unique;
+use crate::fake::record::RecordOptions;
+use crate::fake::generation_context::GenerationContext;
 use anyhow::{anyhow, Result};
-use crate::fake::fake_type::{FakeElement, FakeType, FakeWithFormatElement, FakeWithRangeElement, FakeWithRatioElement};
+use crate::fake::fake_type::{FakeElement, FakeType};
 use crate::fake::fake_type::boolean::boolean::Boolean;
 use crate::fake::fake_type::constant::Constant;
 use crate::fake::fake_type::internet::domain_suffix::DomainSuffix;
@@ -39,12 +52,14 @@ use crate::fake::fake_type::number::number_with_format::NumberWithFormat;
 /// # Example
 ///
 /// ```
+/// # use fake_cli::{FakeDefinitionElement, GenerationContext};
+/// # use serde_json::json;
 /// // Create a new instance of FakeDefinitionElement from JSON
-/// let fd = FakeDefinitionElement::generate(&Value::Object(map!{
-///     "fake_type" => Value::String("word".to_string()),
-///     "lang" => Value::String("JA_JP".to_string()),
+/// let fd = FakeDefinitionElement::generate(&json!({
+///     "fake_type": "word",
+///     "lang": "JA_JP",
 /// })).unwrap();
-/// let fd_value = fd.to_value();
+/// let fd_value = fd.to_value(&mut GenerationContext::new()).unwrap();
 /// println!("Fake definition element value: {:?}", fd_value);
 /// ```
 #[derive(Debug)]
@@ -87,147 +102,388 @@ pub enum FakeDefinitionElement {
 
     // FakeCliOriginal
     Array(Array),
+    Tuple(Tuple),
     Map(Map),
     Constant(Constant),
+    Template(Template),
+    Reference(Reference),
+    Sequence(Sequence),
+    Expr(Expr),
+    Unique(Unique),
+    Conditional(Conditional),
+
+    // Registered
+    Custom(Custom),
+}
+
+/// `ElementState` is the saved state of a stateful element, i.e. the counter of a `sequence`, the values generated by
+/// a `unique` element, the remaining records of a `ref` or the `unique_by` combinations of a map. A record saves the
+/// state of its fields before generating them and restores it when the record is rejected, so that a rejected record
+/// leaves no trace.
+#[derive(Debug, Clone)]
+pub enum ElementState {
+    Sequence(SequenceState),
+    Unique(usize),
+    Reference(Option<Vec<usize>>),
+    Record(Vec<usize>),
 }
 
 impl FakeDefinitionElement {
-    pub fn to_value(&self) -> Value {
+    pub fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
         match self {
             // Lorem
-            FakeDefinitionElement::Word(data) => data.to_value(),
-            FakeDefinitionElement::Words(data) => data.to_value(),
-            FakeDefinitionElement::Sentence(data) => data.to_value(),
-            FakeDefinitionElement::Sentences(data) => data.to_value(),
-            FakeDefinitionElement::Paragraph(data) => data.to_value(),
-            FakeDefinitionElement::Paragraphs(data) => data.to_value(),
+            FakeDefinitionElement::Word(data) => data.to_value(context),
+            FakeDefinitionElement::Words(data) => data.to_value(context),
+            FakeDefinitionElement::Sentence(data) => data.to_value(context),
+            FakeDefinitionElement::Sentences(data) => data.to_value(context),
+            FakeDefinitionElement::Paragraph(data) => data.to_value(context),
+            FakeDefinitionElement::Paragraphs(data) => data.to_value(context),
 
             // Name
-            FakeDefinitionElement::FirstName(data) => data.to_value(),
-            FakeDefinitionElement::LastName(data) => data.to_value(),
-            FakeDefinitionElement::Title(data) => data.to_value(),
-            FakeDefinitionElement::Suffix(data) => data.to_value(),
-            FakeDefinitionElement::Name(data) => data.to_value(),
-            FakeDefinitionElement::NameWithTitle(data) => data.to_value(),
+            FakeDefinitionElement::FirstName(data) => data.to_value(context),
+            FakeDefinitionElement::LastName(data) => data.to_value(context),
+            FakeDefinitionElement::Title(data) => data.to_value(context),
+            FakeDefinitionElement::Suffix(data) => data.to_value(context),
+            FakeDefinitionElement::Name(data) => data.to_value(context),
+            FakeDefinitionElement::NameWithTitle(data) => data.to_value(context),
 
             // Number
-            FakeDefinitionElement::Digit(data) => data.to_value(),
-            FakeDefinitionElement::NumberWithFormat(data) => data.to_value(),
+            FakeDefinitionElement::Digit(data) => data.to_value(context),
+            FakeDefinitionElement::NumberWithFormat(data) => data.to_value(context),
 
             // Boolean
-            FakeDefinitionElement::Boolean(data) => data.to_value(),
+            FakeDefinitionElement::Boolean(data) => data.to_value(context),
 
             // Internet
-            FakeDefinitionElement::FreeEmailProvider(data) => data.to_value(),
-            FakeDefinitionElement::DomainSuffix(data) => data.to_value(),
-            FakeDefinitionElement::FreeEmail(data) => data.to_value(),
-            FakeDefinitionElement::SafeEmail(data) => data.to_value(),
-            FakeDefinitionElement::Username(data) => data.to_value(),
-            FakeDefinitionElement::Password(data) => data.to_value(),
-            FakeDefinitionElement::IPv4(data) => data.to_value(),
-            FakeDefinitionElement::IPv6(data) => data.to_value(),
-            FakeDefinitionElement::IP(data) => data.to_value(),
-            FakeDefinitionElement::MACAddress(data) => data.to_value(),
-            FakeDefinitionElement::UserAgent(data) => data.to_value(),
+            FakeDefinitionElement::FreeEmailProvider(data) => data.to_value(context),
+            FakeDefinitionElement::DomainSuffix(data) => data.to_value(context),
+            FakeDefinitionElement::FreeEmail(data) => data.to_value(context),
+            FakeDefinitionElement::SafeEmail(data) => data.to_value(context),
+            FakeDefinitionElement::Username(data) => data.to_value(context),
+            FakeDefinitionElement::Password(data) => data.to_value(context),
+            FakeDefinitionElement::IPv4(data) => data.to_value(context),
+            FakeDefinitionElement::IPv6(data) => data.to_value(context),
+            FakeDefinitionElement::IP(data) => data.to_value(context),
+            FakeDefinitionElement::MACAddress(data) => data.to_value(context),
+            FakeDefinitionElement::UserAgent(data) => data.to_value(context),
 
             // FakeCliOriginal
-            FakeDefinitionElement::Array(data) => data.to_value(),
-            FakeDefinitionElement::Map(data) => data.to_value(),
-            FakeDefinitionElement::Constant(data) => data.to_value(),
+            FakeDefinitionElement::Array(data) => data.to_value(context),
+            FakeDefinitionElement::Tuple(data) => data.to_value(context),
+            FakeDefinitionElement::Map(data) => data.to_value(context),
+            FakeDefinitionElement::Constant(data) => data.to_value(context),
+            FakeDefinitionElement::Template(data) => data.to_value(context),
+            FakeDefinitionElement::Reference(data) => data.to_value(context),
+            FakeDefinitionElement::Sequence(data) => data.to_value(context),
+            FakeDefinitionElement::Expr(data) => data.to_value(context),
+            FakeDefinitionElement::Unique(data) => data.to_value(context),
+            FakeDefinitionElement::Conditional(data) => data.to_value(context),
+
+            // Registered
+            FakeDefinitionElement::Custom(data) => data.to_value(context),
+        }
+    }
+
+    /// Generates the value of this element as a field of a map. Returns `None` when the field is omitted,
+    /// i.e. when the condition of a `when` does not hold.
+    pub fn to_field_value(&self, context: &mut GenerationContext) -> Result<Option<Value>> {
+        match self {
+            FakeDefinitionElement::Conditional(data) => data.fake_apply(context),
+            _ => Ok(Some(self.to_value(context)?)),
+        }
+    }
+
+    /// Returns the names of the fields this element refers to and which have to be resolved by an enclosing map.
+    pub fn references(&self) -> Vec<String> {
+        match self {
+            FakeDefinitionElement::Array(data) => data.references(),
+            FakeDefinitionElement::Tuple(data) => data.references(),
+            FakeDefinitionElement::Map(data) => data.references(),
+            FakeDefinitionElement::Template(data) => data.references(),
+            FakeDefinitionElement::Expr(data) => data.references(),
+            FakeDefinitionElement::Unique(data) => data.fake_definition_element().references(),
+            FakeDefinitionElement::Conditional(data) => data.references(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the type of the generated values, as far as it is known before generating.
+    /// The type of an `expr` depends on its sibling fields, so it is inferred by the enclosing map instead.
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            FakeDefinitionElement::Digit(_) => ValueType::Number,
+            FakeDefinitionElement::Boolean(_) => ValueType::Boolean,
+            FakeDefinitionElement::Words(_) | FakeDefinitionElement::Sentences(_) | FakeDefinitionElement::Paragraphs(_) => ValueType::Array,
+            FakeDefinitionElement::Array(_) | FakeDefinitionElement::Tuple(_) => ValueType::Array,
+            FakeDefinitionElement::Map(_) => ValueType::Object,
+            FakeDefinitionElement::Constant(data) => ValueType::of(data.value()),
+            FakeDefinitionElement::Sequence(data) => data.value_type(),
+            FakeDefinitionElement::Reference(_) | FakeDefinitionElement::Expr(_) | FakeDefinitionElement::Custom(_) => ValueType::Any,
+            FakeDefinitionElement::Unique(data) => data.fake_definition_element().value_type(),
+            FakeDefinitionElement::Conditional(data) => data.value_type(),
+            _ => ValueType::String,
+        }
+    }
+
+    /// Returns the JSON Schema of the generated values, e.g. an integer from 0 to 9 for a `digit`.
+    /// The type of an `expr` depends on its sibling fields, so it is inferred by the enclosing map instead.
+    pub fn output_schema(&self) -> Value {
+        match self {
+            FakeDefinitionElement::Digit(_) => json!({ "type": "integer", "minimum": 0, "maximum": 9 }),
+            FakeDefinitionElement::Boolean(_) => json!({ "type": "boolean" }),
+            FakeDefinitionElement::Words(_) | FakeDefinitionElement::Sentences(_) | FakeDefinitionElement::Paragraphs(_) => json!({ "type": "array", "items": { "type": "string" } }),
+            FakeDefinitionElement::FreeEmail(_) | FakeDefinitionElement::SafeEmail(_) => json!({ "type": "string", "format": "email" }),
+            FakeDefinitionElement::IPv4(_) => json!({ "type": "string", "format": "ipv4" }),
+            FakeDefinitionElement::IPv6(_) => json!({ "type": "string", "format": "ipv6" }),
+            FakeDefinitionElement::Array(data) => data.output_schema(),
+            FakeDefinitionElement::Tuple(data) => data.output_schema(),
+            FakeDefinitionElement::Map(data) => data.output_schema(),
+            FakeDefinitionElement::Constant(data) => json!({ "const": data.value() }),
+            FakeDefinitionElement::Sequence(data) => data.output_schema(),
+            FakeDefinitionElement::Reference(data) => data.output_schema(),
+            FakeDefinitionElement::Expr(_) | FakeDefinitionElement::Custom(_) => json!({}),
+            FakeDefinitionElement::Unique(data) => data.fake_definition_element().output_schema(),
+            FakeDefinitionElement::Conditional(data) => data.output_schema(),
+            _ => json!({ "type": "string" }),
+        }
+    }
+
+    /// Returns the JSON Schema of the values of this element as a field of a map, and whether the field is always
+    /// generated, i.e. it is not omitted by a `when` condition.
+    pub fn field_output_schema(&self) -> (Value, bool) {
+        match self {
+            FakeDefinitionElement::Conditional(data) => data.field_output_schema(),
+            _ => (self.output_schema(), true),
+        }
+    }
+
+    /// Returns the `expr` this element computes its values with, if any.
+    pub fn expr(&self) -> Option<&Expr> {
+        match self {
+            FakeDefinitionElement::Expr(data) => Some(data),
+            FakeDefinitionElement::Unique(data) => data.fake_definition_element().expr(),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Conditional` deciding whether this element is generated, if any.
+    pub fn conditional(&self) -> Option<&Conditional> {
+        match self {
+            FakeDefinitionElement::Conditional(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the names of the dataset entities this element samples values from.
+    pub fn entity_references(&self) -> Vec<String> {
+        match self {
+            FakeDefinitionElement::Array(data) => data.entity_references(),
+            FakeDefinitionElement::Tuple(data) => data.entity_references(),
+            FakeDefinitionElement::Map(data) => data.entity_references(),
+            FakeDefinitionElement::Reference(data) => vec![data.entity().to_string()],
+            FakeDefinitionElement::Unique(data) => data.fake_definition_element().entity_references(),
+            FakeDefinitionElement::Conditional(data) => data.entity_references(),
+            _ => vec![],
+        }
+    }
+
+    /// Appends the state of the stateful elements in this element to `states`, in the order `restore_state` reads them.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        match self {
+            FakeDefinitionElement::Array(data) => data.save_state(states),
+            FakeDefinitionElement::Tuple(data) => data.save_state(states),
+            FakeDefinitionElement::Map(data) => data.save_state(states),
+            FakeDefinitionElement::Template(data) => data.save_state(states),
+            FakeDefinitionElement::Reference(data) => data.save_state(states),
+            FakeDefinitionElement::Sequence(data) => data.save_state(states),
+            FakeDefinitionElement::Unique(data) => data.save_state(states),
+            FakeDefinitionElement::Conditional(data) => data.save_state(states),
+            _ => (),
+        }
+    }
+
+    /// Restores the state of the stateful elements in this element from `states`, saved by `save_state`.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        match self {
+            FakeDefinitionElement::Array(data) => data.restore_state(states),
+            FakeDefinitionElement::Tuple(data) => data.restore_state(states),
+            FakeDefinitionElement::Map(data) => data.restore_state(states),
+            FakeDefinitionElement::Template(data) => data.restore_state(states),
+            FakeDefinitionElement::Reference(data) => data.restore_state(states),
+            FakeDefinitionElement::Sequence(data) => data.restore_state(states),
+            FakeDefinitionElement::Unique(data) => data.restore_state(states),
+            FakeDefinitionElement::Conditional(data) => data.restore_state(states),
+            _ => (),
         }
     }
 }
 
 impl FakeDefinitionElement {
+    /// Builds the fake type `T` from the settings of an element, deserializing them into its `Params`.
     pub fn generate_element<T>(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement>
     where
         T: FakeElement + Into<FakeDefinitionElement>,
     {
-        let lang_value = fake_definition_element_setting.get("lang").ok_or(anyhow!("fake_type: {}, lang is missing", fake_type))?;
-        let lang = lang_value.as_str().ok_or(anyhow!("fake_type: {}, lang should be a string", fake_type))?;
+        let params = from_setting::<T::Params>(fake_definition_element_setting, fake_type)?;
 
-        Ok(T::new(fake_type.to_string(), lang.to_string()).into())
+        Ok(T::new(fake_type.to_string(), params)?.into())
     }
 
-    pub fn generate_with_range_element<T>(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement>
-    where
-        T: FakeWithRangeElement + Into<FakeDefinitionElement>,
-    {
-        let lang_value = fake_definition_element_setting.get("lang").ok_or(anyhow!("fake_type: {}, lang is missing", fake_type))?;
-        let min_value = fake_definition_element_setting.get("min").ok_or(anyhow!("fake_type: {}, min is missing", fake_type))?;
-        let max_value = fake_definition_element_setting.get("max").ok_or(anyhow!("fake_type: {}, max is missing", fake_type))?;
-        let min = min_value.as_u64().ok_or(anyhow!("fake_type: {}, min parse error. please 0 < min ", fake_type))? as usize;
-        let max = max_value.as_u64().ok_or(anyhow!("fake_type: {}, max parse error. please 0 < max ", fake_type))? as usize;
-        let lang = lang_value.as_str().ok_or(anyhow!("fake_type: {}, lang should be a string",fake_type))?;
+    pub fn generate_array(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str, registry: &Registry) -> Result<FakeDefinitionElement> {
+        let params = from_setting::<ArrayParams>(fake_definition_element_setting, fake_type)?;
+        if let Some(tuple) = &params.tuple {
+            return FakeDefinitionElement::generate_tuple(fake_definition_element_setting, tuple, fake_type, registry);
+        }
 
-        Ok(T::new(fake_type.to_string(), lang.to_string(), min, max)?.into())
+        let length = FakeDefinitionElement::generate_array_length(&params)?;
+
+        let exclude_conditions = ["count", "min_count", "max_count", "distribution", "unique", "sort", "items", "fake_type", "when"];
+        let children: Vec<(&String, &Value)> = fake_definition_element_setting
+            .iter()
+            .filter(|(k, _)| !exclude_conditions.contains(&k.as_str()))
+            .collect();
+        let keys: Vec<&str> = children.iter().map(|(k, _)| k.as_str()).collect();
+
+        let child_value = match (&params.items, children.as_slice()) {
+            (Some(items_value), []) => Ok(items_value),
+            (Some(_), _) => Err(anyhow!("fake_type: array, items can not be used together with other fake_definition_element ({})", keys.join(", "))),
+            (None, [(_, child_value)]) => Ok(*child_value),
+            (None, []) => Err(anyhow!("fake_type: array, undefined fake_definition_element")),
+            (None, _) => Err(anyhow!("fake_type: array, only one fake_definition_element is allowed but found {} ({}). use tuple for fixed-shape arrays", keys.len(), keys.join(", "))),
+        }?;
+
+        let fake_definition_element = FakeDefinitionElement::generate_with_registry(child_value, registry)?;
+        Ok(FakeDefinitionElement::Array(Array::new(fake_type.to_string(), length, params.unique, params.sort, Box::new(fake_definition_element))))
     }
 
-    pub fn generate_with_ratio_element<T>(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement>
-    where
-        T: FakeWithRatioElement + Into<FakeDefinitionElement>,
-    {
-        let lang_value = fake_definition_element_setting.get("lang").ok_or(anyhow!("fake_type: {}, lang is missing", fake_type))?;
-        let ratio_value = fake_definition_element_setting.get("ratio").ok_or(anyhow!("fake_type: {}, ratio is missing", fake_type))?;
-        let ratio = ratio_value.as_u64().ok_or(anyhow!("fake_type: boolean, ratio parse error. please input 0 to 100"))? as u8;
-        let lang = lang_value.as_str().ok_or(anyhow!("fake_type: boolean, lang should be a string"))?;
+    fn generate_tuple(fake_definition_element_setting: &serde_json::Map<String, Value>, tuple: &[Value], fake_type: &str, registry: &Registry) -> Result<FakeDefinitionElement> {
+        let unexpected_keys: Vec<&str> = fake_definition_element_setting
+            .keys()
+            .map(|k| k.as_str())
+            .filter(|k| !["tuple", "fake_type", "when"].contains(k))
+            .collect();
+        if !unexpected_keys.is_empty() {
+            Err(anyhow!("fake_type: array, tuple can not be used together with {}", unexpected_keys.join(", ")))?
+        }
 
-        Ok(T::new(fake_type.to_string(), lang.to_string(), ratio).into())
-    }
+        if tuple.is_empty() {
+            Err(anyhow!("fake_type: array, tuple should have at least one fake_definition_element"))?
+        }
 
-    pub fn generate_with_format_element<T>(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement>
-    where
-        T: FakeWithFormatElement + Into<FakeDefinitionElement>,
-    {
-        let lang_value = fake_definition_element_setting.get("lang").ok_or(anyhow!("fake_type: {}, lang is missing", fake_type))?;
-        let format_value = fake_definition_element_setting.get("format").ok_or(anyhow!("fake_type: {}, format is missing", fake_type))?;
-        let format = format_value.as_str().ok_or(anyhow!("fake_type: {}, format should be a string", fake_type))?;
-        let lang = lang_value.as_str().ok_or(anyhow!("fake_type: {}, lang should be a string", fake_type))?;
+        let fake_definition_elements = tuple
+            .iter()
+            .map(|value| FakeDefinitionElement::generate_with_registry(value, registry))
+            .collect::<Result<Vec<FakeDefinitionElement>>>()?;
 
-        Ok(T::new(fake_type.to_string(), lang.to_string(), format.to_string()).into())
+        Ok(FakeDefinitionElement::Tuple(Tuple::new(fake_type.to_string(), fake_definition_elements)))
     }
 
-    pub fn generate_array(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement> {
-        let count_value = fake_definition_element_setting.get("count").ok_or(anyhow!("fake_type: array, count is missing"))?;
-        let count = count_value.as_u64().ok_or(anyhow!("fake_type: array, count parse error. please 0 < count "))? as usize;
-
-        let mut deque = VecDeque::new();
-        let exclude_conditions = vec!["count", "fake_type"];
-        for (k, v) in fake_definition_element_setting {
-            if !exclude_conditions.contains(&k.as_str()) {
-                deque.push_back(FakeDefinitionElement::generate(v)?);
-            }
+    fn generate_array_length(params: &ArrayParams) -> Result<ArrayLength> {
+        match (params.count, params.min_count, params.max_count) {
+            (Some(count), None, None) => Ok(ArrayLength::Fixed(count)),
+            (None, Some(min_count), Some(max_count)) => ArrayLength::range(min_count, max_count, params.distribution),
+            (Some(_), _, _) => Err(anyhow!("fake_type: array, count can not be used together with min_count and max_count")),
+            (None, None, None) => Err(anyhow!("fake_type: array, count is missing")),
+            _ => Err(anyhow!("fake_type: array, both min_count and max_count are required")),
         }
-
-        let fake_definition_element = deque.pop_front().ok_or(anyhow!("fake_type: array, undefined fake_definition_element"))?;
-        Ok(FakeDefinitionElement::Array(Array::new(fake_type.to_string(), count, Box::new(fake_definition_element))))
     }
 
-    pub fn generate_map(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement> {
-        let mut btree_map = BTreeMap::new();
+    pub fn generate_map(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str, registry: &Registry) -> Result<FakeDefinitionElement> {
+        let mut index_map = IndexMap::new();
 
-        for (k, v) in fake_definition_element_setting {
-            if k.as_str().ne("fake_type") {
-                btree_map.insert(k.clone(), FakeDefinitionElement::generate(v)?);
-            }
+        for (k, v) in FakeDefinitionElement::map_fields(fake_definition_element_setting)? {
+            index_map.insert(k.clone(), FakeDefinitionElement::generate_with_registry(v, registry)?);
         }
 
-        if btree_map.len() > 0 {
-            let m = Map::new(fake_type.to_string(), btree_map);
+        let options = RecordOptions::from_json(fake_definition_element_setting).map_err(|e| anyhow!("fake_type: map, {}", e))?;
+
+        if !index_map.is_empty() {
+            let m = Map::new(fake_type.to_string(), index_map, options)?;
             Ok(FakeDefinitionElement::Map(m))
         } else {
             Err(anyhow!("fake_type: map, undefined fake_definition_element"))
         }
     }
 
+    /// Returns the child definitions of a map.
+    ///
+    /// They are either listed explicitly under `fields`, where any field name is allowed, or implicitly as every key
+    /// other than `fake_type` and the map options. A `fields` key which is itself a definition (it has a `fake_type`
+    /// string) is treated as an implicit child named `fields`. Since a definition is always an object, `unique`, `when`
+    /// and the record options (`unique_by`, `constraints`) are only taken as options when they are not objects.
+    fn map_fields(fake_definition_element_setting: &serde_json::Map<String, Value>) -> Result<Vec<(&String, &Value)>> {
+        let is_option = |k: &str, v: &Value| k == "fake_type" || (["unique", "when"].contains(&k) && !v.is_object()) || RecordOptions::is_option(k, v);
+
+        match fake_definition_element_setting.get("fields") {
+            Some(Value::Object(fields)) if !fields.get("fake_type").is_some_and(Value::is_string) => {
+                let unexpected_keys: Vec<&str> = fake_definition_element_setting
+                    .iter()
+                    .filter(|(k, v)| k.as_str() != "fields" && !is_option(k, v))
+                    .map(|(k, _)| k.as_str())
+                    .collect();
+                if !unexpected_keys.is_empty() {
+                    Err(anyhow!("fake_type: map, fields can not be used together with other fake_definition_element ({})", unexpected_keys.join(", ")))?
+                }
+
+                Ok(fields.iter().collect())
+            }
+            _ => {
+                Ok(fake_definition_element_setting.iter().filter(|(k, v)| !is_option(k, v)).collect())
+            }
+        }
+    }
+
+    pub fn generate_template(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str, registry: &Registry) -> Result<FakeDefinitionElement> {
+        let params = from_setting::<TemplateParams>(fake_definition_element_setting, fake_type)?;
+
+        Ok(FakeDefinitionElement::Template(Template::new_with_registry(fake_type.to_string(), params.lang, &params.template, registry)?))
+    }
+
+    pub fn generate_reference(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement> {
+        let params = from_setting::<ReferenceParams>(fake_definition_element_setting, fake_type)?;
+
+        Ok(FakeDefinitionElement::Reference(Reference::new(fake_type.to_string(), &params.reference, params.cardinality)?))
+    }
+
+    pub fn generate_sequence(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement> {
+        let params = from_setting::<SequenceParams>(fake_definition_element_setting, fake_type)?;
+
+        Ok(FakeDefinitionElement::Sequence(Sequence::new(fake_type.to_string(), params.start, params.step, params.format, params.scope)?))
+    }
+
+    pub fn generate_expr(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement> {
+        let params = from_setting::<ExprParams>(fake_definition_element_setting, fake_type)?;
+
+        Ok(FakeDefinitionElement::Expr(Expr::new(fake_type.to_string(), &params.expr)?))
+    }
+
+    pub fn generate_if(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str, registry: &Registry) -> Result<FakeDefinitionElement> {
+        let params = from_setting::<ConditionalParams>(fake_definition_element_setting, fake_type)?;
+        let then = FakeDefinitionElement::generate_with_registry(&params.then, registry)?;
+        let otherwise = match &params.otherwise {
+            Some(else_value) => Some(Box::new(FakeDefinitionElement::generate_with_registry(else_value, registry)?)),
+            None => None,
+        };
+
+        Ok(FakeDefinitionElement::Conditional(Conditional::new(fake_type.to_string(), &params.condition, Box::new(then), otherwise)?))
+    }
+
     pub fn generate_constant(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement> {
-        let value = fake_definition_element_setting.get("value").ok_or(anyhow!("fake_type: {}, value is missing", fake_type))?;
-        Ok(FakeDefinitionElement::Constant(Constant::new(fake_type.to_string(), value.clone())))
+        let params = from_setting::<ConstantParams>(fake_definition_element_setting, fake_type)?;
+
+        Ok(FakeDefinitionElement::Constant(Constant::new(fake_type.to_string(), params.value)))
     }
 }
 
 impl FakeDefinitionElement {
+    /// Parses a `FakeDefinitionElement` from JSON with the built-in fake types.
     pub fn generate(fake_definition_element_value: &Value) -> Result<FakeDefinitionElement> {
+        FakeDefinitionElement::generate_with_registry(fake_definition_element_value, Registry::builtin())
+    }
+
+    /// Parses a `FakeDefinitionElement` from JSON with the fake types registered in `registry`,
+    /// which is also used to parse the nested elements of arrays, maps and conditions.
+    pub fn generate_with_registry(fake_definition_element_value: &Value, registry: &Registry) -> Result<FakeDefinitionElement> {
         let fake_definition_element_setting = match fake_definition_element_value {
             Value::Object(map) => Ok(map),
             _ => Err(anyhow!("INVALID JSON FORMAT: fake_definition_element_settings is undefined"))
@@ -236,59 +492,44 @@ impl FakeDefinitionElement {
         let value = fake_definition_element_setting.get("fake_type").ok_or(anyhow!("fake_definition_element_settings: fake_type is missing"))?;
         let fake_type = value.as_str().ok_or(anyhow!("fake_definition_element_settings: fake_type convert error"))?;
 
-        let obj = match fake_type {
-            // Lorem
-            "word" => FakeDefinitionElement::generate_element::<Word>(fake_definition_element_setting, fake_type)?,
-            "words" => FakeDefinitionElement::generate_with_range_element::<Words>(fake_definition_element_setting, fake_type)?,
-            "sentence" => FakeDefinitionElement::generate_with_range_element::<Sentence>(fake_definition_element_setting, fake_type)?,
-            "sentences" => FakeDefinitionElement::generate_with_range_element::<Sentences>(fake_definition_element_setting, fake_type)?,
-            "paragraph" => FakeDefinitionElement::generate_with_range_element::<Paragraph>(fake_definition_element_setting, fake_type)?,
-            "paragraphs" => FakeDefinitionElement::generate_with_range_element::<Paragraphs>(fake_definition_element_setting, fake_type)?,
-
-            // Name
-            "first_name" => FakeDefinitionElement::generate_element::<FirstName>(fake_definition_element_setting, fake_type)?,
-            "last_name" => FakeDefinitionElement::generate_element::<LastName>(fake_definition_element_setting, fake_type)?,
-            "title" => FakeDefinitionElement::generate_element::<Title>(fake_definition_element_setting, fake_type)?,
-            "suffix" => FakeDefinitionElement::generate_element::<Suffix>(fake_definition_element_setting, fake_type)?,
-            "name" => FakeDefinitionElement::generate_element::<Name>(fake_definition_element_setting, fake_type)?,
-            "name_with_title" => FakeDefinitionElement::generate_element::<NameWithTitle>(fake_definition_element_setting, fake_type)?,
-
-            // Number
-            "digit" => FakeDefinitionElement::generate_element::<Digit>(fake_definition_element_setting, fake_type)?,
-            "number_with_format" => FakeDefinitionElement::generate_with_format_element::<NumberWithFormat>(fake_definition_element_setting, fake_type)?,
-
-            // Boolean
-            "boolean" => FakeDefinitionElement::generate_with_ratio_element::<Boolean>(fake_definition_element_setting, fake_type)?,
+        let obj = registry.construct(fake_definition_element_setting, fake_type)?;
+        let obj = FakeDefinitionElement::generate_unique(fake_definition_element_setting, fake_type, obj)?;
+        FakeDefinitionElement::generate_when(fake_definition_element_setting, fake_type, obj)
+    }
 
-            // Internet
-            "free_email_provider" => FakeDefinitionElement::generate_element::<FreeEmailProvider>(fake_definition_element_setting, fake_type)?,
-            "domain_suffix" => FakeDefinitionElement::generate_element::<DomainSuffix>(fake_definition_element_setting, fake_type)?,
-            "free_email" => FakeDefinitionElement::generate_element::<FreeEmail>(fake_definition_element_setting, fake_type)?,
-            "safe_email" => FakeDefinitionElement::generate_element::<SafeEmail>(fake_definition_element_setting, fake_type)?,
-            "username" => FakeDefinitionElement::generate_element::<Username>(fake_definition_element_setting, fake_type)?,
-            "password" => FakeDefinitionElement::generate_with_range_element::<Password>(fake_definition_element_setting, fake_type)?,
-            "ip_v4" => FakeDefinitionElement::generate_element::<IPv4>(fake_definition_element_setting, fake_type)?,
-            "ip_v6" => FakeDefinitionElement::generate_element::<IPv6>(fake_definition_element_setting, fake_type)?,
-            "ip" => FakeDefinitionElement::generate_element::<IP>(fake_definition_element_setting, fake_type)?,
-            "mac_address" => FakeDefinitionElement::generate_element::<MACAddress>(fake_definition_element_setting, fake_type)?,
-            "user_agent" => FakeDefinitionElement::generate_element::<UserAgent>(fake_definition_element_setting, fake_type)?,
+    /// Wraps `fake_definition_element` with a `Conditional` when it is set with a `when` condition,
+    /// so that it is only generated when the condition holds.
+    fn generate_when(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str, fake_definition_element: FakeDefinitionElement) -> Result<FakeDefinitionElement> {
+        match fake_definition_element_setting.get("when") {
+            Some(when_value) if !when_value.is_object() => {
+                let condition = when_value.as_str().ok_or(anyhow!("fake_type: {}, when should be a string", fake_type))?;
+                Ok(Conditional::new(fake_type.to_string(), condition, Box::new(fake_definition_element), None)?.into())
+            }
+            _ => Ok(fake_definition_element),
+        }
+    }
 
-            // FakeCliOriginal
-            "array" => FakeDefinitionElement::generate_array(fake_definition_element_setting, fake_type)?,
-            "map" => FakeDefinitionElement::generate_map(fake_definition_element_setting, fake_type)?,
-            "constant" => FakeDefinitionElement::generate_constant(fake_definition_element_setting, fake_type)?,
-            _ => {
-                Err(anyhow!("{} is missing fake_type", fake_type))?
+    /// Wraps `fake_definition_element` with `Unique` when it is set with `unique: true`.
+    /// An array keeps its own `unique`, which makes the elements of each array distinct instead.
+    fn generate_unique(fake_definition_element_setting: &serde_json::Map<String, Value>, fake_type: &str, fake_definition_element: FakeDefinitionElement) -> Result<FakeDefinitionElement> {
+        let unique = match fake_definition_element_setting.get("unique") {
+            Some(unique_value) if fake_type != "array" && !unique_value.is_object() => {
+                unique_value.as_bool().ok_or(anyhow!("fake_type: {}, unique should be a boolean", fake_type))?
             }
+            _ => false,
         };
 
-        Ok(obj)
+        match unique {
+            true => Ok(Unique::new(fake_type.to_string(), Box::new(fake_definition_element)).into()),
+            false => Ok(fake_definition_element),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::FakeDefinitionElement;
+    use crate::fake::generation_context::GenerationContext;
     use serde_json::{Value};
     use anyhow::Result;
 
@@ -458,7 +699,7 @@ mod tests {
 
     #[test]
     fn test_fake_definition_element_generate_for_password() {
-        let fd = generate_element_with_range("username", "EN", 1, 5);
+        let fd = generate_element_with_range("password", "EN", 1, 5);
         assert!(fd.is_ok(), "Should return Ok for a defined fake type");
     }
 
@@ -515,6 +756,75 @@ mod tests {
         assert!(fd.is_ok(), "Should return Ok for a defined fake type");
     }
 
+    #[test]
+    fn test_fake_definition_element_generate_for_array_with_range() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "array",
+            "min_count": 1,
+            "max_count": 3,
+            "distribution": "normal",
+            "unique": true,
+            "sort": "desc",
+            "example_word": { "fake_type": "word", "lang": "EN" }
+        });
+
+        let fd = FakeDefinitionElement::generate(&fake_definition_element);
+        assert!(fd.is_ok(), "Should return Ok for a defined fake type");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_array_with_invalid_length() {
+        let child = serde_json::json!({ "fake_type": "word", "lang": "EN" });
+
+        let with_count_and_range = serde_json::json!({ "fake_type": "array", "count": 2, "min_count": 1, "max_count": 3, "example_word": child });
+        assert!(FakeDefinitionElement::generate(&with_count_and_range).is_err(), "Should return an error when count and min_count/max_count are mixed");
+
+        let without_max_count = serde_json::json!({ "fake_type": "array", "min_count": 1, "example_word": child });
+        assert!(FakeDefinitionElement::generate(&without_max_count).is_err(), "Should return an error when max_count is missing");
+
+        let with_invalid_sort = serde_json::json!({ "fake_type": "array", "count": 2, "sort": "random", "example_word": child });
+        assert!(FakeDefinitionElement::generate(&with_invalid_sort).is_err(), "Should return an error for an undefined sort order");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_array_with_multiple_children() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "array",
+            "count": 2,
+            "example_word": { "fake_type": "word", "lang": "EN" },
+            "example_digit": { "fake_type": "digit", "lang": "EN" }
+        });
+
+        let fd = FakeDefinitionElement::generate(&fake_definition_element);
+        assert!(fd.is_err(), "Should return an error when an array has more than one child definition");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_tuple() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "array",
+            "tuple": [
+                { "fake_type": "constant", "value": 35.6 },
+                { "fake_type": "constant", "value": 139.7 }
+            ]
+        });
+
+        let fd = FakeDefinitionElement::generate(&fake_definition_element).unwrap();
+        assert_eq!(fd.to_value(&mut GenerationContext::new()).unwrap(), serde_json::json!([35.6, 139.7]), "Tuple elements should keep their position");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_tuple_with_count() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "array",
+            "count": 2,
+            "tuple": [{ "fake_type": "word", "lang": "EN" }]
+        });
+
+        let fd = FakeDefinitionElement::generate(&fake_definition_element);
+        assert!(fd.is_err(), "Should return an error when tuple is combined with count");
+    }
+
     #[test]
     fn test_fake_definition_element_generate_for_map() {
         let mut fake_definition_element = serde_json::Map::new();
@@ -530,6 +840,190 @@ mod tests {
         assert!(fd.is_ok(), "Should return Ok for a defined fake type");
     }
 
+    #[test]
+    fn test_fake_definition_element_generate_for_map_with_fields() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "map",
+            "fields": {
+                "fake_type": { "fake_type": "constant", "value": "reserved" },
+                "count": { "fake_type": "constant", "value": 1 }
+            }
+        });
+
+        let fd = FakeDefinitionElement::generate(&fake_definition_element).unwrap();
+        assert_eq!(fd.to_value(&mut GenerationContext::new()).unwrap(), serde_json::json!({ "fake_type": "reserved", "count": 1 }), "Reserved keys should be usable as field names under fields");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_map_with_fields_and_implicit_children() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "map",
+            "fields": { "count": { "fake_type": "constant", "value": 1 } },
+            "example_word": { "fake_type": "word", "lang": "EN" }
+        });
+
+        let fd = FakeDefinitionElement::generate(&fake_definition_element);
+        assert!(fd.is_err(), "Should return an error when fields is mixed with implicit children");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_map_with_implicit_fields_child() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "map",
+            "fields": { "fake_type": "constant", "value": 1 }
+        });
+
+        let fd = FakeDefinitionElement::generate(&fake_definition_element).unwrap();
+        assert_eq!(fd.to_value(&mut GenerationContext::new()).unwrap(), serde_json::json!({ "fields": 1 }), "A fields definition should remain an implicit child");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_with_fields_fail() {
+        let fake_definition_element = serde_json::json!({ "fake_type": "word", "fields": { "q": 1 } });
+
+        let error = FakeDefinitionElement::generate(&fake_definition_element).unwrap_err();
+        assert_eq!(error.to_string(), "fake_type: word, unknown field `fields`, expected `lang`", "Should return an error for fields on a fake type other than a map");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_array_with_items() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "array",
+            "count": 2,
+            "items": {
+                "fake_type": "map",
+                "fields": { "count": { "fake_type": "constant", "value": 1 } }
+            }
+        });
+
+        let fd = FakeDefinitionElement::generate(&fake_definition_element).unwrap();
+        assert_eq!(fd.to_value(&mut GenerationContext::new()).unwrap(), serde_json::json!([{ "count": 1 }, { "count": 1 }]));
+
+        let with_implicit_child = serde_json::json!({
+            "fake_type": "array",
+            "count": 2,
+            "items": { "fake_type": "word", "lang": "EN" },
+            "example_word": { "fake_type": "word", "lang": "EN" }
+        });
+        assert!(FakeDefinitionElement::generate(&with_implicit_child).is_err(), "Should return an error when items is mixed with implicit children");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_reference() {
+        let fake_definition_element = serde_json::json!({ "fake_type": "ref", "ref": "users.id", "cardinality": "1:N" });
+        let fd = FakeDefinitionElement::generate(&fake_definition_element).unwrap();
+        assert_eq!(fd.entity_references(), vec!["users".to_string()]);
+
+        let with_invalid_cardinality = serde_json::json!({ "fake_type": "ref", "ref": "users.id", "cardinality": "N:M" });
+        assert!(FakeDefinitionElement::generate(&with_invalid_cardinality).is_err(), "Should return an error for an undefined cardinality");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_sequence() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "array",
+            "count": 2,
+            "items": {
+                "fake_type": "map",
+                "order_id": { "fake_type": "sequence", "start": 100, "step": 10 },
+                "line_no": { "fake_type": "sequence", "format": "L##", "scope": "array" }
+            }
+        });
+        let fd = FakeDefinitionElement::generate(&fake_definition_element).unwrap();
+        let mut context = GenerationContext::new();
+
+        assert_eq!(fd.to_value(&mut context).unwrap(), serde_json::json!([{ "order_id": 100, "line_no": "L01" }, { "order_id": 110, "line_no": "L02" }]));
+        assert_eq!(fd.to_value(&mut context).unwrap(), serde_json::json!([{ "order_id": 120, "line_no": "L01" }, { "order_id": 130, "line_no": "L02" }]), "Only array scoped sequences should restart");
+
+        let with_invalid_step = serde_json::json!({ "fake_type": "sequence", "step": 1.5 });
+        assert!(FakeDefinitionElement::generate(&with_invalid_step).is_err(), "Should return an error for a non-integer step");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_unique() {
+        let fake_definition_element = serde_json::json!({ "fake_type": "ref", "ref": "users.id", "unique": true });
+        let fd = FakeDefinitionElement::generate(&fake_definition_element).unwrap();
+        assert!(matches!(fd, FakeDefinitionElement::Unique(_)), "Should wrap an element set with unique");
+        assert_eq!(fd.entity_references(), vec!["users".to_string()], "Unique should keep the entity references");
+
+        let map_with_fields = serde_json::json!({
+            "fake_type": "map",
+            "unique_by": [["unique"]],
+            "fields": { "unique": { "fake_type": "digit", "lang": "EN" } }
+        });
+        assert!(FakeDefinitionElement::generate(&map_with_fields).is_ok(), "unique_by should be allowed together with fields");
+
+        let map_with_unique_child = serde_json::json!({ "fake_type": "map", "unique": { "fake_type": "constant", "value": 1 } });
+        let fd = FakeDefinitionElement::generate(&map_with_unique_child).unwrap();
+        assert_eq!(fd.to_value(&mut GenerationContext::new()).unwrap(), serde_json::json!({ "unique": 1 }), "A unique definition should remain an implicit child");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_for_when() {
+        let fake_definition_element = serde_json::json!({
+            "fake_type": "map",
+            "payment_method": { "fake_type": "constant", "value": "bank" },
+            "card_number": { "fake_type": "constant", "value": "4111", "when": "payment_method == 'card'" },
+            "iban": { "fake_type": "constant", "value": "DE89", "when": "payment_method == 'bank'" },
+            "details": {
+                "fake_type": "if",
+                "if": "payment_method == 'card'",
+                "then": { "fake_type": "map", "brand": { "fake_type": "constant", "value": "visa" } },
+                "else": { "fake_type": "map", "bank_code": { "fake_type": "constant", "value": "370" } }
+            }
+        });
+        let fd = FakeDefinitionElement::generate(&fake_definition_element).unwrap();
+
+        assert_eq!(
+            fd.to_value(&mut GenerationContext::new()).unwrap(),
+            serde_json::json!({ "payment_method": "bank", "iban": "DE89", "details": { "bank_code": "370" } }),
+            "Fields should be omitted or picked by their conditions"
+        );
+
+        let array_with_when = serde_json::json!({ "fake_type": "array", "count": 1, "when": "false", "items": { "fake_type": "digit", "lang": "EN" } });
+        assert!(matches!(FakeDefinitionElement::generate(&array_with_when).unwrap(), FakeDefinitionElement::Conditional(_)), "when should be an option of an array");
+
+        let with_invalid_when = serde_json::json!({ "fake_type": "digit", "lang": "EN", "when": 1 });
+        assert!(FakeDefinitionElement::generate(&with_invalid_when).is_err(), "Should return an error for a non-string when");
+
+        let without_then = serde_json::json!({ "fake_type": "if", "if": "true" });
+        assert!(FakeDefinitionElement::generate(&without_then).is_err(), "Should return an error for an if without then");
+
+        let with_non_boolean_condition = serde_json::json!({
+            "fake_type": "map",
+            "name": { "fake_type": "name", "lang": "EN" },
+            "nickname": { "fake_type": "name", "lang": "EN", "when": "upper(name)" }
+        });
+        assert!(FakeDefinitionElement::generate(&with_non_boolean_condition).is_err(), "Should return an error for a condition which is not boolean");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_with_default_params() {
+        let word = FakeDefinitionElement::generate(&serde_json::json!({ "fake_type": "word" }));
+        assert!(word.is_ok(), "lang should be optional");
+
+        let boolean = FakeDefinitionElement::generate(&serde_json::json!({ "fake_type": "boolean", "unique": false })).unwrap();
+        assert!(boolean.to_value(&mut GenerationContext::new()).unwrap().is_boolean(), "ratio should be optional");
+    }
+
+    #[test]
+    fn test_fake_definition_element_generate_with_invalid_params() {
+        let missing = FakeDefinitionElement::generate(&serde_json::json!({ "fake_type": "words", "min": 1 })).unwrap_err();
+        assert!(missing.to_string().contains("fake_type: words, missing field `max`"), "Should name the missing parameter but got {}", missing);
+
+        let invalid = FakeDefinitionElement::generate(&serde_json::json!({ "fake_type": "number_with_format", "format": 1 }));
+        assert!(invalid.is_err(), "Should return an error for a parameter of the wrong type");
+
+        let ratio = FakeDefinitionElement::generate(&serde_json::json!({ "fake_type": "boolean", "ratio": 300 })).unwrap_err();
+        assert_eq!(ratio.to_string(), "fake_type: boolean, invalid value: integer `300`, expected an integer from 0 to 100", "Should reject the ratio instead of truncating it");
+
+        let count = FakeDefinitionElement::generate(&serde_json::json!({ "fake_type": "array", "count": 2.5, "items": { "fake_type": "word" } })).unwrap_err();
+        assert_eq!(count.to_string(), "fake_type: array, invalid type: floating point `2.5`, expected usize");
+
+        let cardinality = FakeDefinitionElement::generate(&serde_json::json!({ "fake_type": "ref", "ref": "users.id", "cardinality": "N:N" })).unwrap_err();
+        assert_eq!(cardinality.to_string(), "fake_type: ref, unknown variant `N:N`, expected one of `1:1`, `1:N`, `random`");
+    }
+
     #[test]
     fn test_fake_definition_element_generate_missing_fake_type() {
         let fd = generate_element("undefined_type", "EN");
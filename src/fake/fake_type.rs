@@ -1,10 +1,22 @@
 use serde_json::Value;
 use anyhow::{Result};
+use serde::de::DeserializeOwned;
+use crate::fake::fake_type::params::DescribeParams;
+use crate::fake::generation_context::GenerationContext;
 
 pub mod map;
 pub mod boolean;
 pub mod array;
+pub mod tuple;
 pub mod constant;
+pub mod template;
+pub mod reference;
+pub mod sequence;
+pub mod expr;
+pub mod conditional;
+pub mod custom;
+pub mod unique;
+pub mod params;
 pub mod name;
 pub mod internet;
 pub mod lorem;
@@ -14,54 +26,28 @@ pub mod number;
 ///
 /// The `fake_apply` method is where the fake data generation happens.
 /// The `to_value` method is used to convert the generated fake data to a `Value` for further operations.
+/// Both receive the `GenerationContext`, which exposes the values already generated for the surrounding fields.
 pub trait FakeType {
     type Response;
 
     /// Generates the fake data.
-    fn fake_apply(&self) -> Self::Response;
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response;
 
     /// Converts the generated fake data to a `Value`.
-    fn to_value(&self) -> Value;
+    /// Returns an error when the data can not be generated, e.g. when a uniqueness constraint can not be satisfied.
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value>;
 }
 
-/// The `FakeElement` trait is used for types that represent an element of fake data.
+/// The `FakeElement` trait is used for types that represent an element of fake data, built from the parameters
+/// set on it in a definition.
 ///
-/// The `new` method is used to create a new instance of the implementing type with the given `fake_type` and `lang`.
-pub trait FakeElement {
-    fn new(fake_type: String, lang: String) -> Self
-    where
-        Self: Sized;
+/// Each type declares its parameters as a `Params` struct, which is deserialized from the settings of the element,
+/// so a parameter with a serde default is optional, and which describes its parameters for `list-types` and
+/// `describe`. The `new` method is used to create a new instance of the implementing type with the given
+/// `fake_type` and parameters. It returns a `Result` to handle cases where the parameters are invalid,
+/// e.g. an empty range.
+pub trait FakeElement: Sized {
+    type Params: DeserializeOwned + DescribeParams;
+
+    fn new(fake_type: String, params: Self::Params) -> Result<Self>;
 }
-
-/// The `FakeWithRatioElement` trait is used for types that represent an element of fake data
-/// that can be weighted with a given ratio.
-///
-/// The `new` method is used to create a new instance of the implementing type with the given `fake_type`,
-/// `lang`, and `ratio`.
-pub trait FakeWithRatioElement {
-    fn new(fake_type: String, lang: String, ratio: u8) -> Self
-    where
-        Self: Sized;
-}
-
-/// The `FakeWithFormatElement` trait is used for types that represent an element of fake data
-/// that can be formatted in a specific way.
-///
-/// The `new` method is used to create a new instance of the implementing type with the given `fake_type`,
-/// `lang`, and `format`.
-pub trait FakeWithFormatElement {
-    fn new(fake_type: String, lang: String, format: String) -> Self
-    where
-        Self: Sized;
-}
-
-/// The `FakeWithRangeElement` trait is used for types that represent an element of fake data
-/// that should fall within a specific range.
-///
-/// The `new` method is used to create a new instance of the implementing type with the given `fake_type`,
-/// `lang`, `min`, and `max`. It returns a `Result` to handle cases where the provided range is invalid.
-pub trait FakeWithRangeElement {
-    fn new(fake_type: String, lang: String, min: usize, max: usize) -> Result<Self>
-    where
-        Self: Sized;
-}
\ No newline at end of file
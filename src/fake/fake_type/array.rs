@@ -1,9 +1,48 @@
-use serde_json::Value;
-use crate::fake::fake_definition_element::FakeDefinitionElement;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use anyhow::{anyhow, Result};
+use rand::Rng;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
 use crate::fake::fake_type::FakeType;
+use crate::fake::fake_type::unique::MAX_UNIQUE_ATTEMPTS;
+use crate::fake::generation_context::GenerationContext;
+
+/// `ArrayLength` decides how many elements an `Array` generates.
+///
+/// * `Fixed`: Always generates `count` elements.
+/// * `Range`: Picks a length between `min` and `max` (both inclusive) for every generated array,
+///   following the given `Distribution`.
+#[derive(Debug, PartialEq)]
+pub enum ArrayLength {
+    Fixed(usize),
+    Range { min: usize, max: usize, distribution: Distribution },
+}
+
+/// `Distribution` is the probability distribution used to pick the length of a variable-length `Array`.
+///
+/// * `Uniform`: Every length between `min` and `max` is equally likely.
+/// * `Normal`: Lengths cluster around the middle of `min` and `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Distribution {
+    #[default]
+    Uniform,
+    Normal,
+}
+
+/// `ArraySort` is the order the generated elements of an `Array` are sorted in.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArraySort {
+    Asc,
+    Desc,
+}
 
 /// `Array` is an implementation of `FakeType`. It generates an array of fake elements.
 /// The length of the array and the type of elements it contains are specified.
+/// The elements can optionally be made distinct (`unique`) and sorted (`sort`).
 ///
 /// # Attributes
 ///
@@ -12,40 +51,180 @@ use crate::fake::fake_type::FakeType;
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::array::{Array, ArrayLength};
+/// # use fake_cli::fake::fake_type::constant::Constant;
+/// # use fake_cli::{FakeDefinitionElement, FakeType, GenerationContext};
+/// # use serde_json::Value;
 /// // Create a new instance of Array with FakeDefinitionElement instances
-/// let a = Array::new("array", 5, Box::new(FakeDefinitionElement::Constant(Constant::new("constant".to_string(), Value::String("Hello, world!".to_string())))));
-/// let array = a.fake_apply();
+/// let a = Array::new("array".to_string(), ArrayLength::Fixed(5), false, None, Box::new(FakeDefinitionElement::Constant(Constant::new("constant".to_string(), Value::String("Hello, world!".to_string())))));
+/// let array = a.fake_apply(&mut GenerationContext::new()).unwrap();
 /// println!("Fake array: {:?}", array);
 /// ```
 #[derive(Debug)]
 pub struct Array {
     _fake_type: String,
-    count: usize,
+    length: ArrayLength,
+    unique: bool,
+    sort: Option<ArraySort>,
     fake_definition_element: Box<FakeDefinitionElement>,
 }
 
 impl FakeType for Array {
-    type Response = Vec<Value>;
+    type Response = Result<Vec<Value>>;
 
-    fn fake_apply(&self) -> Self::Response {
-        let mut array = Vec::new();
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let count = self.length.pick(context.rng());
+        context.push_array();
+        let array = match self.unique {
+            true => self.generate_unique(count, context),
+            false => (0..count)
+                .filter_map(|index| self.generate_element(index, context).transpose())
+                .collect::<Result<Vec<Value>>>(),
+        };
+        context.pop_array();
+        let mut array = array?;
 
-        for _ in 0..self.count {
-            let fake_definition_value = self.fake_definition_element.to_value();
-            array.push(fake_definition_value.clone());
+        match self.sort {
+            Some(ArraySort::Asc) => array.sort_by(compare_values),
+            Some(ArraySort::Desc) => array.sort_by(|a, b| compare_values(b, a)),
+            None => (),
         }
 
-        array
+        Ok(array)
     }
 
-    fn to_value(&self) -> Value {
-        Value::Array(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::Array(self.fake_apply(context)?))
     }
 }
 
 impl Array {
-    pub fn new(_fake_type: String, count: usize, fake_definition_element: Box<FakeDefinitionElement>) -> Self {
-        Self { _fake_type, count, fake_definition_element }
+    pub fn new(_fake_type: String, length: ArrayLength, unique: bool, sort: Option<ArraySort>, fake_definition_element: Box<FakeDefinitionElement>) -> Self {
+        Self { _fake_type, length, unique, sort, fake_definition_element }
+    }
+
+    /// Returns the names referred to by the element which have to be resolved by an enclosing map.
+    pub fn references(&self) -> Vec<String> {
+        self.fake_definition_element.references()
+    }
+
+    /// Returns the names of the dataset entities the element samples values from.
+    pub fn entity_references(&self) -> Vec<String> {
+        self.fake_definition_element.entity_references()
+    }
+
+    /// Appends the state of the stateful element to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        self.fake_definition_element.save_state(states)
+    }
+
+    /// Restores the state of the stateful element from `states`.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        self.fake_definition_element.restore_state(states)
+    }
+
+    /// Returns the JSON Schema of the generated arrays. An element omitted by a `when` condition is left out of
+    /// the array, so the array may then be shorter than its length.
+    pub fn output_schema(&self) -> Value {
+        let (items, always) = self.fake_definition_element.field_output_schema();
+        let (min_items, max_items) = match self.length {
+            ArrayLength::Fixed(count) => (count, count),
+            ArrayLength::Range { min, max, .. } => (min, max),
+        };
+
+        let mut schema = json!({ "type": "array", "items": items, "minItems": if always { min_items } else { 0 }, "maxItems": max_items });
+        if self.unique {
+            schema["uniqueItems"] = json!(true);
+        }
+        schema
+    }
+
+    /// Generates the element at `index`, recording its index and path in `context`.
+    /// Returns `None` when the element is omitted, e.g. by a `when` condition, and left out of the array.
+    fn generate_element(&self, index: usize, context: &mut GenerationContext) -> Result<Option<Value>> {
+        context.set_array_index(index);
+        context.push_path(index.to_string());
+        let value = self.fake_definition_element.to_field_value(context);
+        context.pop_path();
+
+        value
+    }
+
+    /// Generates `count` distinct elements, retrying each element up to `MAX_UNIQUE_ATTEMPTS` times.
+    fn generate_unique(&self, count: usize, context: &mut GenerationContext) -> Result<Vec<Value>> {
+        let mut seen = HashSet::new();
+        let mut array = Vec::with_capacity(count);
+
+        for index in 0..count {
+            let value = (0..MAX_UNIQUE_ATTEMPTS)
+                .map(|_| self.generate_element(index, context))
+                .find(|value| match value {
+                    Ok(Some(value)) => !seen.contains(&value.to_string()),
+                    _ => true,
+                })
+                .ok_or(anyhow!("fake_type: array, unique elements exhausted after {} attempts. generated {} of {} elements", MAX_UNIQUE_ATTEMPTS, array.len(), count))??;
+
+            if let Some(value) = value {
+                seen.insert(value.to_string());
+                array.push(value);
+            }
+        }
+
+        Ok(array)
+    }
+}
+
+impl ArrayLength {
+    /// Creates a variable length, validating that `min <= max`.
+    pub fn range(min: usize, max: usize, distribution: Distribution) -> Result<Self> {
+        match (min, max) {
+            (min, max) if min > max => {
+                Err(anyhow!("fake_type: array, please setting 0 <= min_count, 0 <= max_count, min_count <= max_count"))
+            }
+            _ => {
+                Ok(ArrayLength::Range { min, max, distribution })
+            }
+        }
+    }
+
+    /// Picks the length of the next generated array.
+    fn pick<R: Rng>(&self, rng: &mut R) -> usize {
+        match self {
+            ArrayLength::Fixed(count) => *count,
+            ArrayLength::Range { min, max, distribution } => distribution.sample(rng, *min, *max),
+        }
+    }
+}
+
+impl Distribution {
+    /// Samples an integer between `min` and `max` (both inclusive).
+    fn sample<R: Rng>(&self, rng: &mut R, min: usize, max: usize) -> usize {
+        match self {
+            Distribution::Uniform => rng.gen_range(min..=max),
+            Distribution::Normal => {
+                // Box-Muller transform, with 99.7% of the samples falling between min and max.
+                let mean = (min + max) as f64 / 2.0;
+                let std_dev = (max - min) as f64 / 6.0;
+                let u1: f64 = 1.0 - rng.gen::<f64>();
+                let u2: f64 = rng.gen();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+
+                (mean + z * std_dev).round().clamp(min as f64, max as f64) as usize
+            }
+        }
+    }
+}
+
+/// Compares two generated values. Numbers are compared numerically, strings and booleans naturally
+/// and everything else by its JSON representation.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            a.as_f64().partial_cmp(&b.as_f64()).unwrap_or(Ordering::Equal)
+        }
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (a, b) => a.to_string().cmp(&b.to_string()),
     }
 }
 
@@ -57,41 +236,85 @@ impl From<Array> for FakeDefinitionElement {
 
 #[cfg(test)]
 mod tests {
-    use super::Array;
+    use super::{Array, ArrayLength, ArraySort, Distribution};
     use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_definition_element::FakeDefinitionElement;
     use crate::fake::fake_type::constant::Constant;
+    use crate::fake::fake_type::number::digit::Digit;
+    use crate::fake::fake_type::params::DigitParams;
+    use crate::fake::fake_type::FakeElement;
     use serde_json::Value;
 
+    fn constant_element() -> Box<FakeDefinitionElement> {
+        Box::new(
+            FakeDefinitionElement::Constant(
+                Constant::new("constant".to_string(), Value::String("Hello, world!".to_string()))
+            )
+        )
+    }
+
+    fn digit_element() -> Box<FakeDefinitionElement> {
+        Box::new(FakeDefinitionElement::Digit(Digit::new("digit".to_string(), DigitParams::new("EN")).unwrap()))
+    }
+
     #[test]
     fn test_array_fake_apply() {
-        let a = Array::new(
-            "array".to_string(),
-            3,
-            Box::new(
-                FakeDefinitionElement::Constant(
-                    Constant::new("constant".to_string(), Value::String("Hello, world!".to_string()))
-                )
-            ),
-        );
-        let array_value = a.fake_apply();
+        let a = Array::new("array".to_string(), ArrayLength::Fixed(3), false, None, constant_element());
+        let array_value = a.fake_apply(&mut GenerationContext::new()).unwrap();
 
         assert_eq!(array_value.len(), 3, "Generated array should have 3 elements");
     }
 
     #[test]
     fn test_array_new() {
-        let a = Array::new(
-            "array".to_string(),
-            3,
-            Box::new(
-                FakeDefinitionElement::Constant(
-                    Constant::new("constant".to_string(), Value::String("Hello, world!".to_string()))
-                )
-            ),
-        );
+        let a = Array::new("array".to_string(), ArrayLength::Fixed(3), false, None, constant_element());
 
         assert_eq!(a._fake_type, "array");
-        assert_eq!(a.count, 3);
+        assert_eq!(a.length, ArrayLength::Fixed(3));
+    }
+
+    #[test]
+    fn test_array_fake_apply_with_range() {
+        for distribution in [Distribution::Uniform, Distribution::Normal] {
+            let length = ArrayLength::range(2, 4, distribution).unwrap();
+            let a = Array::new("array".to_string(), length, false, None, constant_element());
+
+            for _ in 0..20 {
+                let len = a.fake_apply(&mut GenerationContext::new()).unwrap().len();
+                assert!((2..=4).contains(&len), "Generated array length should be within the range");
+            }
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_array_length_range_fail() {
+        let length = ArrayLength::range(4, 2, Distribution::Uniform);
+
+        assert!(length.is_err());
+    }
+
+    #[test]
+    fn test_array_fake_apply_with_unique_and_sort() {
+        let a = Array::new("array".to_string(), ArrayLength::Fixed(10), true, Some(ArraySort::Asc), digit_element());
+        let array_value = a.fake_apply(&mut GenerationContext::new()).unwrap();
+
+        let expected: Vec<Value> = (0..10).map(Value::from).collect();
+        assert_eq!(array_value, expected, "Generated array should contain every digit once in ascending order");
+    }
+
+    #[test]
+    fn test_array_fake_apply_with_unique_exhausted() {
+        let a = Array::new("array".to_string(), ArrayLength::Fixed(2), true, None, constant_element());
+
+        assert!(a.fake_apply(&mut GenerationContext::new()).is_err(), "Should return an error when unique elements can not be generated");
+    }
+
+    #[test]
+    fn test_array_fake_apply_with_sort_desc() {
+        let a = Array::new("array".to_string(), ArrayLength::Fixed(20), false, Some(ArraySort::Desc), digit_element());
+        let array_value = a.fake_apply(&mut GenerationContext::new()).unwrap();
+
+        assert!(array_value.windows(2).all(|w| w[0].as_u64() >= w[1].as_u64()), "Generated array should be sorted in descending order");
+    }
+}
@@ -1 +1,2 @@
+#[allow(clippy::module_inception)]
 pub mod boolean;
\ No newline at end of file
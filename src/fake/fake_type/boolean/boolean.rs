@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::boolean::raw;
+use anyhow::{anyhow, Result};
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
-use crate::fake::fake_type::{FakeType, FakeWithRatioElement};
+use crate::fake::generation_context::GenerationContext;
+use crate::fake::fake_type::{FakeType, FakeElement};
+use crate::fake::fake_type::params::RatioParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `Boolean` is an implementation of `FakeType`. It generates a boolean value 
@@ -11,14 +14,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Boolean` with the `fake_apply` and `to_value` methods.
-/// * `FakeWithRatioElement`: This provides `Boolean` with the `new` method.
+/// * `FakeElement`: This provides `Boolean` with the `new` method, taking `RatioParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::boolean::boolean::Boolean;
+/// # use fake_cli::fake::fake_type::params::RatioParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Boolean, specifying "English" as the language and a ratio of 30
-/// let b = Boolean::new("boolean", "English", 30);
-/// let boolean = b.fake_apply();
+/// let b = Boolean::new("boolean".to_string(), RatioParams::new("English", 30)).unwrap();
+/// let boolean = b.fake_apply(&mut GenerationContext::new());
 /// println!("Fake boolean: {}", boolean);
 /// ```
 #[derive(Debug)]
@@ -31,27 +38,33 @@ pub struct Boolean {
 impl FakeType for Boolean {
     type Response = bool;
 
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => raw::Boolean(l, self.ratio).fake(),
-            Language::En(l) => raw::Boolean(l, self.ratio).fake(),
-            Language::ArSa(l) => raw::Boolean(l, self.ratio).fake(),
-            Language::FrFr(l) => raw::Boolean(l, self.ratio).fake(),
-            Language::PtBr(l) => raw::Boolean(l, self.ratio).fake(),
-            Language::ZhCn(l) => raw::Boolean(l, self.ratio).fake(),
-            Language::ZhTw(l) => raw::Boolean(l, self.ratio).fake(),
+            Language::JaJp(l) => raw::Boolean(l, self.ratio).fake_with_rng(context.rng()),
+            Language::En(l) => raw::Boolean(l, self.ratio).fake_with_rng(context.rng()),
+            Language::ArSa(l) => raw::Boolean(l, self.ratio).fake_with_rng(context.rng()),
+            Language::FrFr(l) => raw::Boolean(l, self.ratio).fake_with_rng(context.rng()),
+            Language::PtBr(l) => raw::Boolean(l, self.ratio).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => raw::Boolean(l, self.ratio).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => raw::Boolean(l, self.ratio).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::Bool(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::Bool(self.fake_apply(context)))
     }
 }
 
-impl FakeWithRatioElement for Boolean {
-    fn new(_fake_type: String, lang: String, ratio: u8) -> Self {
-        Self { _fake_type, lang, ratio }
+impl FakeElement for Boolean {
+    type Params = RatioParams;
+
+    fn new(_fake_type: String, params: RatioParams) -> Result<Self> {
+        if params.ratio > 100 {
+            Err(anyhow!("fake_type: boolean, ratio should be from 0 to 100 but got {}", params.ratio))?
+        }
+
+        Ok(Self { _fake_type, lang: params.lang, ratio: params.ratio })
     }
 }
 
@@ -64,22 +77,26 @@ impl From<Boolean> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Boolean;
-    use crate::fake::fake_type::{FakeType, FakeWithRatioElement};
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::fake_type::{FakeType, FakeElement};
+    use crate::fake::fake_type::params::RatioParams;
 
     #[test]
+    #[allow(clippy::bool_comparison)]
     fn test_boolean_fake_apply() {
-        let b = Boolean::new("boolean".to_string(), "English".to_string(), 30);
-        let boolean_value = b.fake_apply();
+        let b = Boolean::new("boolean".to_string(), RatioParams::new("English", 30)).unwrap();
+        let boolean_value = b.fake_apply(&mut GenerationContext::new());
 
         assert!(boolean_value == true || boolean_value == false, "Generated value should be a boolean");
     }
 
     #[test]
     fn test_boolean_new() {
-        let b = Boolean::new("boolean".to_string(), "English".to_string(), 30);
+        let b = Boolean::new("boolean".to_string(), RatioParams::new("English", 30)).unwrap();
 
         assert_eq!(b._fake_type, "boolean");
         assert_eq!(b.lang, "English");
         assert_eq!(b.ratio, 30);
+        assert!(Boolean::new("boolean".to_string(), RatioParams::new("English", 101)).is_err(), "Should return an error for a ratio over 100");
     }
 }
\ No newline at end of file
@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use crate::fake::expression::{Expression, ValueType};
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
+use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::GenerationContext;
+
+/// `Conditional` is an implementation of `FakeType`. It evaluates a boolean `Expression` on the values of other
+/// fields, e.g. `payment_method == 'card'`, and generates `then` when it holds and `otherwise` when it does not.
+///
+/// Without `otherwise`, nothing is generated when the condition does not hold, and an enclosing map omits the field
+/// or an enclosing array leaves the element out.
+/// This is how a definition element set with `when` is generated.
+///
+/// # Attributes
+///
+/// * `FakeType`: This provides `Conditional` with the `fake_apply` and `to_value` methods.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::fake_type::conditional::Conditional;
+/// # use fake_cli::fake::fake_type::constant::Constant;
+/// # use fake_cli::{FakeDefinitionElement, FakeType, GenerationContext};
+/// # use serde_json::json;
+/// // Create a new instance of Conditional, generating a card number only for card payments
+/// let then = FakeDefinitionElement::Constant(Constant::new("constant".to_string(), json!("4111-1111-1111-1111")));
+/// let c = Conditional::new("if".to_string(), "payment_method == 'card'", Box::new(then), None).unwrap();
+/// let mut context = GenerationContext::new();
+/// context.push_scope();
+/// context.insert("payment_method".to_string(), json!("card"));
+/// let card_number = c.fake_apply(&mut context).unwrap();
+/// println!("Fake card number: {:?}", card_number);
+/// ```
+#[derive(Debug)]
+pub struct Conditional {
+    _fake_type: String,
+    condition: Expression,
+    then: Box<FakeDefinitionElement>,
+    otherwise: Option<Box<FakeDefinitionElement>>,
+}
+
+impl FakeType for Conditional {
+    type Response = Result<Option<Value>>;
+
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let condition = self.condition.evaluate(context).map_err(|e| anyhow!("fake_type: {}, {}", self._fake_type, e))?;
+        let holds = condition
+            .as_bool()
+            .ok_or(anyhow!("fake_type: {}, condition \"{}\" should be a boolean but got {}", self._fake_type, self.condition.source(), condition))?;
+
+        match (holds, &self.otherwise) {
+            (true, _) => self.then.to_field_value(context),
+            (false, Some(otherwise)) => otherwise.to_field_value(context),
+            (false, None) => Ok(None),
+        }
+    }
+
+    /// Generates `null` when nothing is generated, e.g. as an element of a tuple.
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(self.fake_apply(context)?.unwrap_or(Value::Null))
+    }
+}
+
+impl Conditional {
+    /// Creates a new `Conditional`. Returns an error when `condition` can not be parsed.
+    pub fn new(_fake_type: String, condition: &str, then: Box<FakeDefinitionElement>, otherwise: Option<Box<FakeDefinitionElement>>) -> Result<Self> {
+        let condition = Expression::parse(condition).map_err(|e| anyhow!("fake_type: {}, {}", _fake_type, e))?;
+        Ok(Self { _fake_type, condition, then, otherwise })
+    }
+
+    /// Returns the names the condition and both branches refer to.
+    pub fn references(&self) -> Vec<String> {
+        let mut references = self.condition.references();
+        for reference in self.branches().flat_map(FakeDefinitionElement::references) {
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+
+        references
+    }
+
+    /// Returns the names of the dataset entities both branches sample values from.
+    pub fn entity_references(&self) -> Vec<String> {
+        self.branches().flat_map(FakeDefinitionElement::entity_references).collect()
+    }
+
+    /// Appends the state of the stateful branches to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        for branch in self.branches() {
+            branch.save_state(states);
+        }
+    }
+
+    /// Restores the state of the stateful branches from `states`.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        for branch in self.branches() {
+            branch.restore_state(states);
+        }
+    }
+
+    /// Checks that the condition is a boolean expression on fields of the right types.
+    pub fn check(&self, field_type: &dyn Fn(&str) -> Option<ValueType>) -> Result<()> {
+        let condition_type = self.condition.infer(field_type).map_err(|e| anyhow!("fake_type: {}, {}", self._fake_type, e))?;
+        if !condition_type.is(ValueType::Boolean) {
+            Err(anyhow!("fake_type: {}, condition \"{}\" should be a boolean expression but is a {}", self._fake_type, self.condition.source(), condition_type))?
+        }
+
+        Ok(())
+    }
+
+    /// Returns the type of the generated values: the type shared by both branches, or any type when the branches
+    /// differ or the value may be omitted.
+    pub fn value_type(&self) -> ValueType {
+        match &self.otherwise {
+            Some(otherwise) if self.then.value_type() == otherwise.value_type() => otherwise.value_type(),
+            _ => ValueType::Any,
+        }
+    }
+
+    /// Returns the JSON Schema of the generated values, which are `null` when nothing is generated.
+    pub fn output_schema(&self) -> Value {
+        match self.field_output_schema() {
+            (schema, true) => schema,
+            (schema, false) => any_of(schema, json!({ "type": "null" })),
+        }
+    }
+
+    /// Returns the JSON Schema of the values of either branch, and whether a value is always generated,
+    /// i.e. there is an `else` branch and neither branch is omitted itself.
+    pub fn field_output_schema(&self) -> (Value, bool) {
+        let (then, then_always) = self.then.field_output_schema();
+        match &self.otherwise {
+            Some(otherwise) => {
+                let (otherwise, otherwise_always) = otherwise.field_output_schema();
+                (any_of(then, otherwise), then_always && otherwise_always)
+            }
+            None => (then, false),
+        }
+    }
+
+    fn branches(&self) -> impl Iterator<Item = &FakeDefinitionElement> {
+        std::iter::once(self.then.as_ref()).chain(self.otherwise.as_deref())
+    }
+}
+
+/// Returns the JSON Schema of the values of either `a` or `b`, merging the alternatives of nested unions.
+fn any_of(a: Value, b: Value) -> Value {
+    let alternatives = |schema: Value| match schema.get("anyOf").and_then(Value::as_array) {
+        Some(alternatives) if schema.as_object().is_some_and(|schema| schema.len() == 1) => alternatives.clone(),
+        _ => vec![schema],
+    };
+
+    let mut schemas: Vec<Value> = Vec::new();
+    for schema in alternatives(a).into_iter().chain(alternatives(b)) {
+        if schema == json!({}) {
+            return schema;
+        }
+        if !schemas.contains(&schema) {
+            schemas.push(schema);
+        }
+    }
+
+    match schemas.len() {
+        1 => schemas.remove(0),
+        _ => json!({ "anyOf": schemas }),
+    }
+}
+
+impl From<Conditional> for FakeDefinitionElement {
+    fn from(value: Conditional) -> Self {
+        FakeDefinitionElement::Conditional(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Conditional;
+    use crate::fake::expression::ValueType;
+    use crate::fake::fake_definition_element::FakeDefinitionElement;
+    use crate::fake::fake_type::constant::Constant;
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::{json, Value};
+
+    fn constant(value: Value) -> Box<FakeDefinitionElement> {
+        Box::new(FakeDefinitionElement::Constant(Constant::new("constant".to_string(), value)))
+    }
+
+    fn context(payment_method: &str) -> GenerationContext {
+        let mut context = GenerationContext::new();
+        context.push_scope();
+        context.insert("payment_method".to_string(), json!(payment_method));
+        context
+    }
+
+    #[test]
+    fn test_conditional_fake_apply() {
+        let c = Conditional::new("if".to_string(), "payment_method == 'card'", constant(json!("card")), Some(constant(json!("bank")))).unwrap();
+
+        assert_eq!(c.fake_apply(&mut context("card")).unwrap(), Some(json!("card")));
+        assert_eq!(c.fake_apply(&mut context("bank")).unwrap(), Some(json!("bank")));
+    }
+
+    #[test]
+    fn test_conditional_fake_apply_without_otherwise() {
+        let c = Conditional::new("constant".to_string(), "payment_method == 'card'", constant(json!("card")), None).unwrap();
+
+        assert_eq!(c.fake_apply(&mut context("bank")).unwrap(), None, "Nothing should be generated when the condition does not hold");
+        assert_eq!(c.to_value(&mut context("bank")).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_conditional_new() {
+        let c = Conditional::new("if".to_string(), "payment_method == 'card'", constant(json!(1)), Some(constant(json!(2)))).unwrap();
+
+        assert_eq!(c._fake_type, "if");
+        assert_eq!(c.references(), vec!["payment_method".to_string()]);
+        assert_eq!(c.value_type(), ValueType::Number);
+        assert!(c.check(&|_| Some(ValueType::String)).is_ok());
+        assert!(Conditional::new("if".to_string(), "payment_method ==", constant(json!(1)), None).is_err(), "Should return an error for a syntax error");
+
+        let not_boolean = Conditional::new("if".to_string(), "upper(payment_method)", constant(json!(1)), None).unwrap();
+        assert!(not_boolean.check(&|_| Some(ValueType::String)).is_err(), "Should return an error for a condition which is not boolean");
+    }
+}
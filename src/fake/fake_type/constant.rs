@@ -1,5 +1,7 @@
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeType};
 
 /// `Constant` is an implementation of `FakeType`. It doesn't generate a fake value but instead returns
@@ -12,9 +14,12 @@ use crate::fake::fake_type::{FakeType};
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::constant::Constant;
+/// # use fake_cli::{FakeType, GenerationContext};
+/// # use serde_json::Value;
 /// // Create a new instance of Constant, specifying "constant" as the type and a constant value
-/// let c = Constant::new("constant", Value::String("Hello, world!".to_string()));
-/// let constant = c.fake_apply();
+/// let c = Constant::new("constant".to_string(), Value::String("Hello, world!".to_string()));
+/// let constant = c.fake_apply(&mut GenerationContext::new());
 /// println!("Constant value: {}", constant);
 /// ```
 #[derive(Debug)]
@@ -26,12 +31,12 @@ pub struct Constant {
 impl FakeType for Constant {
     type Response = Value;
 
-    fn fake_apply(&self) -> Self::Response {
+    fn fake_apply(&self, _context: &mut GenerationContext) -> Self::Response {
         self.value.clone()
     }
 
-    fn to_value(&self) -> Value {
-        self.fake_apply()
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(self.fake_apply(context))
     }
 }
 
@@ -39,6 +44,11 @@ impl Constant {
     pub fn new(_fake_type: String, value: Value) -> Self {
         Self { _fake_type, value }
     }
+
+    /// Returns the constant value.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
 }
 
 impl From<Constant> for FakeDefinitionElement {
@@ -50,13 +60,14 @@ impl From<Constant> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Constant;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::FakeType;
     use serde_json::Value;
 
     #[test]
     fn test_constant_fake_apply() {
         let c = Constant::new("constant".to_string(), Value::String("Hello, world!".to_string()));
-        let constant_value = c.fake_apply();
+        let constant_value = c.fake_apply(&mut GenerationContext::new());
 
         assert_eq!(constant_value, Value::String("Hello, world!".to_string()));
     }
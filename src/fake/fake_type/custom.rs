@@ -0,0 +1,126 @@
+use std::fmt::Debug;
+use anyhow::Result;
+use serde_json::Value;
+use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::GenerationContext;
+
+/// `Custom` is an implementation of `FakeType`. It wraps a fake type defined outside of this crate and registered
+/// in a `Registry`, so that it can be used in a definition like any built-in fake type.
+///
+/// Any type implementing `FakeType` and `Debug` can be wrapped, whatever its `Response` is,
+/// since only its `to_value` is used.
+///
+/// # Attributes
+///
+/// * `FakeType`: This provides `Custom` with the `fake_apply` and `to_value` methods.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::{Custom, FakeType, GenerationContext};
+/// # use anyhow::Result;
+/// # use serde_json::Value;
+/// #
+/// # #[derive(Debug)]
+/// # struct Sku { prefix: String }
+/// #
+/// # impl Sku {
+/// #     fn new(prefix: &str) -> Self { Self { prefix: prefix.to_string() } }
+/// # }
+/// #
+/// # impl FakeType for Sku {
+/// #     type Response = String;
+/// #     fn fake_apply(&self, _: &mut GenerationContext) -> String { format!("{}-0001", self.prefix) }
+/// #     fn to_value(&self, context: &mut GenerationContext) -> Result<Value> { Ok(Value::String(self.fake_apply(context))) }
+/// # }
+/// #
+/// // Create a new instance of Custom, wrapping a downstream SKU generator
+/// let c = Custom::new("sku".to_string(), Sku::new("ABC"));
+/// let sku = c.fake_apply(&mut GenerationContext::new()).unwrap();
+/// println!("Fake SKU: {}", sku);
+/// ```
+#[derive(Debug)]
+pub struct Custom {
+    _fake_type: String,
+    generator: Box<dyn CustomFakeType>,
+}
+
+/// The object-safe part of `FakeType` a `Custom` generates its values with.
+trait CustomFakeType: Debug {
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value>;
+}
+
+impl<T: FakeType + Debug> CustomFakeType for T {
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        FakeType::to_value(self, context)
+    }
+}
+
+impl FakeType for Custom {
+    type Response = Result<Value>;
+
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        self.generator.to_value(context)
+    }
+
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        self.fake_apply(context)
+    }
+}
+
+impl Custom {
+    pub fn new<T: FakeType + Debug + 'static>(_fake_type: String, generator: T) -> Self {
+        Self { _fake_type, generator: Box::new(generator) }
+    }
+
+    /// Returns the name the fake type is registered with.
+    pub fn fake_type(&self) -> &str {
+        &self._fake_type
+    }
+}
+
+impl From<Custom> for FakeDefinitionElement {
+    fn from(value: Custom) -> Self {
+        FakeDefinitionElement::Custom(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Custom;
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use rand::Rng;
+    use serde_json::{json, Value};
+
+    #[derive(Debug)]
+    struct Sku {
+        prefix: String,
+    }
+
+    impl FakeType for Sku {
+        type Response = String;
+
+        fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+            format!("{}-{:04}", self.prefix, context.rng().gen_range(0..10000))
+        }
+
+        fn to_value(&self, context: &mut GenerationContext) -> anyhow::Result<Value> {
+            Ok(Value::String(self.fake_apply(context)))
+        }
+    }
+
+    #[test]
+    fn test_custom_fake_apply() {
+        let c = Custom::new("sku".to_string(), Sku { prefix: "ABC".to_string() });
+        let sku = c.fake_apply(&mut GenerationContext::new()).unwrap();
+
+        assert_eq!(c.fake_type(), "sku");
+        assert!(sku.as_str().is_some_and(|sku| sku.starts_with("ABC-") && sku.len() == 8), "Should generate a SKU but got {}", sku);
+
+        let generate = |seed: u64| c.to_value(&mut GenerationContext::with_seed(seed)).unwrap();
+        assert_eq!(generate(1), generate(1), "The same seed should generate the same value");
+        assert_ne!(json!(null), generate(1));
+    }
+}
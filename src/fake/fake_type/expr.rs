@@ -0,0 +1,103 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use crate::fake::expression::{Expression, ValueType};
+use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::GenerationContext;
+
+/// `Expr` is an implementation of `FakeType`. It computes a value from the values of other fields with an
+/// `Expression`, e.g. `price * quantity` or `years_between(birth_date, today()) >= 18`.
+///
+/// Like template placeholders, the fields are looked up from the sibling fields to the ancestor fields,
+/// and the fields it refers to are generated first.
+///
+/// # Attributes
+///
+/// * `FakeType`: This provides `Expr` with the `fake_apply` and `to_value` methods.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::fake_type::expr::Expr;
+/// # use fake_cli::{FakeType, GenerationContext};
+/// # use serde_json::json;
+/// // Create a new instance of Expr, computing the total of an order
+/// let e = Expr::new("expr".to_string(), "price * quantity").unwrap();
+/// let mut context = GenerationContext::new();
+/// context.push_scope();
+/// context.insert("price".to_string(), json!(120));
+/// context.insert("quantity".to_string(), json!(3));
+/// let total = e.fake_apply(&mut context).unwrap();
+/// println!("Fake total: {}", total);
+/// ```
+#[derive(Debug)]
+pub struct Expr {
+    _fake_type: String,
+    expression: Expression,
+}
+
+impl FakeType for Expr {
+    type Response = Result<Value>;
+
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        self.expression.evaluate(context).map_err(|e| anyhow!("fake_type: {}, {}", self._fake_type, e))
+    }
+
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        self.fake_apply(context)
+    }
+}
+
+impl Expr {
+    /// Creates a new `Expr`. Returns an error when `expression` can not be parsed.
+    pub fn new(_fake_type: String, expression: &str) -> Result<Self> {
+        let expression = Expression::parse(expression).map_err(|e| anyhow!("fake_type: {}, {}", _fake_type, e))?;
+        Ok(Self { _fake_type, expression })
+    }
+
+    /// Returns the names of the fields the expression refers to.
+    pub fn references(&self) -> Vec<String> {
+        self.expression.references()
+    }
+
+    /// Checks the types of the expression against the types of the fields, and returns the type of the computed value.
+    pub fn infer(&self, field_type: &dyn Fn(&str) -> Option<ValueType>) -> Result<ValueType> {
+        self.expression.infer(field_type).map_err(|e| anyhow!("fake_type: {}, {}", self._fake_type, e))
+    }
+}
+
+impl From<Expr> for FakeDefinitionElement {
+    fn from(value: Expr) -> Self {
+        FakeDefinitionElement::Expr(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Expr;
+    use crate::fake::expression::ValueType;
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::json;
+
+    #[test]
+    fn test_expr_fake_apply() {
+        let e = Expr::new("expr".to_string(), "price * quantity").unwrap();
+        let mut context = GenerationContext::new();
+        context.push_scope();
+        context.insert("price".to_string(), json!(120));
+        context.insert("quantity".to_string(), json!(3));
+
+        assert_eq!(e.fake_apply(&mut context).unwrap(), json!(360));
+    }
+
+    #[test]
+    fn test_expr_new() {
+        let e = Expr::new("expr".to_string(), "price * quantity").unwrap();
+
+        assert_eq!(e._fake_type, "expr");
+        assert_eq!(e.references(), vec!["price".to_string(), "quantity".to_string()]);
+        assert!(e.infer(&|_| Some(ValueType::String)).is_err(), "Should return an error for arithmetic on strings");
+        assert!(Expr::new("expr".to_string(), "price *").is_err(), "Should return an error for a syntax error");
+    }
+}
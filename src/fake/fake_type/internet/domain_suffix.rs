@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::internet;
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `DomainSuffix` is an implementation of `FakeType`. It generates a domain_suffix that varies according to language.
@@ -10,14 +13,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `DomainSuffix` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `DomainSuffix` with the `new` method.
+/// * `FakeElement`: This provides `DomainSuffix` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::internet::domain_suffix::DomainSuffix;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of DomainSuffix, specifying "Japanese" as the language
-/// let w = DomainSuffix::new("domain_suffix", "Japanese");
-/// let domain_suffix = w.fake_apply();
+/// let w = DomainSuffix::new("domain_suffix".to_string(), LangParams::new("Japanese")).unwrap();
+/// let domain_suffix = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake domain_suffix: {}", domain_suffix);
 /// ```
 #[derive(Debug)]
@@ -28,27 +35,29 @@ pub struct DomainSuffix {
 
 impl FakeType for DomainSuffix {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => internet::raw::DomainSuffix(l).fake(),
-            Language::En(l) => internet::raw::DomainSuffix(l).fake(),
-            Language::ArSa(l) => internet::raw::DomainSuffix(l).fake(),
-            Language::FrFr(l) => internet::raw::DomainSuffix(l).fake(),
-            Language::PtBr(l) => internet::raw::DomainSuffix(l).fake(),
-            Language::ZhCn(l) => internet::raw::DomainSuffix(l).fake(),
-            Language::ZhTw(l) => internet::raw::DomainSuffix(l).fake(),
+            Language::JaJp(l) => internet::raw::DomainSuffix(l).fake_with_rng(context.rng()),
+            Language::En(l) => internet::raw::DomainSuffix(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => internet::raw::DomainSuffix(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => internet::raw::DomainSuffix(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => internet::raw::DomainSuffix(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => internet::raw::DomainSuffix(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => internet::raw::DomainSuffix(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for DomainSuffix {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -61,18 +70,20 @@ impl From<DomainSuffix> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::DomainSuffix;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeElement, FakeType};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_domain_suffix_fake_apply() {
-        let w = DomainSuffix::new("domain_suffix".to_string(), "English".to_string());
-        let domain_suffix = w.fake_apply();
+        let w = DomainSuffix::new("domain_suffix".to_string(), LangParams::new("English")).unwrap();
+        let domain_suffix = w.fake_apply(&mut GenerationContext::new());
         assert!(!domain_suffix.is_empty(), "Generated domain_suffix should not be empty");
     }
 
     #[test]
     fn test_domain_suffix_new() {
-        let w = DomainSuffix::new("domain_suffix".to_string(), "English".to_string());
+        let w = DomainSuffix::new("domain_suffix".to_string(), LangParams::new("English")).unwrap();
         assert_eq!(w._fake_type, "domain_suffix");
         assert_eq!(w.lang, "English");
     }
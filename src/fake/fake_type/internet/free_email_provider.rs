@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::internet;
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `FreeEmailProvider` is an implementation of `FakeType`. It generates a free_email_provider that varies according to language.
@@ -10,14 +13,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `FreeEmailProvider` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `FreeEmailProvider` with the `new` method.
+/// * `FakeElement`: This provides `FreeEmailProvider` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::internet::free_email_provider::FreeEmailProvider;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of FreeEmailProvider, specifying "Japanese" as the language
-/// let w = FreeEmailProvider::new("free_email_provider", "Japanese");
-/// let free_email_provider = w.fake_apply();
+/// let w = FreeEmailProvider::new("free_email_provider".to_string(), LangParams::new("Japanese")).unwrap();
+/// let free_email_provider = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake free_email_provider: {}", free_email_provider);
 /// ```
 #[derive(Debug)]
@@ -28,27 +35,29 @@ pub struct FreeEmailProvider {
 
 impl FakeType for FreeEmailProvider {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => internet::raw::FreeEmailProvider(l).fake(),
-            Language::En(l) => internet::raw::FreeEmailProvider(l).fake(),
-            Language::ArSa(l) => internet::raw::FreeEmailProvider(l).fake(),
-            Language::FrFr(l) => internet::raw::FreeEmailProvider(l).fake(),
-            Language::PtBr(l) => internet::raw::FreeEmailProvider(l).fake(),
-            Language::ZhCn(l) => internet::raw::FreeEmailProvider(l).fake(),
-            Language::ZhTw(l) => internet::raw::FreeEmailProvider(l).fake(),
+            Language::JaJp(l) => internet::raw::FreeEmailProvider(l).fake_with_rng(context.rng()),
+            Language::En(l) => internet::raw::FreeEmailProvider(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => internet::raw::FreeEmailProvider(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => internet::raw::FreeEmailProvider(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => internet::raw::FreeEmailProvider(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => internet::raw::FreeEmailProvider(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => internet::raw::FreeEmailProvider(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for FreeEmailProvider {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -61,18 +70,20 @@ impl From<FreeEmailProvider> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::FreeEmailProvider;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeElement, FakeType};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_free_email_provider_fake_apply() {
-        let w = FreeEmailProvider::new("free_email_provider".to_string(), "English".to_string());
-        let free_email_provider = w.fake_apply();
+        let w = FreeEmailProvider::new("free_email_provider".to_string(), LangParams::new("English")).unwrap();
+        let free_email_provider = w.fake_apply(&mut GenerationContext::new());
         assert!(!free_email_provider.is_empty(), "Generated free_email_provider should not be empty");
     }
 
     #[test]
     fn test_free_email_provider_new() {
-        let w = FreeEmailProvider::new("free_email_provider".to_string(), "English".to_string());
+        let w = FreeEmailProvider::new("free_email_provider".to_string(), LangParams::new("English")).unwrap();
         assert_eq!(w._fake_type, "free_email_provider");
         assert_eq!(w.lang, "English");
     }
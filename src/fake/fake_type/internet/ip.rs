@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::internet;
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `IP` is an implementation of `FakeType`. It generates a ip that varies according to language.
@@ -10,14 +13,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `IP` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `IP` with the `new` method.
+/// * `FakeElement`: This provides `IP` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::internet::ip::IP;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of IP, specifying "Japanese" as the language
-/// let w = IP::new("ip", "Japanese");
-/// let ip = w.fake_apply();
+/// let w = IP::new("ip".to_string(), LangParams::new("Japanese")).unwrap();
+/// let ip = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake ip: {}", ip);
 /// ```
 #[derive(Debug)]
@@ -28,27 +35,29 @@ pub struct IP {
 
 impl FakeType for IP {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => internet::raw::IP(l).fake(),
-            Language::En(l) => internet::raw::IP(l).fake(),
-            Language::ArSa(l) => internet::raw::IP(l).fake(),
-            Language::FrFr(l) => internet::raw::IP(l).fake(),
-            Language::PtBr(l) => internet::raw::IP(l).fake(),
-            Language::ZhCn(l) => internet::raw::IP(l).fake(),
-            Language::ZhTw(l) => internet::raw::IP(l).fake(),
+            Language::JaJp(l) => internet::raw::IP(l).fake_with_rng(context.rng()),
+            Language::En(l) => internet::raw::IP(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => internet::raw::IP(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => internet::raw::IP(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => internet::raw::IP(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => internet::raw::IP(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => internet::raw::IP(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for IP {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -61,18 +70,20 @@ impl From<IP> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::IP;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeElement, FakeType};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_ip_fake_apply() {
-        let w = IP::new("ip".to_string(), "English".to_string());
-        let ip = w.fake_apply();
+        let w = IP::new("ip".to_string(), LangParams::new("English")).unwrap();
+        let ip = w.fake_apply(&mut GenerationContext::new());
         assert!(!ip.is_empty(), "Generated ip should not be empty");
     }
 
     #[test]
     fn test_ip_new() {
-        let w = IP::new("ip".to_string(), "English".to_string());
+        let w = IP::new("ip".to_string(), LangParams::new("English")).unwrap();
         assert_eq!(w._fake_type, "ip");
         assert_eq!(w.lang, "English");
     }
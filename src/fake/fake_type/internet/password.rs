@@ -1,10 +1,12 @@
 use fake::Fake;
 use fake::faker::internet;
 use serde_json::Value;
-use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+use crate::fake::fake_type::{FakeType, FakeElement};
+use crate::fake::fake_type::params::RangeParams;
 use crate::fake::lang::{get_language, Language};
 use anyhow::{Result, anyhow};
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 
 /// `Password` is an implementation of `FakeType`. It generates a password with a number of words within a certain range
 /// that varies according to language.
@@ -12,14 +14,18 @@ use crate::fake::fake_definition_element::FakeDefinitionElement;
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Password` with the `fake_apply` and `to_value` methods.
-/// * `FakeWithRangeElement`: This provides `Password` with the `new` method.
+/// * `FakeElement`: This provides `Password` with the `new` method, taking `RangeParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::internet::password::Password;
+/// # use fake_cli::fake::fake_type::params::RangeParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Password, specifying "Japanese" as the language
-/// let s = Password::new("password", "Japanese", 1, 7).unwrap();
-/// let password = s.fake_apply();
+/// let s = Password::new("password".to_string(), RangeParams::new("Japanese", 1, 7)).unwrap();
+/// let password = s.fake_apply(&mut GenerationContext::new());
 /// println!("Fake password: {}", password);
 /// ```
 #[derive(Debug)]
@@ -32,26 +38,29 @@ pub struct Password {
 
 impl FakeType for Password {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => internet::raw::Password(l, self.min..self.max).fake(),
-            Language::En(l) => internet::raw::Password(l, self.min..self.max).fake(),
-            Language::ArSa(l) => internet::raw::Password(l, self.min..self.max).fake(),
-            Language::FrFr(l) => internet::raw::Password(l, self.min..self.max).fake(),
-            Language::PtBr(l) => internet::raw::Password(l, self.min..self.max).fake(),
-            Language::ZhCn(l) => internet::raw::Password(l, self.min..self.max).fake(),
-            Language::ZhTw(l) => internet::raw::Password(l, self.min..self.max).fake(),
+            Language::JaJp(l) => internet::raw::Password(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::En(l) => internet::raw::Password(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ArSa(l) => internet::raw::Password(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::FrFr(l) => internet::raw::Password(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::PtBr(l) => internet::raw::Password(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => internet::raw::Password(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => internet::raw::Password(l, self.min..self.max).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
-impl FakeWithRangeElement for Password {
-    fn new(_fake_type: String, lang: String, min: usize, max: usize) -> Result<Self> {
+impl FakeElement for Password {
+    type Params = RangeParams;
+
+    fn new(_fake_type: String, params: RangeParams) -> Result<Self> {
+        let RangeParams { lang, min, max } = params;
         match (min, max) {
             (min, max) if min >= max => {
                 Err(anyhow!("fake_type: password, please setting 0 <= min, 0 <= max, min < max"))
@@ -72,12 +81,14 @@ impl From<Password> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Password;
-    use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::fake_type::{FakeType, FakeElement};
+    use crate::fake::fake_type::params::RangeParams;
 
     #[test]
     fn test_password_fake_apply() {
-        let s = Password::new("password".to_string(), "English".to_string(), 1, 7).unwrap();
-        let password = s.fake_apply();
+        let s = Password::new("password".to_string(), RangeParams::new("English", 1, 7)).unwrap();
+        let password = s.fake_apply(&mut GenerationContext::new());
 
         assert!(password.split_whitespace().count() >= 1 && password.split_whitespace().count() <= 7, "The number of words in the generated password should be within the range");
         assert!(!password.is_empty(), "Generated password should not be empty");
@@ -85,14 +96,14 @@ mod tests {
 
     #[test]
     fn test_password_new() {
-        let s = Password::new("password".to_string(), "English".to_string(), 1, 7);
+        let s = Password::new("password".to_string(), RangeParams::new("English", 1, 7));
 
         assert!(s.is_ok());
     }
 
     #[test]
     fn test_password_new_fail() {
-        let s = Password::new("password".to_string(), "English".to_string(), 7, 1);
+        let s = Password::new("password".to_string(), RangeParams::new("English", 7, 1));
 
         assert!(s.is_err());
     }
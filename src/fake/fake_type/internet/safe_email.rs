@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::internet;
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `SafeEmail` is an implementation of `FakeType`. It generates a safe_email that varies according to language.
@@ -10,14 +13,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `SafeEmail` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `SafeEmail` with the `new` method.
+/// * `FakeElement`: This provides `SafeEmail` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::internet::safe_email::SafeEmail;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of SafeEmail, specifying "Japanese" as the language
-/// let w = SafeEmail::new("safe_email", "Japanese");
-/// let safe_email = w.fake_apply();
+/// let w = SafeEmail::new("safe_email".to_string(), LangParams::new("Japanese")).unwrap();
+/// let safe_email = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake safe_email: {}", safe_email);
 /// ```
 #[derive(Debug)]
@@ -28,27 +35,29 @@ pub struct SafeEmail {
 
 impl FakeType for SafeEmail {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => internet::raw::SafeEmail(l).fake(),
-            Language::En(l) => internet::raw::SafeEmail(l).fake(),
-            Language::ArSa(l) => internet::raw::SafeEmail(l).fake(),
-            Language::FrFr(l) => internet::raw::SafeEmail(l).fake(),
-            Language::PtBr(l) => internet::raw::SafeEmail(l).fake(),
-            Language::ZhCn(l) => internet::raw::SafeEmail(l).fake(),
-            Language::ZhTw(l) => internet::raw::SafeEmail(l).fake(),
+            Language::JaJp(l) => internet::raw::SafeEmail(l).fake_with_rng(context.rng()),
+            Language::En(l) => internet::raw::SafeEmail(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => internet::raw::SafeEmail(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => internet::raw::SafeEmail(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => internet::raw::SafeEmail(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => internet::raw::SafeEmail(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => internet::raw::SafeEmail(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for SafeEmail {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -61,18 +70,20 @@ impl From<SafeEmail> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::SafeEmail;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeElement, FakeType};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_safe_email_fake_apply() {
-        let w = SafeEmail::new("safe_email".to_string(), "English".to_string());
-        let safe_email = w.fake_apply();
+        let w = SafeEmail::new("safe_email".to_string(), LangParams::new("English")).unwrap();
+        let safe_email = w.fake_apply(&mut GenerationContext::new());
         assert!(!safe_email.is_empty(), "Generated safe_email should not be empty");
     }
 
     #[test]
     fn test_safe_email_new() {
-        let w = SafeEmail::new("safe_email".to_string(), "English".to_string());
+        let w = SafeEmail::new("safe_email".to_string(), LangParams::new("English")).unwrap();
         assert_eq!(w._fake_type, "safe_email");
         assert_eq!(w.lang, "English");
     }
@@ -1,10 +1,12 @@
 use fake::Fake;
 use fake::faker::lorem;
 use serde_json::Value;
-use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+use crate::fake::fake_type::{FakeType, FakeElement};
+use crate::fake::fake_type::params::RangeParams;
 use crate::fake::lang::{get_language, Language};
 use anyhow::{Result, anyhow};
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 
 /// `Paragraphs` is an implementation of `FakeType`. It generates a paragraphs with a number of words within a certain range
 /// that varies according to language.
@@ -12,15 +14,19 @@ use crate::fake::fake_definition_element::FakeDefinitionElement;
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Paragraphs` with the `fake_apply` and `to_value` methods.
-/// * `FakeWithRangeElement`: This provides `Paragraphs` with the `new` method.
+/// * `FakeElement`: This provides `Paragraphs` with the `new` method, taking `RangeParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::lorem::paragraphs::Paragraphs;
+/// # use fake_cli::fake::fake_type::params::RangeParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Paragraphs, specifying "Japanese" as the language
-/// let s = Paragraphs::new("paragraphs", "Japanese", 1, 7).unwrap();
-/// let paragraphs = s.fake_apply();
-/// println!("Fake paragraphs: {}", paragraphs);
+/// let s = Paragraphs::new("paragraphs".to_string(), RangeParams::new("Japanese", 1, 7)).unwrap();
+/// let paragraphs = s.fake_apply(&mut GenerationContext::new());
+/// println!("Fake paragraphs: {:?}", paragraphs);
 /// ```
 #[derive(Debug)]
 pub struct Paragraphs {
@@ -32,29 +38,32 @@ pub struct Paragraphs {
 
 impl FakeType for Paragraphs {
     type Response = Vec<String>;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake(),
-            Language::En(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake(),
-            Language::ArSa(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake(),
-            Language::FrFr(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake(),
-            Language::PtBr(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake(),
-            Language::ZhCn(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake(),
-            Language::ZhTw(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake(),
+            Language::JaJp(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::En(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ArSa(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::FrFr(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::PtBr(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => lorem::raw::Paragraphs(l, self.min..self.max).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        let array = self.fake_apply();
-        Value::Array(array.iter().map(
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        let array = self.fake_apply(context);
+        Ok(Value::Array(array.iter().map(
             |word| Value::String(word.to_string())
-        ).collect())
+        ).collect()))
     }
 }
 
-impl FakeWithRangeElement for Paragraphs {
-    fn new(_fake_type: String, lang: String, min: usize, max: usize) -> Result<Self> {
+impl FakeElement for Paragraphs {
+    type Params = RangeParams;
+
+    fn new(_fake_type: String, params: RangeParams) -> Result<Self> {
+        let RangeParams { lang, min, max } = params;
         match (min, max) {
             (min, max) if min >= max => {
                 Err(anyhow!("fake_type: paragraphs, please setting 0 <= min, 0 <= max, min < max"))
@@ -75,26 +84,28 @@ impl From<Paragraphs> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Paragraphs;
-    use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::fake_type::{FakeType, FakeElement};
+    use crate::fake::fake_type::params::RangeParams;
 
     #[test]
     fn test_paragraphs_fake_apply() {
-        let s = Paragraphs::new("paragraphs".to_string(), "English".to_string(), 1, 7).unwrap();
-        let paragraphs = s.fake_apply();
+        let s = Paragraphs::new("paragraphs".to_string(), RangeParams::new("English", 1, 7)).unwrap();
+        let paragraphs = s.fake_apply(&mut GenerationContext::new());
 
-        assert!(paragraphs.len() >= 1 && paragraphs.len() <= 7, "The number of paragraph in the generated paragraphs should be within the range");
+        assert!((1..=7).contains(&paragraphs.len()), "The number of paragraph in the generated paragraphs should be within the range");
     }
 
     #[test]
     fn test_paragraphs_new() {
-        let s = Paragraphs::new("paragraphs".to_string(), "English".to_string(), 1, 7);
+        let s = Paragraphs::new("paragraphs".to_string(), RangeParams::new("English", 1, 7));
 
         assert!(s.is_ok());
     }
 
     #[test]
     fn test_paragraphs_new_fail() {
-        let s = Paragraphs::new("paragraphs".to_string(), "English".to_string(), 7, 1);
+        let s = Paragraphs::new("paragraphs".to_string(), RangeParams::new("English", 7, 1));
 
         assert!(s.is_err());
     }
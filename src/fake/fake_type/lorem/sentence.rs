@@ -1,10 +1,12 @@
 use fake::Fake;
 use fake::faker::lorem;
 use serde_json::Value;
-use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+use crate::fake::fake_type::{FakeType, FakeElement};
+use crate::fake::fake_type::params::RangeParams;
 use crate::fake::lang::{get_language, Language};
 use anyhow::{Result, anyhow};
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 
 /// `Sentence` is an implementation of `FakeType`. It generates a sentence with a number of words within a certain range
 /// that varies according to language.
@@ -12,14 +14,18 @@ use crate::fake::fake_definition_element::FakeDefinitionElement;
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Sentence` with the `fake_apply` and `to_value` methods.
-/// * `FakeWithRangeElement`: This provides `Sentence` with the `new` method.
+/// * `FakeElement`: This provides `Sentence` with the `new` method, taking `RangeParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::lorem::sentence::Sentence;
+/// # use fake_cli::fake::fake_type::params::RangeParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Sentence, specifying "Japanese" as the language
-/// let s = Sentence::new("sentence", "Japanese", 1, 7).unwrap();
-/// let sentence = s.fake_apply();
+/// let s = Sentence::new("sentence".to_string(), RangeParams::new("Japanese", 1, 7)).unwrap();
+/// let sentence = s.fake_apply(&mut GenerationContext::new());
 /// println!("Fake sentence: {}", sentence);
 /// ```
 #[derive(Debug)]
@@ -32,26 +38,29 @@ pub struct Sentence {
 
 impl FakeType for Sentence {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => lorem::raw::Sentence(l, self.min..self.max).fake(),
-            Language::En(l) => lorem::raw::Sentence(l, self.min..self.max).fake(),
-            Language::ArSa(l) => lorem::raw::Sentence(l, self.min..self.max).fake(),
-            Language::FrFr(l) => lorem::raw::Sentence(l, self.min..self.max).fake(),
-            Language::PtBr(l) => lorem::raw::Sentence(l, self.min..self.max).fake(),
-            Language::ZhCn(l) => lorem::raw::Sentence(l, self.min..self.max).fake(),
-            Language::ZhTw(l) => lorem::raw::Sentence(l, self.min..self.max).fake(),
+            Language::JaJp(l) => lorem::raw::Sentence(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::En(l) => lorem::raw::Sentence(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ArSa(l) => lorem::raw::Sentence(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::FrFr(l) => lorem::raw::Sentence(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::PtBr(l) => lorem::raw::Sentence(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => lorem::raw::Sentence(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => lorem::raw::Sentence(l, self.min..self.max).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
-impl FakeWithRangeElement for Sentence {
-    fn new(_fake_type: String, lang: String, min: usize, max: usize) -> Result<Self> {
+impl FakeElement for Sentence {
+    type Params = RangeParams;
+
+    fn new(_fake_type: String, params: RangeParams) -> Result<Self> {
+        let RangeParams { lang, min, max } = params;
         match (min, max) {
             (min, max) if min >= max => {
                 Err(anyhow!("fake_type: sentence, please setting 0 <= min, 0 <= max, min < max"))
@@ -72,12 +81,14 @@ impl From<Sentence> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Sentence;
-    use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::fake_type::{FakeType, FakeElement};
+    use crate::fake::fake_type::params::RangeParams;
 
     #[test]
     fn test_sentence_fake_apply() {
-        let s = Sentence::new("sentence".to_string(), "English".to_string(), 1, 7).unwrap();
-        let sentence = s.fake_apply();
+        let s = Sentence::new("sentence".to_string(), RangeParams::new("English", 1, 7)).unwrap();
+        let sentence = s.fake_apply(&mut GenerationContext::new());
 
         assert!(sentence.split_whitespace().count() >= 1 && sentence.split_whitespace().count() <= 7, "The number of words in the generated sentence should be within the range");
         assert!(!sentence.is_empty(), "Generated sentence should not be empty");
@@ -85,14 +96,14 @@ mod tests {
 
     #[test]
     fn test_sentence_new() {
-        let s = Sentence::new("sentence".to_string(), "English".to_string(), 1, 7);
+        let s = Sentence::new("sentence".to_string(), RangeParams::new("English", 1, 7));
 
         assert!(s.is_ok());
     }
 
     #[test]
     fn test_sentence_new_fail() {
-        let s = Sentence::new("sentence".to_string(), "English".to_string(), 7, 1);
+        let s = Sentence::new("sentence".to_string(), RangeParams::new("English", 7, 1));
 
         assert!(s.is_err());
     }
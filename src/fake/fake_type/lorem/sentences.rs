@@ -1,10 +1,12 @@
 use fake::Fake;
 use fake::faker::lorem;
 use serde_json::Value;
-use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+use crate::fake::fake_type::{FakeType, FakeElement};
+use crate::fake::fake_type::params::RangeParams;
 use crate::fake::lang::{get_language, Language};
 use anyhow::{Result, anyhow};
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 
 /// `Sentences` is an implementation of `FakeType`. It generates a sentences with a number of words within a certain range
 /// that varies according to language.
@@ -12,15 +14,19 @@ use crate::fake::fake_definition_element::FakeDefinitionElement;
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Sentences` with the `fake_apply` and `to_value` methods.
-/// * `FakeWithRangeElement`: This provides `Sentences` with the `new` method.
+/// * `FakeElement`: This provides `Sentences` with the `new` method, taking `RangeParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::lorem::sentences::Sentences;
+/// # use fake_cli::fake::fake_type::params::RangeParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Sentences, specifying "Japanese" as the language
-/// let s = Sentences::new("sentences", "Japanese", 1, 7).unwrap();
-/// let sentences = s.fake_apply();
-/// println!("Fake sentences: {}", sentences);
+/// let s = Sentences::new("sentences".to_string(), RangeParams::new("Japanese", 1, 7)).unwrap();
+/// let sentences = s.fake_apply(&mut GenerationContext::new());
+/// println!("Fake sentences: {:?}", sentences);
 /// ```
 #[derive(Debug)]
 pub struct Sentences {
@@ -32,29 +38,32 @@ pub struct Sentences {
 
 impl FakeType for Sentences {
     type Response = Vec<String>;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => lorem::raw::Sentences(l, self.min..self.max).fake(),
-            Language::En(l) => lorem::raw::Sentences(l, self.min..self.max).fake(),
-            Language::ArSa(l) => lorem::raw::Sentences(l, self.min..self.max).fake(),
-            Language::FrFr(l) => lorem::raw::Sentences(l, self.min..self.max).fake(),
-            Language::PtBr(l) => lorem::raw::Sentences(l, self.min..self.max).fake(),
-            Language::ZhCn(l) => lorem::raw::Sentences(l, self.min..self.max).fake(),
-            Language::ZhTw(l) => lorem::raw::Sentences(l, self.min..self.max).fake(),
+            Language::JaJp(l) => lorem::raw::Sentences(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::En(l) => lorem::raw::Sentences(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ArSa(l) => lorem::raw::Sentences(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::FrFr(l) => lorem::raw::Sentences(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::PtBr(l) => lorem::raw::Sentences(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => lorem::raw::Sentences(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => lorem::raw::Sentences(l, self.min..self.max).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        let array = self.fake_apply();
-        Value::Array(array.iter().map(
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        let array = self.fake_apply(context);
+        Ok(Value::Array(array.iter().map(
             |word| Value::String(word.to_string())
-        ).collect())
+        ).collect()))
     }
 }
 
-impl FakeWithRangeElement for Sentences {
-    fn new(_fake_type: String, lang: String, min: usize, max: usize) -> Result<Self> {
+impl FakeElement for Sentences {
+    type Params = RangeParams;
+
+    fn new(_fake_type: String, params: RangeParams) -> Result<Self> {
+        let RangeParams { lang, min, max } = params;
         match (min, max) {
             (min, max) if min >= max => {
                 Err(anyhow!("fake_type: sentences, please setting 0 <= min, 0 <= max, min < max"))
@@ -78,21 +87,21 @@ mod tests {
 
     #[test]
     fn test_fake_apply() {
-        let sentences = Sentences::new("sentences".to_string(), "English".to_string(), 1, 7).unwrap();
-        let fake_sentences = sentences.fake_apply();
+        let sentences = Sentences::new("sentences".to_string(), RangeParams::new("English", 1, 7)).unwrap();
+        let fake_sentences = sentences.fake_apply(&mut GenerationContext::new());
 
-        assert!(fake_sentences.len() >= 1 && fake_sentences.len() <= 7, "The number of sentence in the generated sentence should be within the range");
+        assert!((1..=7).contains(&fake_sentences.len()), "The number of sentence in the generated sentence should be within the range");
     }
 
     #[test]
     fn test_new() {
-        let sentences = Sentences::new("sentences".to_string(), "English".to_string(), 1, 7);
+        let sentences = Sentences::new("sentences".to_string(), RangeParams::new("English", 1, 7));
         assert!(sentences.is_ok(), "Should return Ok for a defined fake type");
     }
 
     #[test]
     fn test_new_error() {
-        let sentences = Sentences::new("sentences".to_string(), "English".to_string(), 10, 1);
+        let sentences = Sentences::new("sentences".to_string(), RangeParams::new("English", 10, 1));
         assert!(sentences.is_err(), "Should return Ok for a defined fake type");
     }
 }
\ No newline at end of file
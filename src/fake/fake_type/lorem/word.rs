@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::lorem;
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `Word` is an implementation of `FakeType`. It generates a word that varies according to language.
@@ -10,14 +13,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Word` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `Word` with the `new` method.
+/// * `FakeElement`: This provides `Word` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::lorem::word::Word;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Word, specifying "Japanese" as the language
-/// let w = Word::new("word", "Japanese");
-/// let word = w.fake_apply();
+/// let w = Word::new("word".to_string(), LangParams::new("Japanese")).unwrap();
+/// let word = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake word: {}", word);
 /// ```
 #[derive(Debug)]
@@ -28,27 +35,29 @@ pub struct Word {
 
 impl FakeType for Word {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => lorem::raw::Word(l).fake(),
-            Language::En(l) => lorem::raw::Word(l).fake(),
-            Language::ArSa(l) => lorem::raw::Word(l).fake(),
-            Language::FrFr(l) => lorem::raw::Word(l).fake(),
-            Language::PtBr(l) => lorem::raw::Word(l).fake(),
-            Language::ZhCn(l) => lorem::raw::Word(l).fake(),
-            Language::ZhTw(l) => lorem::raw::Word(l).fake(),
+            Language::JaJp(l) => lorem::raw::Word(l).fake_with_rng(context.rng()),
+            Language::En(l) => lorem::raw::Word(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => lorem::raw::Word(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => lorem::raw::Word(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => lorem::raw::Word(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => lorem::raw::Word(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => lorem::raw::Word(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for Word {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -61,18 +70,20 @@ impl From<Word> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Word;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeElement, FakeType};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_word_fake_apply() {
-        let w = Word::new("word".to_string(), "English".to_string());
-        let word = w.fake_apply();
+        let w = Word::new("word".to_string(), LangParams::new("English")).unwrap();
+        let word = w.fake_apply(&mut GenerationContext::new());
         assert!(!word.is_empty(), "Generated word should not be empty");
     }
 
     #[test]
     fn test_word_new() {
-        let w = Word::new("word".to_string(), "English".to_string());
+        let w = Word::new("word".to_string(), LangParams::new("English")).unwrap();
         assert_eq!(w._fake_type, "word");
         assert_eq!(w.lang, "English");
     }
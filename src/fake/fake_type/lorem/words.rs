@@ -1,10 +1,12 @@
 use fake::Fake;
 use fake::faker::lorem;
 use serde_json::Value;
-use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+use crate::fake::fake_type::{FakeType, FakeElement};
+use crate::fake::fake_type::params::RangeParams;
 use crate::fake::lang::{get_language, Language};
 use anyhow::{Result, anyhow};
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 
 /// `Words` is an implementation of `FakeType`. It generates a vector of words within a certain range
 /// that varies according to language.
@@ -12,14 +14,18 @@ use crate::fake::fake_definition_element::FakeDefinitionElement;
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Words` with the `fake_apply` and `to_value` methods.
-/// * `FakeWithRangeElement`: This provides `Words` with the `new` method.
+/// * `FakeElement`: This provides `Words` with the `new` method, taking `RangeParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::lorem::words::Words;
+/// # use fake_cli::fake::fake_type::params::RangeParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Words, specifying "Japanese" as the language
-/// let w = Words::new("words", "Japanese", 1, 5).unwrap();
-/// let words = w.fake_apply();
+/// let w = Words::new("words".to_string(), RangeParams::new("Japanese", 1, 5)).unwrap();
+/// let words = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake words: {:?}", words);
 /// ```
 #[derive(Debug)]
@@ -33,29 +39,32 @@ pub struct Words {
 impl FakeType for Words {
     type Response = Vec<String>;
 
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => lorem::raw::Words(l, self.min..self.max).fake(),
-            Language::En(l) => lorem::raw::Words(l, self.min..self.max).fake(),
-            Language::ArSa(l) => lorem::raw::Words(l, self.min..self.max).fake(),
-            Language::FrFr(l) => lorem::raw::Words(l, self.min..self.max).fake(),
-            Language::PtBr(l) => lorem::raw::Words(l, self.min..self.max).fake(),
-            Language::ZhCn(l) => lorem::raw::Words(l, self.min..self.max).fake(),
-            Language::ZhTw(l) => lorem::raw::Words(l, self.min..self.max).fake(),
+            Language::JaJp(l) => lorem::raw::Words(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::En(l) => lorem::raw::Words(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ArSa(l) => lorem::raw::Words(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::FrFr(l) => lorem::raw::Words(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::PtBr(l) => lorem::raw::Words(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => lorem::raw::Words(l, self.min..self.max).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => lorem::raw::Words(l, self.min..self.max).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        let array = self.fake_apply();
-        Value::Array(array.iter().map(
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        let array = self.fake_apply(context);
+        Ok(Value::Array(array.iter().map(
             |word| Value::String(word.to_string())
-        ).collect())
+        ).collect()))
     }
 }
 
-impl FakeWithRangeElement for Words {
-    fn new(_fake_type: String, lang: String, min: usize, max: usize) -> Result<Self> {
+impl FakeElement for Words {
+    type Params = RangeParams;
+
+    fn new(_fake_type: String, params: RangeParams) -> Result<Self> {
+        let RangeParams { lang, min, max } = params;
         match (min, max) {
             (min, max) if min >= max => {
                 Err(anyhow!("fake_type: words, please setting 0 <= min, 0 <= max, min < max"))
@@ -76,27 +85,29 @@ impl From<Words> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Words;
-    use crate::fake::fake_type::{FakeType, FakeWithRangeElement};
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::fake_type::{FakeType, FakeElement};
+    use crate::fake::fake_type::params::RangeParams;
 
     #[test]
     fn test_words_fake_apply() {
-        let w = Words::new("words".to_string(), "English".to_string(), 1, 5).unwrap();
-        let words = w.fake_apply();
+        let w = Words::new("words".to_string(), RangeParams::new("English", 1, 5)).unwrap();
+        let words = w.fake_apply(&mut GenerationContext::new());
 
-        assert!(words.len() >= 1 && words.len() <= 5, "The number of generated words should be within the range");
+        assert!((1..=5).contains(&words.len()), "The number of generated words should be within the range");
         assert!(!words.iter().any(|word| word.is_empty()), "No generated word should be empty");
     }
 
     #[test]
     fn test_words_new() {
-        let w = Words::new("words".to_string(), "English".to_string(), 1, 5);
+        let w = Words::new("words".to_string(), RangeParams::new("English", 1, 5));
 
         assert!(w.is_ok());
     }
 
     #[test]
     fn test_words_new_fail() {
-        let w = Words::new("words".to_string(), "English".to_string(), 5, 1);
+        let w = Words::new("words".to_string(), RangeParams::new("English", 5, 1));
 
         assert!(w.is_err());
     }
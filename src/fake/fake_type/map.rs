@@ -1,9 +1,15 @@
-use std::collections::{BTreeMap};
+use indexmap::IndexMap;
+use anyhow::Result;
 use serde_json::{Value};
-use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
 use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::GenerationContext;
+use crate::fake::record::{Record, RecordOptions};
 
 /// The `Map` structure is an implementation of `FakeType`. It produces a map of elements of `FakeType`.
+/// The generated keys keep the order the elements are defined in, while elements referring to sibling
+/// fields are generated after the fields they refer to. With `unique_by`, the listed combinations of fields are
+/// unique across every map generated during a run, and with `constraints`, the boolean expressions hold for every map.
 ///
 /// # Attributes
 ///
@@ -12,40 +18,64 @@ use crate::fake::fake_type::FakeType;
 /// # Example
 ///
 /// ```
-/// use std::collections::BTreeMap;
+/// # use fake_cli::fake::fake_type::map::Map;
+/// # use fake_cli::fake::record::RecordOptions;
+/// # use fake_cli::{FakeType, GenerationContext};
+/// use indexmap::IndexMap;
 /// // Create a new instance of Map, specifying "map" as the type
-/// let m = Map::new("map".to_string(), BTreeMap::new());
-/// let map = m.fake_apply();
+/// let m = Map::new("map".to_string(), IndexMap::new(), RecordOptions::default()).unwrap();
+/// let map = m.fake_apply(&mut GenerationContext::new()).unwrap();
 /// println!("Fake map: {:?}", map);
 /// ```
 #[derive(Debug)]
 pub struct Map {
     _fake_type: String,
-    map: BTreeMap<String, FakeDefinitionElement>,
+    record: Record,
 }
 
 impl FakeType for Map {
-    type Response = serde_json::Map<String, Value>;
+    type Response = Result<serde_json::Map<String, Value>>;
 
-    fn fake_apply(&self) -> Self::Response {
-        let m = serde_json::Map::new();
-
-        let result = self.map.iter().fold(m, |mut acc, (key, obj)| {
-            acc.insert(key.clone(), obj.to_value());
-            acc
-        });
-
-        result
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        self.record.generate(context)
     }
 
-    fn to_value(&self) -> Value {
-        Value::Object(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::Object(self.fake_apply(context)?))
     }
 }
 
 impl Map {
-    pub fn new(_fake_type: String, map: BTreeMap<String, FakeDefinitionElement>) -> Self {
-        Self { _fake_type, map }
+    /// Creates a new `Map`. Returns an error when the elements refer to each other circularly,
+    /// or when the record options are invalid.
+    pub fn new(_fake_type: String, map: IndexMap<String, FakeDefinitionElement>, options: RecordOptions) -> Result<Self> {
+        let record = Record::new(map, options)?;
+        Ok(Self { _fake_type, record })
+    }
+
+    /// Returns the names referred to by the elements which have to be resolved by an enclosing map.
+    pub fn references(&self) -> Vec<String> {
+        self.record.references()
+    }
+
+    /// Returns the names of the dataset entities the elements sample values from.
+    pub fn entity_references(&self) -> Vec<String> {
+        self.record.entity_references()
+    }
+
+    /// Appends the state of the stateful fields to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        self.record.save_state(states)
+    }
+
+    /// Restores the state of the stateful fields from `states`.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        self.record.restore_state(states)
+    }
+
+    /// Returns the JSON Schema of the generated objects.
+    pub fn output_schema(&self) -> Value {
+        self.record.output_schema()
     }
 }
 
@@ -58,22 +88,23 @@ impl From<Map> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Map;
+    use crate::fake::record::RecordOptions;
     use crate::fake::fake_type::FakeType;
-    use std::collections::BTreeMap;
+    use crate::fake::generation_context::GenerationContext;
+    use indexmap::IndexMap;
 
     #[test]
     fn test_map_fake_apply() {
-        let m = Map::new("map".to_string(), BTreeMap::new());
-        let map = m.fake_apply();
+        let m = Map::new("map".to_string(), IndexMap::new(), RecordOptions::default()).unwrap();
+        let map = m.fake_apply(&mut GenerationContext::new()).unwrap();
 
         assert!(map.is_empty(), "Generated map should be empty when no FakeDefinitionElements are provided");
     }
 
     #[test]
     fn test_map_new() {
-        let m = Map::new("map".to_string(), BTreeMap::new());
+        let m = Map::new("map".to_string(), IndexMap::new(), RecordOptions::default()).unwrap();
 
         assert_eq!(m._fake_type, "map");
-        assert_eq!(m.map.len(), 0);
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod name;
 pub mod last_name;
 pub mod first_name;
@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::{name};
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `LastName` is an implementation of `FakeType`. It generates a last name 
@@ -11,14 +14,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `LastName` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `LastName` with the `new` method.
+/// * `FakeElement`: This provides `LastName` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::name::last_name::LastName;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of LastName, specifying "Japanese" as the language
-/// let ln = LastName::new("lastname", "Japanese");
-/// let last_name = ln.fake_apply();
+/// let ln = LastName::new("lastname".to_string(), LangParams::new("Japanese")).unwrap();
+/// let last_name = ln.fake_apply(&mut GenerationContext::new());
 /// println!("Fake last name: {}", last_name);
 /// ```
 #[derive(Debug)]
@@ -29,27 +36,29 @@ pub struct LastName {
 
 impl FakeType for LastName {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => name::raw::LastName(l).fake(),
-            Language::En(l) => name::raw::LastName(l).fake(),
-            Language::ArSa(l) => name::raw::LastName(l).fake(),
-            Language::FrFr(l) => name::raw::LastName(l).fake(),
-            Language::PtBr(l) => name::raw::LastName(l).fake(),
-            Language::ZhCn(l) => name::raw::LastName(l).fake(),
-            Language::ZhTw(l) => name::raw::LastName(l).fake(),
+            Language::JaJp(l) => name::raw::LastName(l).fake_with_rng(context.rng()),
+            Language::En(l) => name::raw::LastName(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => name::raw::LastName(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => name::raw::LastName(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => name::raw::LastName(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => name::raw::LastName(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => name::raw::LastName(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for LastName {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -62,19 +71,21 @@ impl From<LastName> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::LastName;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeType, FakeElement};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_last_name_fake_apply() {
-        let ln = LastName::new("lastname".to_string(), "English".to_string());
-        let last_name = ln.fake_apply();
+        let ln = LastName::new("lastname".to_string(), LangParams::new("English")).unwrap();
+        let last_name = ln.fake_apply(&mut GenerationContext::new());
 
         assert!(!last_name.is_empty(), "Generated last name should not be empty");
     }
 
     #[test]
     fn test_last_name_new() {
-        let ln = LastName::new("lastname".to_string(), "English".to_string());
+        let ln = LastName::new("lastname".to_string(), LangParams::new("English")).unwrap();
 
         assert_eq!(ln._fake_type, "lastname");
         assert_eq!(ln.lang, "English");
@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::name;
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `NameWithTitle` is an implementation of `FakeType`. It generates a name_with_title that varies according to language.
@@ -10,14 +13,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `NameWithTitle` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `NameWithTitle` with the `new` method.
+/// * `FakeElement`: This provides `NameWithTitle` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::name::name_with_title::NameWithTitle;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of NameWithTitle, specifying "Japanese" as the language
-/// let w = NameWithTitle::new("name_with_title", "Japanese");
-/// let name_with_title = w.fake_apply();
+/// let w = NameWithTitle::new("name_with_title".to_string(), LangParams::new("Japanese")).unwrap();
+/// let name_with_title = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake name_with_title: {}", name_with_title);
 /// ```
 #[derive(Debug)]
@@ -28,27 +35,29 @@ pub struct NameWithTitle {
 
 impl FakeType for NameWithTitle {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => name::raw::NameWithTitle(l).fake(),
-            Language::En(l) => name::raw::NameWithTitle(l).fake(),
-            Language::ArSa(l) => name::raw::NameWithTitle(l).fake(),
-            Language::FrFr(l) => name::raw::NameWithTitle(l).fake(),
-            Language::PtBr(l) => name::raw::NameWithTitle(l).fake(),
-            Language::ZhCn(l) => name::raw::NameWithTitle(l).fake(),
-            Language::ZhTw(l) => name::raw::NameWithTitle(l).fake(),
+            Language::JaJp(l) => name::raw::NameWithTitle(l).fake_with_rng(context.rng()),
+            Language::En(l) => name::raw::NameWithTitle(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => name::raw::NameWithTitle(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => name::raw::NameWithTitle(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => name::raw::NameWithTitle(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => name::raw::NameWithTitle(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => name::raw::NameWithTitle(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for NameWithTitle {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -61,18 +70,20 @@ impl From<NameWithTitle> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::NameWithTitle;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeElement, FakeType};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_name_with_title_fake_apply() {
-        let w = NameWithTitle::new("name_with_title".to_string(), "English".to_string());
-        let name_with_title = w.fake_apply();
+        let w = NameWithTitle::new("name_with_title".to_string(), LangParams::new("English")).unwrap();
+        let name_with_title = w.fake_apply(&mut GenerationContext::new());
         assert!(!name_with_title.is_empty(), "Generated name_with_title should not be empty");
     }
 
     #[test]
     fn test_name_with_title_new() {
-        let w = NameWithTitle::new("name_with_title".to_string(), "English".to_string());
+        let w = NameWithTitle::new("name_with_title".to_string(), LangParams::new("English")).unwrap();
         assert_eq!(w._fake_type, "name_with_title");
         assert_eq!(w.lang, "English");
     }
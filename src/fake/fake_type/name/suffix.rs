@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::{name};
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `Suffix` is an implementation of `FakeType`. It generates a suffix that varies according to language.
@@ -10,14 +13,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Suffix` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `Suffix` with the `new` method.
+/// * `FakeElement`: This provides `Suffix` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::name::suffix::Suffix;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Suffix, specifying "Japanese" as the language
-/// let w = Suffix::new("suffix", "Japanese");
-/// let suffix = w.fake_apply();
+/// let w = Suffix::new("suffix".to_string(), LangParams::new("Japanese")).unwrap();
+/// let suffix = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake suffix: {}", suffix);
 /// ```
 #[derive(Debug)]
@@ -28,27 +35,29 @@ pub struct Suffix {
 
 impl FakeType for Suffix {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => name::raw::Suffix(l).fake(),
-            Language::En(l) => name::raw::Suffix(l).fake(),
-            Language::ArSa(l) => name::raw::Suffix(l).fake(),
-            Language::FrFr(l) => name::raw::Suffix(l).fake(),
-            Language::PtBr(l) => name::raw::Suffix(l).fake(),
-            Language::ZhCn(l) => name::raw::Suffix(l).fake(),
-            Language::ZhTw(l) => name::raw::Suffix(l).fake(),
+            Language::JaJp(l) => name::raw::Suffix(l).fake_with_rng(context.rng()),
+            Language::En(l) => name::raw::Suffix(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => name::raw::Suffix(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => name::raw::Suffix(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => name::raw::Suffix(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => name::raw::Suffix(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => name::raw::Suffix(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for Suffix {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -61,18 +70,20 @@ impl From<Suffix> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Suffix;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeElement, FakeType};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_suffix_fake_apply() {
-        let w = Suffix::new("suffix".to_string(), "English".to_string());
-        let suffix = w.fake_apply();
+        let w = Suffix::new("suffix".to_string(), LangParams::new("English")).unwrap();
+        let suffix = w.fake_apply(&mut GenerationContext::new());
         assert!(!suffix.is_empty(), "Generated suffix should not be empty");
     }
 
     #[test]
     fn test_suffix_new() {
-        let w = Suffix::new("suffix".to_string(), "English".to_string());
+        let w = Suffix::new("suffix".to_string(), LangParams::new("English")).unwrap();
         assert_eq!(w._fake_type, "suffix");
         assert_eq!(w.lang, "English");
     }
@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::{name};
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::LangParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `Title` is an implementation of `FakeType`. It generates a title that varies according to language.
@@ -10,14 +13,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Title` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `Title` with the `new` method.
+/// * `FakeElement`: This provides `Title` with the `new` method, taking `LangParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::name::title::Title;
+/// # use fake_cli::fake::fake_type::params::LangParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Title, specifying "Japanese" as the language
-/// let w = Title::new("title", "Japanese");
-/// let title = w.fake_apply();
+/// let w = Title::new("title".to_string(), LangParams::new("Japanese")).unwrap();
+/// let title = w.fake_apply(&mut GenerationContext::new());
 /// println!("Fake title: {}", title);
 /// ```
 #[derive(Debug)]
@@ -28,27 +35,29 @@ pub struct Title {
 
 impl FakeType for Title {
     type Response = String;
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => name::raw::Title(l).fake(),
-            Language::En(l) => name::raw::Title(l).fake(),
-            Language::ArSa(l) => name::raw::Title(l).fake(),
-            Language::FrFr(l) => name::raw::Title(l).fake(),
-            Language::PtBr(l) => name::raw::Title(l).fake(),
-            Language::ZhCn(l) => name::raw::Title(l).fake(),
-            Language::ZhTw(l) => name::raw::Title(l).fake(),
+            Language::JaJp(l) => name::raw::Title(l).fake_with_rng(context.rng()),
+            Language::En(l) => name::raw::Title(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => name::raw::Title(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => name::raw::Title(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => name::raw::Title(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => name::raw::Title(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => name::raw::Title(l).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
 impl FakeElement for Title {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = LangParams;
+
+    fn new(_fake_type: String, params: LangParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -61,18 +70,20 @@ impl From<Title> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Title;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeElement, FakeType};
+    use crate::fake::fake_type::params::LangParams;
 
     #[test]
     fn test_title_fake_apply() {
-        let w = Title::new("title".to_string(), "English".to_string());
-        let title = w.fake_apply();
+        let w = Title::new("title".to_string(), LangParams::new("English")).unwrap();
+        let title = w.fake_apply(&mut GenerationContext::new());
         assert!(!title.is_empty(), "Generated title should not be empty");
     }
 
     #[test]
     fn test_title_new() {
-        let w = Title::new("title".to_string(), "English".to_string());
+        let w = Title::new("title".to_string(), LangParams::new("English")).unwrap();
         assert_eq!(w._fake_type, "title");
         assert_eq!(w.lang, "English");
     }
@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::number;
+use anyhow::Result;
 use serde_json::{Number, Value};
 use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
 use crate::fake::fake_type::{FakeElement, FakeType};
+use crate::fake::fake_type::params::DigitParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `Digit` is an implementation of `FakeType`. It generates a single digit
@@ -11,14 +14,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `Digit` with the `fake_apply` and `to_value` methods.
-/// * `FakeElement`: This provides `Digit` with the `new` method.
+/// * `FakeElement`: This provides `Digit` with the `new` method, taking `DigitParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::number::digit::Digit;
+/// # use fake_cli::fake::fake_type::params::DigitParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of Digit, specifying "Japanese" as the language
-/// let d = Digit::new("digit", "Japanese");
-/// let digit = d.fake_apply();
+/// let d = Digit::new("digit".to_string(), DigitParams::new("Japanese")).unwrap();
+/// let digit = d.fake_apply(&mut GenerationContext::new());
 /// println!("Fake digit: {}", digit);
 /// ```
 #[derive(Debug)]
@@ -30,30 +37,32 @@ pub struct Digit {
 impl FakeType for Digit {
     type Response = u8;
 
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         let digit: &str = match lang {
-            Language::JaJp(l) => number::raw::Digit(l).fake(),
-            Language::En(l) => number::raw::Digit(l).fake(),
-            Language::ArSa(l) => number::raw::Digit(l).fake(),
-            Language::FrFr(l) => number::raw::Digit(l).fake(),
-            Language::PtBr(l) => number::raw::Digit(l).fake(),
-            Language::ZhCn(l) => number::raw::Digit(l).fake(),
-            Language::ZhTw(l) => number::raw::Digit(l).fake(),
+            Language::JaJp(l) => number::raw::Digit(l).fake_with_rng(context.rng()),
+            Language::En(l) => number::raw::Digit(l).fake_with_rng(context.rng()),
+            Language::ArSa(l) => number::raw::Digit(l).fake_with_rng(context.rng()),
+            Language::FrFr(l) => number::raw::Digit(l).fake_with_rng(context.rng()),
+            Language::PtBr(l) => number::raw::Digit(l).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => number::raw::Digit(l).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => number::raw::Digit(l).fake_with_rng(context.rng()),
         };
 
         digit.parse::<u8>().unwrap()
     }
 
-    fn to_value(&self) -> Value {
-        let number = Number::from(self.fake_apply());
-        Value::Number(number)
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        let number = Number::from(self.fake_apply(context));
+        Ok(Value::Number(number))
     }
 }
 
 impl FakeElement for Digit {
-    fn new(_fake_type: String, lang: String) -> Self {
-        Self { _fake_type, lang }
+    type Params = DigitParams;
+
+    fn new(_fake_type: String, params: DigitParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang })
     }
 }
 
@@ -66,12 +75,14 @@ impl From<Digit> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::Digit;
+    use crate::fake::generation_context::GenerationContext;
     use crate::fake::fake_type::{FakeType, FakeElement};
+    use crate::fake::fake_type::params::DigitParams;
 
     #[test]
     fn test_digit_fake_apply() {
-        let d = Digit::new("digit".to_string(), "English".to_string());
-        let digit = d.fake_apply();
+        let d = Digit::new("digit".to_string(), DigitParams::new("English")).unwrap();
+        let digit = d.fake_apply(&mut GenerationContext::new());
 
         // Since u8 is always >= 0 no need to check "digit >= 0"
         assert!(digit <= 9, "Generated digit should be less than or equal to 9");
@@ -79,7 +90,7 @@ mod tests {
 
     #[test]
     fn test_digit_new() {
-        let d = Digit::new("digit".to_string(), "English".to_string());
+        let d = Digit::new("digit".to_string(), DigitParams::new("English")).unwrap();
 
         assert_eq!(d._fake_type, "digit");
         assert_eq!(d.lang, "English");
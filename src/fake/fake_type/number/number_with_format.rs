@@ -1,8 +1,11 @@
 use fake::Fake;
 use fake::faker::number;
+use anyhow::Result;
 use serde_json::Value;
 use crate::fake::fake_definition_element::FakeDefinitionElement;
-use crate::fake::fake_type::{FakeType, FakeWithFormatElement};
+use crate::fake::generation_context::GenerationContext;
+use crate::fake::fake_type::{FakeType, FakeElement};
+use crate::fake::fake_type::params::FormatParams;
 use crate::fake::lang::{get_language, Language};
 
 /// `NumberWithFormat` is an implementation of `FakeType`. It generates a number with a specific format
@@ -11,14 +14,18 @@ use crate::fake::lang::{get_language, Language};
 /// # Attributes
 ///
 /// * `FakeType`: This provides `NumberWithFormat` with the `fake_apply` and `to_value` methods.
-/// * `FakeWithFormatElement`: This provides `NumberWithFormat` with the `new` method.
+/// * `FakeElement`: This provides `NumberWithFormat` with the `new` method, taking `FormatParams`.
 ///
 /// # Example
 ///
 /// ```
+/// # use fake_cli::fake::fake_type::{FakeElement, FakeType};
+/// # use fake_cli::fake::fake_type::number::number_with_format::NumberWithFormat;
+/// # use fake_cli::fake::fake_type::params::FormatParams;
+/// # use fake_cli::GenerationContext;
 /// // Create a new instance of NumberWithFormat, specifying "Japanese" as the language and a specific format
-/// let n = NumberWithFormat::new("number_with_format", "Japanese", "###-###".to_string());
-/// let number = n.fake_apply();
+/// let n = NumberWithFormat::new("number_with_format".to_string(), FormatParams::new("Japanese", "###-###")).unwrap();
+/// let number = n.fake_apply(&mut GenerationContext::new());
 /// println!("Fake number with format: {}", number);
 /// ```
 #[derive(Debug)]
@@ -31,27 +38,29 @@ pub struct NumberWithFormat {
 impl FakeType for NumberWithFormat {
     type Response = String;
 
-    fn fake_apply(&self) -> Self::Response {
-        let lang = get_language(&self.lang.as_str());
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let lang = get_language(self.lang.as_str());
         match lang {
-            Language::JaJp(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake(),
-            Language::En(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake(),
-            Language::ArSa(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake(),
-            Language::FrFr(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake(),
-            Language::PtBr(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake(),
-            Language::ZhCn(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake(),
-            Language::ZhTw(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake(),
+            Language::JaJp(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake_with_rng(context.rng()),
+            Language::En(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake_with_rng(context.rng()),
+            Language::ArSa(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake_with_rng(context.rng()),
+            Language::FrFr(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake_with_rng(context.rng()),
+            Language::PtBr(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake_with_rng(context.rng()),
+            Language::ZhCn(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake_with_rng(context.rng()),
+            Language::ZhTw(l) => number::raw::NumberWithFormat(l, self.format.as_str()).fake_with_rng(context.rng()),
         }
     }
 
-    fn to_value(&self) -> Value {
-        Value::String(self.fake_apply())
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)))
     }
 }
 
-impl FakeWithFormatElement for NumberWithFormat {
-    fn new(_fake_type: String, lang: String, format: String) -> Self {
-        Self { _fake_type, lang, format }
+impl FakeElement for NumberWithFormat {
+    type Params = FormatParams;
+
+    fn new(_fake_type: String, params: FormatParams) -> Result<Self> {
+        Ok(Self { _fake_type, lang: params.lang, format: params.format })
     }
 }
 
@@ -64,19 +73,21 @@ impl From<NumberWithFormat> for FakeDefinitionElement {
 #[cfg(test)]
 mod tests {
     use super::NumberWithFormat;
-    use crate::fake::fake_type::{FakeType, FakeWithFormatElement};
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::fake_type::{FakeType, FakeElement};
+    use crate::fake::fake_type::params::FormatParams;
 
     #[test]
     fn test_number_with_format_fake_apply() {
-        let n = NumberWithFormat::new("number_with_format".to_string(), "English".to_string(), "###-###".to_string());
-        let number = n.fake_apply();
+        let n = NumberWithFormat::new("number_with_format".to_string(), FormatParams::new("English", "###-###")).unwrap();
+        let number = n.fake_apply(&mut GenerationContext::new());
 
         assert!(!number.is_empty(), "Generated number with format should not be empty");
     }
 
     #[test]
     fn test_number_with_format_new() {
-        let n = NumberWithFormat::new("number_with_format".to_string(), "English".to_string(), "###-###".to_string());
+        let n = NumberWithFormat::new("number_with_format".to_string(), FormatParams::new("English", "###-###")).unwrap();
         assert_eq!(n._fake_type, "number_with_format");
         assert_eq!(n.lang, "English");
         assert_eq!(n.format, "###-###");
@@ -0,0 +1,480 @@
+use anyhow::{anyhow, Result};
+use serde::de::{self, DeserializeOwned, Unexpected};
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+use crate::fake::fake_type::array::{ArraySort, Distribution};
+use crate::fake::fake_type::reference::Cardinality;
+use crate::fake::fake_type::sequence::SequenceScope;
+use crate::fake::lang::LOCALES;
+use crate::fake::type_info::{ParamInfo, ParamType};
+
+/// The language a fake type generates its values in when `lang` is not set.
+pub const DEFAULT_LANG: &str = "EN";
+
+/// The settings any definition element may be set with, whether or not its fake type takes them as parameters.
+/// `fields` is not one of them, as only a `map` and the element of an `array` may be named so.
+pub const SHARED_SETTINGS: [&str; 4] = ["fake_type", "lang", "when", "unique"];
+
+fn default_lang() -> String {
+    DEFAULT_LANG.to_string()
+}
+
+fn default_ratio() -> u8 {
+    50
+}
+
+fn default_one() -> i64 {
+    1
+}
+
+/// Deserializes a percentage from 0 to 100, rejecting any other integer instead of truncating it.
+fn percentage<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<u8, D::Error> {
+    let value = u64::deserialize(deserializer)?;
+    match u8::try_from(value) {
+        Ok(percentage) if percentage <= 100 => Ok(percentage),
+        _ => Err(de::Error::invalid_value(Unexpected::Unsigned(value), &"an integer from 0 to 100")),
+    }
+}
+
+/// The `DescribeParams` trait is used for parameter structs which describe their parameters,
+/// so that the parameters of every fake type can be listed without reading the source.
+pub trait DescribeParams {
+    fn describe() -> Vec<ParamInfo>;
+}
+
+fn lang_param() -> ParamInfo {
+    ParamInfo::new("lang", ParamType::String, "The language of the generated values").default(json!(DEFAULT_LANG)).values(&LOCALES)
+}
+
+/// Deserializes the parameters `T` of the fake type `fake_type` from the settings of a definition element.
+/// The `SHARED_SETTINGS`, such as `fake_type`, `unique` and `when`, are ignored when they are not parameters of `T`,
+/// and any other setting `T` does not take is an error, so that a misspelled parameter is not silently ignored.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::fake_type::params::{from_setting, RangeParams};
+/// # use serde_json::json;
+/// # fn main() -> anyhow::Result<()> {
+/// let params: RangeParams = from_setting(json!({ "fake_type": "words", "min": 1, "max": 5 }).as_object().unwrap(), "words")?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_setting<T: DeserializeOwned + DescribeParams>(setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<T> {
+    let params = T::describe();
+    let setting = setting
+        .iter()
+        .filter(|(key, _)| !SHARED_SETTINGS.contains(&key.as_str()) || params.iter().any(|param| &param.name == *key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    serde_json::from_value(Value::Object(setting)).map_err(|e| anyhow!("fake_type: {}, {}", fake_type, e))
+}
+
+/// `LangParams` are the parameters of a fake type which only takes the language of its values.
+///
+/// # Attributes
+///
+/// * `lang`: The language of the generated values. Defaults to `EN`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LangParams {
+    #[serde(default = "default_lang")]
+    pub lang: String,
+}
+
+/// `DigitParams` are the parameters of a `digit`.
+///
+/// # Attributes
+///
+/// * `lang`: The language of the generated values. Defaults to `EN`.
+/// * `parse_type`: Deprecated and ignored. A digit is always generated as a number from 0 to 9.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DigitParams {
+    #[serde(default = "default_lang")]
+    pub lang: String,
+    #[serde(default)]
+    pub parse_type: Option<String>,
+}
+
+/// `RangeParams` are the parameters of a fake type which generates a number of items within a range.
+///
+/// # Attributes
+///
+/// * `lang`: The language of the generated values. Defaults to `EN`.
+/// * `min`: The minimum number of items, inclusive.
+/// * `max`: The maximum number of items, exclusive.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RangeParams {
+    #[serde(default = "default_lang")]
+    pub lang: String,
+    pub min: usize,
+    pub max: usize,
+}
+
+/// `RatioParams` are the parameters of a fake type which generates `true` with a given ratio.
+///
+/// # Attributes
+///
+/// * `lang`: The language of the generated values. Defaults to `EN`.
+/// * `ratio`: The percentage of `true` values. Defaults to 50.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RatioParams {
+    #[serde(default = "default_lang")]
+    pub lang: String,
+    #[serde(default = "default_ratio", deserialize_with = "percentage")]
+    pub ratio: u8,
+}
+
+/// `FormatParams` are the parameters of a fake type which generates values in a given format.
+///
+/// # Attributes
+///
+/// * `lang`: The language of the generated values. Defaults to `EN`.
+/// * `format`: The format of the generated values, e.g. `###-####` where every `#` is a digit.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormatParams {
+    #[serde(default = "default_lang")]
+    pub lang: String,
+    pub format: String,
+}
+
+/// `ArrayParams` are the parameters of an `array`, other than its element. Its other settings are not rejected,
+/// as the one which is not a parameter is the element.
+///
+/// # Attributes
+///
+/// * `count`: The fixed length of the arrays. Can not be used together with `min_count` and `max_count`.
+/// * `min_count`, `max_count`: The range of the length of the arrays, both inclusive.
+/// * `distribution`: The distribution the length is picked with within the range. Defaults to `uniform`.
+/// * `unique`: Whether the elements of each array are distinct.
+/// * `sort`: The order the elements of each array are sorted in, `asc` or `desc`.
+/// * `items`: The element, when it is not given as the only other key.
+/// * `tuple`: The elements of a fixed-shape array, one definition per position.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ArrayParams {
+    pub count: Option<usize>,
+    pub min_count: Option<usize>,
+    pub max_count: Option<usize>,
+    #[serde(default)]
+    pub distribution: Distribution,
+    #[serde(default)]
+    pub unique: bool,
+    pub sort: Option<ArraySort>,
+    pub items: Option<Value>,
+    pub tuple: Option<Vec<Value>>,
+}
+
+/// `TemplateParams` are the parameters of a `template`.
+///
+/// # Attributes
+///
+/// * `template`: The template, e.g. `{{first_name}}.{{last_name}}@example.com`.
+/// * `lang`: The language of the inline generators. Defaults to `EN`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateParams {
+    pub template: String,
+    #[serde(default = "default_lang")]
+    pub lang: String,
+}
+
+/// `ReferenceParams` are the parameters of a `ref`.
+///
+/// # Attributes
+///
+/// * `reference`: The field of another entity, e.g. `users.id`, set as `ref`.
+/// * `cardinality`: How the referenced records are picked, `1:1`, `1:N` or `random`. Defaults to `random`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReferenceParams {
+    #[serde(rename = "ref")]
+    pub reference: String,
+    #[serde(default)]
+    pub cardinality: Cardinality,
+}
+
+/// `SequenceParams` are the parameters of a `sequence`.
+///
+/// # Attributes
+///
+/// * `start`: The first number. Defaults to 1.
+/// * `step`: The increment between numbers. Defaults to 1.
+/// * `format`: The format of the numbers, e.g. `ORD-######`.
+/// * `scope`: Whether the counter is `global` or restarts for every `array`. Defaults to `global`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SequenceParams {
+    #[serde(default = "default_one")]
+    pub start: i64,
+    #[serde(default = "default_one")]
+    pub step: i64,
+    pub format: Option<String>,
+    #[serde(default)]
+    pub scope: SequenceScope,
+}
+
+/// `ExprParams` are the parameters of an `expr`.
+///
+/// # Attributes
+///
+/// * `expr`: The expression computing the value from other fields, e.g. `price * quantity`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExprParams {
+    pub expr: String,
+}
+
+/// `ConditionalParams` are the parameters of an `if`.
+///
+/// # Attributes
+///
+/// * `condition`: The boolean expression deciding the branch, set as `if`.
+/// * `then`: The definition generated when the condition holds.
+/// * `otherwise`: The definition generated when it does not, set as `else`. Nothing is generated without it.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConditionalParams {
+    #[serde(rename = "if")]
+    pub condition: String,
+    pub then: Value,
+    #[serde(rename = "else")]
+    pub otherwise: Option<Value>,
+}
+
+/// `ConstantParams` are the parameters of a `constant`.
+///
+/// # Attributes
+///
+/// * `value`: The value generated every time, which may be `null`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConstantParams {
+    pub value: Value,
+}
+
+impl DescribeParams for LangParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![lang_param()]
+    }
+}
+
+impl DescribeParams for DigitParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            lang_param(),
+            ParamInfo::new("parse_type", ParamType::String, "Ignored, as a digit is always generated as a number from 0 to 9").optional().deprecated(),
+        ]
+    }
+}
+
+impl DescribeParams for RangeParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            lang_param(),
+            ParamInfo::new("min", ParamType::Integer, "The minimum number of items, inclusive").minimum(0),
+            ParamInfo::new("max", ParamType::Integer, "The maximum number of items, exclusive. Should be greater than min").minimum(0),
+        ]
+    }
+}
+
+impl DescribeParams for RatioParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            lang_param(),
+            ParamInfo::new("ratio", ParamType::Integer, "The percentage of true values").default(json!(50)).range(0, 100),
+        ]
+    }
+}
+
+impl DescribeParams for FormatParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            lang_param(),
+            ParamInfo::new("format", ParamType::String, "The format of the values, where every # is a digit, e.g. ###-####"),
+        ]
+    }
+}
+
+impl DescribeParams for ArrayParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            ParamInfo::new("count", ParamType::Integer, "The fixed length of the arrays").optional().minimum(0),
+            ParamInfo::new("min_count", ParamType::Integer, "The minimum length of the arrays, used together with max_count").optional().minimum(0),
+            ParamInfo::new("max_count", ParamType::Integer, "The maximum length of the arrays, used together with min_count").optional().minimum(0),
+            ParamInfo::new("distribution", ParamType::String, "The distribution the length is picked with between min_count and max_count").default(json!("uniform")).values(&["uniform", "normal"]),
+            ParamInfo::new("unique", ParamType::Boolean, "Whether the elements of each array are distinct").default(json!(false)),
+            ParamInfo::new("sort", ParamType::String, "The order the elements of each array are sorted in").optional().values(&["asc", "desc"]),
+            ParamInfo::new("items", ParamType::Definition, "The element, when it is not given as the only other key").optional(),
+            ParamInfo::new("tuple", ParamType::Definitions, "The elements of a fixed-shape array, one per position").optional(),
+        ]
+    }
+}
+
+impl DescribeParams for TemplateParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            ParamInfo::new("template", ParamType::String, "The template, where {{field}} is replaced with the value of a field or an inline generator"),
+            ParamInfo::new("lang", ParamType::String, "The language of the inline generators").default(json!(DEFAULT_LANG)).values(&LOCALES),
+        ]
+    }
+}
+
+impl DescribeParams for ReferenceParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            ParamInfo::new("ref", ParamType::String, "The field of another entity of the dataset, e.g. users.id"),
+            ParamInfo::new("cardinality", ParamType::String, "How the referenced records are picked").default(json!("random")).values(&["1:1", "1:N", "random"]),
+        ]
+    }
+}
+
+impl DescribeParams for SequenceParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            ParamInfo::new("start", ParamType::Integer, "The first number").default(json!(1)),
+            ParamInfo::new("step", ParamType::Integer, "The increment between numbers").default(json!(1)),
+            ParamInfo::new("format", ParamType::String, "The format of the numbers, where the #s are the zero-padded number, e.g. ORD-######").optional(),
+            ParamInfo::new("scope", ParamType::String, "Whether the counter is shared by the whole run or restarts for every array").default(json!("global")).values(&["global", "array"]),
+        ]
+    }
+}
+
+impl DescribeParams for ExprParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![ParamInfo::new("expr", ParamType::Expression, "The expression computing the value from other fields, e.g. price * quantity")]
+    }
+}
+
+impl DescribeParams for ConditionalParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![
+            ParamInfo::new("if", ParamType::Expression, "The boolean expression deciding the branch"),
+            ParamInfo::new("then", ParamType::Definition, "The element generated when the condition holds"),
+            ParamInfo::new("else", ParamType::Definition, "The element generated when the condition does not hold. Nothing is generated without it").optional(),
+        ]
+    }
+}
+
+impl DescribeParams for ConstantParams {
+    fn describe() -> Vec<ParamInfo> {
+        vec![ParamInfo::new("value", ParamType::Any, "The value generated every time")]
+    }
+}
+
+impl LangParams {
+    pub fn new(lang: impl Into<String>) -> Self {
+        Self { lang: lang.into() }
+    }
+}
+
+impl DigitParams {
+    pub fn new(lang: impl Into<String>) -> Self {
+        Self { lang: lang.into(), parse_type: None }
+    }
+}
+
+impl RangeParams {
+    pub fn new(lang: impl Into<String>, min: usize, max: usize) -> Self {
+        Self { lang: lang.into(), min, max }
+    }
+}
+
+impl RatioParams {
+    pub fn new(lang: impl Into<String>, ratio: u8) -> Self {
+        Self { lang: lang.into(), ratio }
+    }
+}
+
+impl FormatParams {
+    pub fn new(lang: impl Into<String>, format: impl Into<String>) -> Self {
+        Self { lang: lang.into(), format: format.into() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_setting, ArrayParams, ConditionalParams, ConstantParams, DigitParams, ExprParams, FormatParams, LangParams, RangeParams, RatioParams, ReferenceParams, SequenceParams};
+    use crate::fake::fake_type::array::Distribution;
+    use crate::fake::fake_type::reference::Cardinality;
+    use crate::fake::fake_type::sequence::SequenceScope;
+    use serde_json::json;
+
+    #[test]
+    fn test_params_deserialize() {
+        assert_eq!(serde_json::from_value::<LangParams>(json!({ "lang": "JA_JP" })).unwrap(), LangParams::new("JA_JP"));
+        assert_eq!(serde_json::from_value::<RangeParams>(json!({ "min": 1, "max": 5 })).unwrap(), RangeParams::new("EN", 1, 5));
+        assert_eq!(serde_json::from_value::<FormatParams>(json!({ "format": "###" })).unwrap(), FormatParams::new("EN", "###"));
+        assert_eq!(serde_json::from_value::<DigitParams>(json!({ "lang": "JA_JP", "parse_type": "u8" })).unwrap().lang, "JA_JP", "The deprecated parse_type of a digit should be accepted");
+    }
+
+    #[test]
+    fn test_params_deserialize_with_defaults() {
+        assert_eq!(serde_json::from_value::<LangParams>(json!({})).unwrap(), LangParams::new("EN"), "lang should default to EN");
+        assert_eq!(serde_json::from_value::<RatioParams>(json!({ "lang": "JA_JP" })).unwrap(), RatioParams::new("JA_JP", 50), "ratio should default to 50");
+    }
+
+    #[test]
+    fn test_params_from_setting() {
+        let setting = json!({ "fake_type": "word", "lang": "EN", "unique": true, "when": "a > 1" });
+        assert_eq!(from_setting::<LangParams>(setting.as_object().unwrap(), "word").unwrap(), LangParams::new("EN"), "The shared settings should be ignored");
+        assert_eq!(from_setting::<ExprParams>(json!({ "fake_type": "expr", "expr": "1", "lang": "EN" }).as_object().unwrap(), "expr").unwrap().expr, "1", "lang should be allowed without being a parameter");
+        assert!(from_setting::<ArrayParams>(json!({ "fake_type": "array", "count": 2, "unique": true }).as_object().unwrap(), "array").unwrap().unique, "A shared setting should be kept when it is a parameter");
+
+        let lang = from_setting::<LangParams>(json!({ "fake_type": "word", "lnag": "JA_JP" }).as_object().unwrap(), "word").unwrap_err();
+        assert_eq!(lang.to_string(), "fake_type: word, unknown field `lnag`, expected `lang`", "Should return an error for a misspelled parameter");
+        assert!(from_setting::<RangeParams>(json!({ "fake_type": "words", "mn": 1, "min": 1, "max": 5 }).as_object().unwrap(), "words").is_err());
+        assert!(from_setting::<SequenceParams>(json!({ "fake_type": "sequence", "stat": 10 }).as_object().unwrap(), "sequence").is_err());
+        assert!(from_setting::<LangParams>(json!({ "fake_type": "word", "fields": { "q": 1 } }).as_object().unwrap(), "word").is_err(), "fields should only be allowed on a map and an array");
+    }
+
+    #[test]
+    fn test_params_deserialize_fail() {
+        assert!(serde_json::from_value::<RangeParams>(json!({ "min": 1 })).is_err(), "Should return an error for a missing parameter");
+        assert!(serde_json::from_value::<RangeParams>(json!({ "min": -1, "max": 5 })).is_err(), "Should return an error for a negative count");
+        assert!(serde_json::from_value::<LangParams>(json!({ "lang": 1 })).is_err(), "Should return an error for a parameter of the wrong type");
+    }
+
+    #[test]
+    fn test_params_deserialize_ratio() {
+        assert_eq!(serde_json::from_value::<RatioParams>(json!({ "ratio": 100 })).unwrap(), RatioParams::new("EN", 100));
+
+        let error = serde_json::from_value::<RatioParams>(json!({ "ratio": 300 })).unwrap_err();
+        assert_eq!(error.to_string(), "invalid value: integer `300`, expected an integer from 0 to 100", "Should reject a ratio out of range instead of truncating it");
+        assert!(serde_json::from_value::<RatioParams>(json!({ "ratio": 101 })).is_err());
+        assert!(serde_json::from_value::<RatioParams>(json!({ "ratio": 0.5 })).is_err(), "Should return an error for a ratio which is not an integer");
+    }
+
+    #[test]
+    fn test_params_deserialize_fake_cli_original() {
+        let array: ArrayParams = serde_json::from_value(json!({ "min_count": 1, "max_count": 3, "items": { "fake_type": "word" } })).unwrap();
+        assert_eq!((array.min_count, array.max_count, array.distribution, array.unique), (Some(1), Some(3), Distribution::Uniform, false));
+
+        let reference: ReferenceParams = serde_json::from_value(json!({ "ref": "users.id", "cardinality": "1:N" })).unwrap();
+        assert_eq!((reference.reference.as_str(), reference.cardinality), ("users.id", Cardinality::OneToMany));
+
+        let sequence: SequenceParams = serde_json::from_value(json!({ "step": 10, "scope": "array" })).unwrap();
+        assert_eq!((sequence.start, sequence.step, sequence.scope), (1, 10, SequenceScope::Array));
+
+        let conditional: ConditionalParams = serde_json::from_value(json!({ "if": "a > 1", "then": { "fake_type": "word" } })).unwrap();
+        assert_eq!((conditional.condition.as_str(), conditional.otherwise), ("a > 1", None));
+
+        assert_eq!(serde_json::from_value::<ConstantParams>(json!({ "value": null })).unwrap().value, json!(null), "A null constant should be allowed");
+    }
+
+    #[test]
+    fn test_params_deserialize_fake_cli_original_fail() {
+        let count = from_setting::<ArrayParams>(json!({ "count": -1 }).as_object().unwrap(), "array").unwrap_err();
+        assert_eq!(count.to_string(), "fake_type: array, invalid value: integer `-1`, expected usize");
+
+        let distribution = from_setting::<ArrayParams>(json!({ "distribution": "poisson" }).as_object().unwrap(), "array").unwrap_err();
+        assert_eq!(distribution.to_string(), "fake_type: array, unknown variant `poisson`, expected `uniform` or `normal`");
+
+        assert!(serde_json::from_value::<SequenceParams>(json!({ "start": 1.5 })).is_err(), "Should reject a start which is not an integer");
+        assert!(serde_json::from_value::<ReferenceParams>(json!({ "cardinality": "1:1" })).is_err(), "Should return an error when ref is missing");
+        assert!(serde_json::from_value::<ConstantParams>(json!({})).is_err(), "Should return an error when value is missing");
+    }
+}
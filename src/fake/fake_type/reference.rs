@@ -0,0 +1,213 @@
+use std::cell::RefCell;
+use anyhow::{anyhow, Result};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
+use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::{value_at, GenerationContext};
+
+/// `Cardinality` decides how the records of the referenced entity are picked.
+///
+/// * `OneToOne`: Every referenced record is picked at most once.
+/// * `OneToMany`: Every referenced record is picked once before any record is picked again.
+/// * `Random`: Records are picked independently at random.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+pub enum Cardinality {
+    #[serde(rename = "1:1")]
+    OneToOne,
+    #[serde(rename = "1:N")]
+    OneToMany,
+    #[default]
+    #[serde(rename = "random")]
+    Random,
+}
+
+/// `Reference` is an implementation of `FakeType`. It samples a value from the already generated records
+/// of another entity of a dataset, e.g. `users.id`, acting as a foreign key.
+///
+/// # Attributes
+///
+/// * `FakeType`: This provides `Reference` with the `fake_apply` and `to_value` methods.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::fake_type::reference::{Cardinality, Reference};
+/// # use fake_cli::{FakeType, GenerationContext};
+/// # use serde_json::json;
+/// // Create a new instance of Reference, sampling the id of the generated users
+/// let r = Reference::new("ref".to_string(), "users.id", Cardinality::Random).unwrap();
+/// let mut context = GenerationContext::new();
+/// context.insert_entity("users".to_string(), vec![json!({ "id": 1 }), json!({ "id": 2 })]);
+/// let user_id = r.fake_apply(&mut context).unwrap();
+/// println!("Fake user_id: {}", user_id);
+/// ```
+#[derive(Debug)]
+pub struct Reference {
+    _fake_type: String,
+    entity: String,
+    path: Vec<String>,
+    cardinality: Cardinality,
+    pool: RefCell<Option<Vec<usize>>>,
+}
+
+impl FakeType for Reference {
+    type Response = Result<Value>;
+
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let records = context.entity(&self.entity).ok_or(anyhow!("fake_type: ref, entity {} is not generated", self.entity))?;
+        if records.is_empty() {
+            Err(anyhow!("fake_type: ref, entity {} has no records", self.entity))?
+        }
+
+        let len = records.len();
+        let index = self.pick(len, context.rng())?;
+        let records = context.entity(&self.entity).ok_or(anyhow!("fake_type: ref, entity {} is not generated", self.entity))?;
+        value_at(&records[index], &self.path)
+            .cloned()
+            .ok_or(anyhow!("fake_type: ref, {}.{} is undefined", self.entity, self.path.join(".")))
+    }
+
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        self.fake_apply(context)
+    }
+}
+
+impl Reference {
+    /// Creates a new `Reference` to `reference`, written as `entity.field` (the field can be a nested path).
+    pub fn new(_fake_type: String, reference: &str, cardinality: Cardinality) -> Result<Self> {
+        let mut segments = reference.split('.').map(str::to_string);
+        let entity = segments.next().filter(|entity| !entity.is_empty());
+        let path: Vec<String> = segments.collect();
+
+        match entity {
+            Some(entity) if !path.is_empty() && path.iter().all(|segment| !segment.is_empty()) => {
+                Ok(Self { _fake_type, entity, path, cardinality, pool: RefCell::new(None) })
+            }
+            _ => Err(anyhow!("fake_type: ref, ref should be written as entity.field but got {}", reference)),
+        }
+    }
+
+    /// Returns the name of the referenced entity.
+    pub fn entity(&self) -> &str {
+        &self.entity
+    }
+
+    /// Appends the records left to pick to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        states.push(ElementState::Reference(self.pool.borrow().clone()));
+    }
+
+    /// Restores the records left to pick from `states`.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        if let Some(ElementState::Reference(pool)) = states.next() {
+            *self.pool.borrow_mut() = pool;
+        }
+    }
+
+    /// Returns the JSON Schema of the sampled values, a `$ref` to the schema of the referenced field under the
+    /// `definitions` of the dataset schema, e.g. `#/definitions/users/properties/id`.
+    pub fn output_schema(&self) -> Value {
+        let pointer: String = std::iter::once(self.entity.as_str())
+            .chain(self.path.iter().flat_map(|segment| ["properties", segment.as_str()]))
+            .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+            .collect();
+        json!({ "$ref": format!("#/definitions{}", pointer) })
+    }
+
+    /// Picks the index of the next referenced record out of `len` records.
+    fn pick<R: Rng>(&self, len: usize, rng: &mut R) -> Result<usize> {
+        if self.cardinality == Cardinality::Random {
+            return Ok(rng.gen_range(0..len));
+        }
+
+        let mut pool = self.pool.borrow_mut();
+        let pool = pool.get_or_insert_with(|| shuffled_indexes(len, rng));
+        if pool.is_empty() && self.cardinality == Cardinality::OneToMany {
+            *pool = shuffled_indexes(len, rng);
+        }
+
+        pool.pop().ok_or(anyhow!("fake_type: ref, {} has only {} records, which are exhausted by a 1:1 reference", self.entity, len))
+    }
+}
+
+fn shuffled_indexes<R: Rng>(len: usize, rng: &mut R) -> Vec<usize> {
+    let mut indexes: Vec<usize> = (0..len).collect();
+    indexes.shuffle(rng);
+    indexes
+}
+
+impl From<Reference> for FakeDefinitionElement {
+    fn from(value: Reference) -> Self {
+        FakeDefinitionElement::Reference(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cardinality, Reference};
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::{json, Value};
+
+    fn context_with_users(count: u64) -> GenerationContext {
+        let mut context = GenerationContext::new();
+        let users = (1..=count).map(|id| json!({ "id": id, "profile": { "name": format!("user{}", id) } })).collect();
+        context.insert_entity("users".to_string(), users);
+        context
+    }
+
+    #[test]
+    fn test_reference_fake_apply() {
+        let r = Reference::new("ref".to_string(), "users.profile.name", Cardinality::Random).unwrap();
+        let name = r.fake_apply(&mut context_with_users(3)).unwrap();
+
+        assert!(["user1", "user2", "user3"].contains(&name.as_str().unwrap()), "Should sample a generated value");
+    }
+
+    #[test]
+    fn test_reference_fake_apply_one_to_one() {
+        let r = Reference::new("ref".to_string(), "users.id", Cardinality::OneToOne).unwrap();
+        let mut context = context_with_users(3);
+
+        let mut ids: Vec<Value> = (0..3).map(|_| r.fake_apply(&mut context).unwrap()).collect();
+        ids.sort_by_key(|id| id.as_u64());
+
+        assert_eq!(ids, vec![json!(1), json!(2), json!(3)], "Every record should be referenced exactly once");
+        assert!(r.fake_apply(&mut context).is_err(), "Should return an error when the records are exhausted");
+    }
+
+    #[test]
+    fn test_reference_fake_apply_one_to_many() {
+        let r = Reference::new("ref".to_string(), "users.id", Cardinality::OneToMany).unwrap();
+        let mut context = context_with_users(2);
+
+        let ids: Vec<Value> = (0..4).map(|_| r.fake_apply(&mut context).unwrap()).collect();
+
+        assert_eq!(ids.iter().filter(|id| **id == json!(1)).count(), 2, "Records should be referenced evenly");
+    }
+
+    #[test]
+    fn test_reference_fake_apply_without_entity() {
+        let r = Reference::new("ref".to_string(), "users.id", Cardinality::Random).unwrap();
+
+        assert!(r.fake_apply(&mut GenerationContext::new()).is_err());
+    }
+
+    #[test]
+    fn test_reference_new() {
+        let r = Reference::new("ref".to_string(), "users.id", Cardinality::Random).unwrap();
+
+        assert_eq!(r._fake_type, "ref");
+        assert_eq!(r.entity(), "users");
+        assert_eq!(r.path, vec!["id".to_string()]);
+    }
+
+    #[test]
+    fn test_reference_new_fail() {
+        assert!(Reference::new("ref".to_string(), "users", Cardinality::Random).is_err());
+        assert!(Reference::new("ref".to_string(), ".id", Cardinality::Random).is_err());
+    }
+}
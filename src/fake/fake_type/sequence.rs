@@ -0,0 +1,189 @@
+use std::cell::RefCell;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::fake::expression::ValueType;
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
+use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::GenerationContext;
+
+/// `SequenceScope` decides which values share a counter.
+///
+/// * `Global`: A single counter is shared by every record generated during a run.
+/// * `Array`: The counter restarts for every array the sequence is generated in, e.g. for line item numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SequenceScope {
+    #[default]
+    Global,
+    Array,
+}
+
+/// `Sequence` is an implementation of `FakeType`. It generates an incrementing number, starting from `start`
+/// and incremented by `step` for every generated value, such as `1, 2, 3` or `ORD-000001, ORD-000002`.
+///
+/// With `format`, the number replaces the first run of `#` in the format, zero-padded to the length of the run,
+/// and the value is generated as a string.
+///
+/// # Attributes
+///
+/// * `FakeType`: This provides `Sequence` with the `fake_apply` and `to_value` methods.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::fake_type::sequence::{Sequence, SequenceScope};
+/// # use fake_cli::{FakeType, GenerationContext};
+/// // Create a new instance of Sequence, generating ORD-000001, ORD-000002, ...
+/// let s = Sequence::new("sequence".to_string(), 1, 1, Some("ORD-######".to_string()), SequenceScope::Global).unwrap();
+/// let id = s.fake_apply(&mut GenerationContext::new());
+/// println!("Fake id: {}", id);
+/// ```
+#[derive(Debug)]
+pub struct Sequence {
+    _fake_type: String,
+    start: i64,
+    step: i64,
+    format: Option<String>,
+    scope: SequenceScope,
+    state: RefCell<SequenceState>,
+}
+
+/// The counter of a `Sequence`, and the array it counts in for `SequenceScope::Array`.
+#[derive(Debug, Default, Clone)]
+pub struct SequenceState {
+    array: Option<usize>,
+    count: i64,
+}
+
+impl FakeType for Sequence {
+    type Response = Value;
+
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let mut state = self.state.borrow_mut();
+        if self.scope == SequenceScope::Array && state.array != context.current_array() {
+            state.array = context.current_array();
+            state.count = 0;
+        }
+
+        let number = self.start + self.step * state.count;
+        state.count += 1;
+
+        match &self.format {
+            Some(format) => Value::String(format_sequence(format, number)),
+            None => Value::from(number),
+        }
+    }
+
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(self.fake_apply(context))
+    }
+}
+
+impl Sequence {
+    /// Creates a new `Sequence`. Returns an error when `format` has no `#` to put the number in.
+    pub fn new(_fake_type: String, start: i64, step: i64, format: Option<String>, scope: SequenceScope) -> Result<Self> {
+        if format.as_ref().is_some_and(|format| !format.contains('#')) {
+            Err(anyhow!("fake_type: sequence, format should contain # for the number, e.g. ORD-######"))?
+        }
+
+        Ok(Self { _fake_type, start, step, format, scope, state: RefCell::new(SequenceState::default()) })
+    }
+
+    /// Appends the counter to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        states.push(ElementState::Sequence(self.state.borrow().clone()));
+    }
+
+    /// Restores the counter from `states`.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        if let Some(ElementState::Sequence(state)) = states.next() {
+            *self.state.borrow_mut() = state;
+        }
+    }
+
+    /// Returns the type of the generated values: a number, or a string with `format`.
+    pub fn value_type(&self) -> ValueType {
+        match self.format {
+            Some(_) => ValueType::String,
+            None => ValueType::Number,
+        }
+    }
+
+    /// Returns the JSON Schema of the generated values: an integer, or a string with `format`.
+    pub fn output_schema(&self) -> Value {
+        match self.format {
+            Some(_) => json!({ "type": "string" }),
+            None => json!({ "type": "integer" }),
+        }
+    }
+}
+
+/// Replaces the first run of `#` in `format` with `number`, zero-padded to the length of the run.
+fn format_sequence(format: &str, number: i64) -> String {
+    let start = format.find('#').unwrap_or(format.len());
+    let width = format[start..].chars().take_while(|c| *c == '#').count();
+
+    format!("{}{:0width$}{}", &format[..start], number, &format[start + width..], width = width)
+}
+
+impl From<Sequence> for FakeDefinitionElement {
+    fn from(value: Sequence) -> Self {
+        FakeDefinitionElement::Sequence(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_sequence, Sequence, SequenceScope};
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_sequence_fake_apply() {
+        let s = Sequence::new("sequence".to_string(), 10, 5, None, SequenceScope::Global).unwrap();
+        let mut context = GenerationContext::new();
+
+        let values: Vec<Value> = (0..3).map(|_| s.fake_apply(&mut context)).collect();
+
+        assert_eq!(values, vec![json!(10), json!(15), json!(20)]);
+    }
+
+    #[test]
+    fn test_sequence_fake_apply_with_format() {
+        let s = Sequence::new("sequence".to_string(), 1, 1, Some("ORD-######".to_string()), SequenceScope::Global).unwrap();
+        let mut context = GenerationContext::new();
+
+        assert_eq!(s.fake_apply(&mut context), json!("ORD-000001"));
+        assert_eq!(s.fake_apply(&mut context), json!("ORD-000002"));
+    }
+
+    #[test]
+    fn test_sequence_fake_apply_with_array_scope() {
+        let s = Sequence::new("sequence".to_string(), 1, 1, None, SequenceScope::Array).unwrap();
+        let mut context = GenerationContext::new();
+
+        context.push_array();
+        let first: Vec<Value> = (0..2).map(|_| s.fake_apply(&mut context)).collect();
+        context.pop_array();
+        context.push_array();
+        let second: Vec<Value> = (0..2).map(|_| s.fake_apply(&mut context)).collect();
+        context.pop_array();
+
+        assert_eq!(first, vec![json!(1), json!(2)]);
+        assert_eq!(second, vec![json!(1), json!(2)], "The counter should restart for every array");
+    }
+
+    #[test]
+    fn test_sequence_new() {
+        assert!(Sequence::new("sequence".to_string(), 1, 1, Some("ORD-".to_string()), SequenceScope::Global).is_err(), "Should return an error for a format without #");
+        assert!(serde_json::from_value::<SequenceScope>(serde_json::json!("record")).is_err());
+    }
+
+    #[test]
+    fn test_format_sequence() {
+        assert_eq!(format_sequence("####", 42), "0042");
+        assert_eq!(format_sequence("A-##-B", 123), "A-123-B", "Numbers longer than the format should not be truncated");
+    }
+}
@@ -0,0 +1,291 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
+use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::GenerationContext;
+use crate::fake::registry::Registry;
+
+/// `Template` is an implementation of `FakeType`. It renders a string with `{{placeholder}}`s.
+///
+/// A placeholder is a path to the value of a sibling or ancestor field (e.g. `{{first_name}}` or `{{address.city}}`),
+/// a context variable (`{{index}}`, `{{record_index}}`, `{{array_index}}`, `{{path}}`, `{{parent_path}}` or `{{seed}}`),
+/// or the name of a generator which only takes a `lang` (e.g. `{{word}}`). Fields take precedence over context variables,
+/// which take precedence over generators.
+/// Placeholders can be piped through filters: `lower`, `upper`, `slug` and `truncate(n)`.
+///
+/// # Attributes
+///
+/// * `FakeType`: This provides `Template` with the `fake_apply` and `to_value` methods.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::fake_type::template::Template;
+/// # use fake_cli::{FakeType, GenerationContext};
+/// // Create a new instance of Template, rendering an email from a generated word
+/// let t = Template::new("template".to_string(), "EN".to_string(), "{{word | lower}}@example.com").unwrap();
+/// let email = t.fake_apply(&mut GenerationContext::new()).unwrap();
+/// println!("Fake email: {}", email);
+/// ```
+#[derive(Debug)]
+pub struct Template {
+    _fake_type: String,
+    parts: Vec<TemplatePart>,
+}
+
+/// `TemplatePart` is a piece of a parsed template: either literal text or a placeholder.
+#[derive(Debug)]
+enum TemplatePart {
+    Text(String),
+    Placeholder {
+        path: Vec<String>,
+        filters: Vec<TemplateFilter>,
+        generator: Option<Box<FakeDefinitionElement>>,
+    },
+}
+
+/// `TemplateFilter` transforms the rendered value of a placeholder.
+#[derive(Debug, PartialEq)]
+enum TemplateFilter {
+    Lower,
+    Upper,
+    Slug,
+    Truncate(usize),
+}
+
+impl FakeType for Template {
+    type Response = Result<String>;
+
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        let mut rendered = String::new();
+
+        for part in &self.parts {
+            match part {
+                TemplatePart::Text(text) => rendered.push_str(text),
+                TemplatePart::Placeholder { path, filters, generator } => {
+                    let value = match (context.lookup(path).cloned(), generator) {
+                        (Some(value), _) => value,
+                        (None, _) if path.len() == 1 && GenerationContext::is_variable(&path[0]) => context.variable(&path[0]).unwrap_or(Value::Null),
+                        (None, Some(generator)) => generator.to_value(context)?,
+                        (None, None) => Err(anyhow!("fake_type: template, {{{{{}}}}} is undefined", path.join(".")))?,
+                    };
+
+                    let text = filters.iter().fold(value_to_string(&value), |text, filter| filter.apply(text));
+                    rendered.push_str(&text);
+                }
+            }
+        }
+
+        Ok(rendered)
+    }
+
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::String(self.fake_apply(context)?))
+    }
+}
+
+impl Template {
+    /// Creates a new `Template`. `lang` is the language of the inline generators.
+    pub fn new(_fake_type: String, lang: String, template: &str) -> Result<Self> {
+        Template::new_with_registry(_fake_type, lang, template, Registry::builtin())
+    }
+
+    /// Creates a new `Template` whose inline generators are the fake types registered in `registry`.
+    pub fn new_with_registry(_fake_type: String, lang: String, template: &str, registry: &Registry) -> Result<Self> {
+        let parts = Template::parse(template, &lang, registry)?;
+        Ok(Self { _fake_type, parts })
+    }
+
+    /// Returns the names of the fields the placeholders refer to (the first segment of each path).
+    pub fn references(&self) -> Vec<String> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                TemplatePart::Placeholder { path, .. } => path.first().cloned(),
+                TemplatePart::Text(_) => None,
+            })
+            .collect()
+    }
+
+    /// Appends the state of the stateful inline generators to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        for generator in self.generators() {
+            generator.save_state(states);
+        }
+    }
+
+    /// Restores the state of the stateful inline generators from `states`.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        for generator in self.generators() {
+            generator.restore_state(states);
+        }
+    }
+
+    fn generators(&self) -> impl Iterator<Item = &FakeDefinitionElement> {
+        self.parts.iter().filter_map(|part| match part {
+            TemplatePart::Placeholder { generator, .. } => generator.as_deref(),
+            TemplatePart::Text(_) => None,
+        })
+    }
+
+    /// Returns whether `name` can be rendered by a generator of `registry` when no field of that name is in scope.
+    pub fn is_inline_generator(name: &str, registry: &Registry) -> bool {
+        FakeDefinitionElement::generate_with_registry(&json!({ "fake_type": name, "lang": "EN" }), registry).is_ok()
+    }
+
+    fn parse(template: &str, lang: &str, registry: &Registry) -> Result<Vec<TemplatePart>> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                parts.push(TemplatePart::Text(rest[..start].to_string()));
+            }
+
+            let end = rest[start..].find("}}").ok_or(anyhow!("fake_type: template, unclosed placeholder in \"{}\"", template))?;
+            parts.push(Template::parse_placeholder(&rest[start + 2..start + end], lang, registry)?);
+            rest = &rest[start + end + 2..];
+        }
+
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Text(rest.to_string()));
+        }
+
+        Ok(parts)
+    }
+
+    fn parse_placeholder(placeholder: &str, lang: &str, registry: &Registry) -> Result<TemplatePart> {
+        let mut segments = placeholder.split('|').map(str::trim);
+        let path_value = segments.next().unwrap_or_default();
+        if path_value.is_empty() {
+            Err(anyhow!("fake_type: template, empty placeholder"))?
+        }
+
+        let path: Vec<String> = path_value.split('.').map(str::to_string).collect();
+        let filters = segments.map(TemplateFilter::parse).collect::<Result<Vec<TemplateFilter>>>()?;
+        let generator = match path.as_slice() {
+            [name] => FakeDefinitionElement::generate_with_registry(&json!({ "fake_type": name, "lang": lang }), registry).ok().map(Box::new),
+            _ => None,
+        };
+
+        Ok(TemplatePart::Placeholder { path, filters, generator })
+    }
+}
+
+impl TemplateFilter {
+    fn parse(filter: &str) -> Result<Self> {
+        match filter {
+            "lower" => Ok(TemplateFilter::Lower),
+            "upper" => Ok(TemplateFilter::Upper),
+            "slug" => Ok(TemplateFilter::Slug),
+            _ => {
+                let length = filter
+                    .strip_prefix("truncate(")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .ok_or(anyhow!("fake_type: template, undefined filter {}", filter))?;
+                let length = length.trim().parse::<usize>().map_err(|_| anyhow!("fake_type: template, truncate parse error. please truncate(n) with 0 <= n"))?;
+                Ok(TemplateFilter::Truncate(length))
+            }
+        }
+    }
+
+    fn apply(&self, text: String) -> String {
+        match self {
+            TemplateFilter::Lower => text.to_lowercase(),
+            TemplateFilter::Upper => text.to_uppercase(),
+            TemplateFilter::Slug => {
+                let slug: String = text
+                    .to_lowercase()
+                    .chars()
+                    .map(|c| if c.is_alphanumeric() { c } else { '-' })
+                    .collect();
+                slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<&str>>().join("-")
+            }
+            TemplateFilter::Truncate(length) => text.chars().take(*length).collect(),
+        }
+    }
+}
+
+/// Renders a generated value as template text. Strings are inserted as is, other values as JSON.
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+impl From<Template> for FakeDefinitionElement {
+    fn from(value: Template) -> Self {
+        FakeDefinitionElement::Template(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Template, TemplateFilter};
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::json;
+
+    #[test]
+    fn test_template_fake_apply() {
+        let t = Template::new("template".to_string(), "EN".to_string(), "{{ first_name | lower }}.{{last_name|upper}}@{{domain.name}}").unwrap();
+        let mut context = GenerationContext::new();
+        context.push_scope();
+        context.insert("first_name".to_string(), json!("Taro"));
+        context.insert("last_name".to_string(), json!("Yamada"));
+        context.insert("domain".to_string(), json!({ "name": "example.com" }));
+
+        let rendered = t.fake_apply(&mut context).unwrap();
+
+        assert_eq!(rendered, "taro.YAMADA@example.com");
+    }
+
+    #[test]
+    fn test_template_fake_apply_with_generator() {
+        let t = Template::new("template".to_string(), "EN".to_string(), "tag-{{word}}").unwrap();
+        let rendered = t.fake_apply(&mut GenerationContext::new()).unwrap();
+
+        assert!(rendered.len() > "tag-".len(), "Inline generator should be rendered");
+    }
+
+    #[test]
+    fn test_template_fake_apply_with_context_variable() {
+        let t = Template::new("template".to_string(), "EN".to_string(), "user_{{index}} at {{path}}").unwrap();
+        let mut context = GenerationContext::new();
+        context.set_record_index(4);
+        context.push_path("username".to_string());
+
+        assert_eq!(t.fake_apply(&mut context).unwrap(), "user_4 at username");
+    }
+
+    #[test]
+    fn test_template_fake_apply_with_undefined_field() {
+        let t = Template::new("template".to_string(), "EN".to_string(), "{{undefined_field}}").unwrap();
+
+        assert!(t.fake_apply(&mut GenerationContext::new()).is_err());
+    }
+
+    #[test]
+    fn test_template_new() {
+        let t = Template::new("template".to_string(), "EN".to_string(), "{{a}} and {{b.c}}").unwrap();
+
+        assert_eq!(t._fake_type, "template");
+        assert_eq!(t.references(), vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_template_new_fail() {
+        assert!(Template::new("template".to_string(), "EN".to_string(), "{{a").is_err(), "Should return an error for an unclosed placeholder");
+        assert!(Template::new("template".to_string(), "EN".to_string(), "{{ }}").is_err(), "Should return an error for an empty placeholder");
+        assert!(Template::new("template".to_string(), "EN".to_string(), "{{a | reverse}}").is_err(), "Should return an error for an undefined filter");
+    }
+
+    #[test]
+    fn test_template_filter() {
+        assert_eq!(TemplateFilter::Slug.apply("  Hello, World!  ".to_string()), "hello-world");
+        assert_eq!(TemplateFilter::Truncate(3).apply("abcdef".to_string()), "abc");
+        assert_eq!(TemplateFilter::parse("truncate( 2 )").unwrap(), TemplateFilter::Truncate(2));
+    }
+}
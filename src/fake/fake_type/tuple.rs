@@ -0,0 +1,136 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
+use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::GenerationContext;
+
+/// `Tuple` is an implementation of `FakeType`. It generates a fixed-shape array where every
+/// element is generated from the definition at the same position, e.g. `[lat, lng]`.
+///
+/// # Attributes
+///
+/// * `FakeType`: This provides `Tuple` with the `fake_apply` and `to_value` methods.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::fake_type::constant::Constant;
+/// # use fake_cli::fake::fake_type::number::digit::Digit;
+/// # use fake_cli::fake::fake_type::params::DigitParams;
+/// # use fake_cli::fake::fake_type::tuple::Tuple;
+/// # use fake_cli::fake::fake_type::FakeElement;
+/// # use fake_cli::{FakeDefinitionElement, FakeType, GenerationContext};
+/// # use serde_json::Value;
+/// // Create a new instance of Tuple with a constant and a digit at each position
+/// let t = Tuple::new("array".to_string(), vec![
+///     FakeDefinitionElement::Constant(Constant::new("constant".to_string(), Value::String("Hello, world!".to_string()))),
+///     FakeDefinitionElement::Digit(Digit::new("digit".to_string(), DigitParams::new("EN")).unwrap()),
+/// ]);
+/// let tuple = t.fake_apply(&mut GenerationContext::new()).unwrap();
+/// println!("Fake tuple: {:?}", tuple);
+/// ```
+#[derive(Debug)]
+pub struct Tuple {
+    _fake_type: String,
+    fake_definition_elements: Vec<FakeDefinitionElement>,
+}
+
+impl FakeType for Tuple {
+    type Response = Result<Vec<Value>>;
+
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        self.fake_definition_elements
+            .iter()
+            .enumerate()
+            .map(|(index, fake_definition_element)| {
+                context.push_path(index.to_string());
+                let value = fake_definition_element.to_value(context);
+                context.pop_path();
+                value
+            })
+            .collect()
+    }
+
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        Ok(Value::Array(self.fake_apply(context)?))
+    }
+}
+
+impl Tuple {
+    pub fn new(_fake_type: String, fake_definition_elements: Vec<FakeDefinitionElement>) -> Self {
+        Self { _fake_type, fake_definition_elements }
+    }
+
+    /// Returns the names referred to by the elements which have to be resolved by an enclosing map.
+    pub fn references(&self) -> Vec<String> {
+        self.fake_definition_elements.iter().flat_map(|e| e.references()).collect()
+    }
+
+    /// Returns the names of the dataset entities the elements sample values from.
+    pub fn entity_references(&self) -> Vec<String> {
+        self.fake_definition_elements.iter().flat_map(|e| e.entity_references()).collect()
+    }
+
+    /// Appends the state of the stateful elements to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        for fake_definition_element in &self.fake_definition_elements {
+            fake_definition_element.save_state(states);
+        }
+    }
+
+    /// Restores the state of the stateful elements from `states`.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        for fake_definition_element in &self.fake_definition_elements {
+            fake_definition_element.restore_state(states);
+        }
+    }
+
+    /// Returns the JSON Schema of the generated arrays, with the schema of every element at its position.
+    pub fn output_schema(&self) -> Value {
+        let items: Vec<Value> = self.fake_definition_elements.iter().map(FakeDefinitionElement::output_schema).collect();
+        json!({ "type": "array", "items": items, "minItems": items.len(), "maxItems": items.len() })
+    }
+}
+
+impl From<Tuple> for FakeDefinitionElement {
+    fn from(value: Tuple) -> Self {
+        FakeDefinitionElement::Tuple(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tuple;
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::fake_definition_element::FakeDefinitionElement;
+    use crate::fake::fake_type::constant::Constant;
+    use crate::fake::fake_type::number::digit::Digit;
+    use crate::fake::fake_type::params::DigitParams;
+    use crate::fake::fake_type::FakeElement;
+    use serde_json::Value;
+
+    #[test]
+    fn test_tuple_fake_apply() {
+        let t = Tuple::new(
+            "array".to_string(),
+            vec![
+                FakeDefinitionElement::Constant(Constant::new("constant".to_string(), Value::String("Hello, world!".to_string()))),
+                FakeDefinitionElement::Digit(Digit::new("digit".to_string(), DigitParams::new("EN")).unwrap()),
+            ],
+        );
+        let tuple_value = t.fake_apply(&mut GenerationContext::new()).unwrap();
+
+        assert_eq!(tuple_value.len(), 2, "Generated tuple should have 2 elements");
+        assert_eq!(tuple_value[0], Value::String("Hello, world!".to_string()), "First element should come from the first definition");
+        assert!(tuple_value[1].is_u64(), "Second element should come from the second definition");
+    }
+
+    #[test]
+    fn test_tuple_new() {
+        let t = Tuple::new("array".to_string(), vec![]);
+
+        assert_eq!(t._fake_type, "array");
+        assert!(t.fake_definition_elements.is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+use std::cell::RefCell;
+use anyhow::{anyhow, Result};
+use indexmap::IndexSet;
+use serde_json::Value;
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
+use crate::fake::fake_type::FakeType;
+use crate::fake::generation_context::GenerationContext;
+
+/// The number of times a unique value is regenerated before giving up.
+pub const MAX_UNIQUE_ATTEMPTS: usize = 100;
+
+/// `Unique` is an implementation of `FakeType`. It wraps another `FakeDefinitionElement` set with `unique: true`,
+/// and makes sure that it never generates the same value twice during a run, across all records.
+///
+/// # Attributes
+///
+/// * `FakeType`: This provides `Unique` with the `fake_apply` and `to_value` methods.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::fake_type::number::digit::Digit;
+/// # use fake_cli::fake::fake_type::params::DigitParams;
+/// # use fake_cli::fake::fake_type::unique::Unique;
+/// # use fake_cli::fake::fake_type::FakeElement;
+/// # use fake_cli::{FakeDefinitionElement, FakeType, GenerationContext};
+/// // Create a new instance of Unique, generating distinct digits
+/// let u = Unique::new("digit".to_string(), Box::new(FakeDefinitionElement::Digit(Digit::new("digit".to_string(), DigitParams::new("EN")).unwrap())));
+/// let digit = u.fake_apply(&mut GenerationContext::new()).unwrap();
+/// println!("Fake unique digit: {}", digit);
+/// ```
+#[derive(Debug)]
+pub struct Unique {
+    _fake_type: String,
+    fake_definition_element: Box<FakeDefinitionElement>,
+    seen: RefCell<IndexSet<String>>,
+}
+
+impl FakeType for Unique {
+    type Response = Result<Value>;
+
+    fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+        for _ in 0..MAX_UNIQUE_ATTEMPTS {
+            let value = self.fake_definition_element.to_value(context)?;
+            if self.seen.borrow_mut().insert(value.to_string()) {
+                return Ok(value);
+            }
+        }
+
+        Err(anyhow!("fake_type: {}, unique values exhausted after {} attempts ({} distinct values generated)", self._fake_type, MAX_UNIQUE_ATTEMPTS, self.seen.borrow().len()))
+    }
+
+    fn to_value(&self, context: &mut GenerationContext) -> Result<Value> {
+        self.fake_apply(context)
+    }
+}
+
+impl Unique {
+    pub fn new(_fake_type: String, fake_definition_element: Box<FakeDefinitionElement>) -> Self {
+        Self { _fake_type, fake_definition_element, seen: RefCell::new(IndexSet::new()) }
+    }
+
+    /// Appends the number of generated values, and the state of the wrapped element, to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        states.push(ElementState::Unique(self.seen.borrow().len()));
+        self.fake_definition_element.save_state(states);
+    }
+
+    /// Forgets the values generated since the state was saved, and restores the state of the wrapped element.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        if let Some(ElementState::Unique(len)) = states.next() {
+            self.seen.borrow_mut().truncate(len);
+        }
+        self.fake_definition_element.restore_state(states);
+    }
+
+    /// Returns the wrapped element.
+    pub fn fake_definition_element(&self) -> &FakeDefinitionElement {
+        &self.fake_definition_element
+    }
+}
+
+impl From<Unique> for FakeDefinitionElement {
+    fn from(value: Unique) -> Self {
+        FakeDefinitionElement::Unique(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Unique;
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use crate::fake::fake_definition_element::FakeDefinitionElement;
+    use crate::fake::fake_type::number::digit::Digit;
+    use crate::fake::fake_type::params::DigitParams;
+    use crate::fake::fake_type::FakeElement;
+    use std::collections::HashSet;
+
+    fn unique_digit() -> Unique {
+        Unique::new("digit".to_string(), Box::new(FakeDefinitionElement::Digit(Digit::new("digit".to_string(), DigitParams::new("EN")).unwrap())))
+    }
+
+    #[test]
+    fn test_unique_fake_apply() {
+        let u = unique_digit();
+        let mut context = GenerationContext::new();
+
+        let digits: HashSet<String> = (0..10).map(|_| u.fake_apply(&mut context).unwrap().to_string()).collect();
+
+        assert_eq!(digits.len(), 10, "Every generated digit should be distinct");
+    }
+
+    #[test]
+    fn test_unique_fake_apply_exhausted() {
+        let u = unique_digit();
+        let mut context = GenerationContext::new();
+        for _ in 0..10 {
+            u.fake_apply(&mut context).unwrap();
+        }
+
+        let error = u.fake_apply(&mut context).unwrap_err();
+        assert!(error.to_string().contains("exhausted after 100 attempts"), "Should return an error when the domain is exhausted");
+    }
+
+    #[test]
+    fn test_unique_new() {
+        let u = unique_digit();
+
+        assert_eq!(u._fake_type, "digit");
+        assert!(u.seen.borrow().is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+use indexmap::IndexMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+
+/// The names of the context variables, which templates can render when no field of that name is in scope.
+const VARIABLES: [&str; 6] = ["index", "record_index", "array_index", "path", "parent_path", "seed"];
+
+/// `GenerationContext` carries the state shared while generating fake data.
+///
+/// It keeps a stack of scopes, one for every `FakeDefinition` and `Map` currently being generated.
+/// Each scope holds the values already generated for the fields of that map, so that elements such as
+/// templates can refer to the values of their sibling and ancestor fields.
+/// When generating a dataset, it also holds the records already generated for each entity, so that
+/// references can sample them. It also tracks the arrays currently being generated, so that elements such as
+/// sequences can tell the arrays apart.
+///
+/// Generators can read where they are generated: the index of the record, the index within the innermost array,
+/// and the path of the element (e.g. `lines.2.quantity`). Every random value is drawn from the random number
+/// generator of the context, so generating with the same seed produces the same data.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::GenerationContext;
+/// # use serde_json::Value;
+/// let mut context = GenerationContext::new();
+/// context.push_scope();
+/// context.insert("first_name".to_string(), Value::String("Taro".to_string()));
+/// assert_eq!(context.lookup(&["first_name"]), Some(&Value::String("Taro".to_string())));
+/// context.pop_scope();
+/// ```
+#[derive(Debug)]
+pub struct GenerationContext {
+    scopes: Vec<serde_json::Map<String, Value>>,
+    entities: IndexMap<String, Vec<Value>>,
+    arrays: Vec<ArrayFrame>,
+    generated_arrays: usize,
+    record_index: usize,
+    path: Vec<String>,
+    seed: u64,
+    rng: StdRng,
+}
+
+/// `ArrayFrame` is an array currently being generated: its id, unique within a run, and the index of the element
+/// currently being generated.
+#[derive(Debug)]
+struct ArrayFrame {
+    id: usize,
+    index: usize,
+}
+
+impl Default for GenerationContext {
+    fn default() -> Self {
+        Self::with_seed(rand::thread_rng().gen())
+    }
+}
+
+impl GenerationContext {
+    /// Creates a new `GenerationContext` with a random seed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `GenerationContext` whose random values are drawn from `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            scopes: Vec::new(),
+            entities: IndexMap::new(),
+            arrays: Vec::new(),
+            generated_arrays: 0,
+            record_index: 0,
+            path: Vec::new(),
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the seed of the random number generator.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the random number generator every random value is drawn from.
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Starts a new scope for the fields of a map.
+    pub fn push_scope(&mut self) {
+        self.scopes.push(serde_json::Map::new());
+    }
+
+    /// Ends the innermost scope, discarding the values generated in it.
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Records the generated value of a field in the innermost scope.
+    pub fn insert(&mut self, key: String, value: Value) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(key, value);
+        }
+    }
+
+    /// Looks up a generated value by its path, e.g. `["address", "city"]`.
+    ///
+    /// The first segment is searched from the innermost scope to the outermost one, the remaining
+    /// segments walk into the found value by object key or array index.
+    pub fn lookup<S: AsRef<str>>(&self, path: &[S]) -> Option<&Value> {
+        let (first, rest) = path.split_first()?;
+        let value = self.scopes.iter().rev().find_map(|scope| scope.get(first.as_ref()))?;
+
+        value_at(value, rest)
+    }
+
+    /// Records the generated records of an entity of a dataset.
+    pub fn insert_entity(&mut self, name: String, records: Vec<Value>) {
+        self.entities.insert(name, records);
+    }
+
+    /// Returns the generated records of an entity of a dataset.
+    pub fn entity(&self, name: &str) -> Option<&Vec<Value>> {
+        self.entities.get(name)
+    }
+
+    /// Starts generating an array. Every array generated during a run gets its own id.
+    pub fn push_array(&mut self) {
+        self.generated_arrays += 1;
+        self.arrays.push(ArrayFrame { id: self.generated_arrays, index: 0 });
+    }
+
+    /// Ends generating the innermost array.
+    pub fn pop_array(&mut self) {
+        self.arrays.pop();
+    }
+
+    /// Returns the id of the innermost array currently being generated.
+    pub fn current_array(&self) -> Option<usize> {
+        self.arrays.last().map(|array| array.id)
+    }
+
+    /// Records the index of the element of the innermost array currently being generated.
+    pub fn set_array_index(&mut self, index: usize) {
+        if let Some(array) = self.arrays.last_mut() {
+            array.index = index;
+        }
+    }
+
+    /// Returns the index of the element of the innermost array currently being generated.
+    pub fn array_index(&self) -> Option<usize> {
+        self.arrays.last().map(|array| array.index)
+    }
+
+    /// Records the index of the record currently being generated, e.g. within `--count` or a dataset entity.
+    pub fn set_record_index(&mut self, index: usize) {
+        self.record_index = index;
+    }
+
+    /// Returns the index of the record currently being generated.
+    pub fn record_index(&self) -> usize {
+        self.record_index
+    }
+
+    /// Starts generating the child `segment` (a field name or an array index) of the current element.
+    pub fn push_path(&mut self, segment: String) {
+        self.path.push(segment);
+    }
+
+    /// Ends generating the innermost child.
+    pub fn pop_path(&mut self) {
+        self.path.pop();
+    }
+
+    /// Returns the path of the element currently being generated, e.g. `["lines", "2", "quantity"]`.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Returns the path of the parent of the element currently being generated.
+    pub fn parent_path(&self) -> &[String] {
+        &self.path[..self.path.len().saturating_sub(1)]
+    }
+
+    /// Returns whether `name` is a context variable.
+    pub fn is_variable(name: &str) -> bool {
+        VARIABLES.contains(&name)
+    }
+
+    /// Returns the value of a context variable. `index` is the index within the innermost array,
+    /// or the index of the record outside of arrays.
+    pub fn variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "index" => Some(Value::from(self.array_index().unwrap_or(self.record_index()))),
+            "record_index" => Some(Value::from(self.record_index())),
+            "array_index" => Some(self.array_index().map(Value::from).unwrap_or(Value::Null)),
+            "path" => Some(Value::String(self.path().join("."))),
+            "parent_path" => Some(Value::String(self.parent_path().join("."))),
+            "seed" => Some(Value::from(self.seed())),
+            _ => None,
+        }
+    }
+}
+
+/// Walks into `value` along `path`, by object key or array index.
+pub fn value_at<'a, S: AsRef<str>>(value: &'a Value, path: &[S]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| match value {
+        Value::Object(map) => map.get(segment.as_ref()),
+        Value::Array(array) => array.get(segment.as_ref().parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GenerationContext;
+    use rand::Rng;
+    use serde_json::json;
+
+    #[test]
+    fn test_generation_context_lookup() {
+        let mut context = GenerationContext::new();
+        context.push_scope();
+        context.insert("name".to_string(), json!("outer"));
+        context.insert("address".to_string(), json!({ "city": "Tokyo", "lines": ["a", "b"] }));
+        context.push_scope();
+        context.insert("name".to_string(), json!("inner"));
+
+        assert_eq!(context.lookup(&["name"]), Some(&json!("inner")), "The innermost scope should win");
+        assert_eq!(context.lookup(&["address", "city"]), Some(&json!("Tokyo")), "Ancestor values should be visible");
+        assert_eq!(context.lookup(&["address", "lines", "1"]), Some(&json!("b")), "Array elements should be addressable by index");
+        assert_eq!(context.lookup(&["missing"]), None);
+
+        context.pop_scope();
+        assert_eq!(context.lookup(&["name"]), Some(&json!("outer")), "Popped values should be discarded");
+    }
+
+    #[test]
+    fn test_generation_context_entity() {
+        let mut context = GenerationContext::new();
+        context.insert_entity("users".to_string(), vec![json!({ "id": 1 })]);
+
+        assert_eq!(context.entity("users"), Some(&vec![json!({ "id": 1 })]));
+        assert_eq!(context.entity("orders"), None);
+    }
+
+    #[test]
+    fn test_generation_context_current_array() {
+        let mut context = GenerationContext::new();
+        assert_eq!(context.current_array(), None);
+
+        context.push_array();
+        let outer = context.current_array();
+        context.push_array();
+        assert_ne!(context.current_array(), outer, "Nested arrays should have their own id");
+        context.pop_array();
+        assert_eq!(context.current_array(), outer);
+        context.pop_array();
+
+        context.push_array();
+        assert_ne!(context.current_array(), outer, "Every generated array should have its own id");
+    }
+
+    #[test]
+    fn test_generation_context_variable() {
+        let mut context = GenerationContext::with_seed(42);
+        context.set_record_index(3);
+        context.push_path("lines".to_string());
+        assert_eq!(context.variable("index"), Some(json!(3)), "index should be the record index outside of arrays");
+
+        context.push_array();
+        context.set_array_index(1);
+        context.push_path("1".to_string());
+        context.push_path("quantity".to_string());
+
+        assert_eq!(context.variable("index"), Some(json!(1)), "index should be the innermost array index");
+        assert_eq!(context.variable("record_index"), Some(json!(3)));
+        assert_eq!(context.variable("path"), Some(json!("lines.1.quantity")));
+        assert_eq!(context.variable("parent_path"), Some(json!("lines.1")));
+        assert_eq!(context.variable("seed"), Some(json!(42)));
+        assert_eq!(context.variable("undefined"), None);
+    }
+
+    #[test]
+    fn test_generation_context_with_seed() {
+        let mut a = GenerationContext::with_seed(7);
+        let mut b = GenerationContext::with_seed(7);
+
+        assert_eq!(a.rng().gen::<u64>(), b.rng().gen::<u64>(), "The same seed should draw the same values");
+    }
+}
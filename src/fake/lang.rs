@@ -1,5 +1,8 @@
 use fake::locales::{AR_SA, EN, FR_FR, JA_JP, PT_BR, ZH_CN, ZH_TW};
 
+/// The codes of the supported languages, set as the `lang` of a fake type.
+pub const LOCALES: [&str; 7] = ["EN", "JA_JP", "AR_SA", "FR_FR", "PT_BR", "ZH_CN", "ZH_TW"];
+
 /// `Language` enum represents a set of supported languages.
 /// Each variant of the enum corresponds to a different language.
 pub enum Language {
@@ -0,0 +1,422 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use anyhow::{anyhow, Result};
+use indexmap::{IndexMap, IndexSet};
+use serde_json::{json, Value};
+use crate::fake::dependency::{evaluation_order, generate_fields, unresolved_references};
+use crate::fake::expression::{Expression, ValueType};
+use crate::fake::fake_definition_element::{ElementState, FakeDefinitionElement};
+use crate::fake::generation_context::GenerationContext;
+
+/// The number of times a record is regenerated before giving up, when it repeats a `unique_by` combination
+/// or fails a constraint.
+pub const MAX_RECORD_ATTEMPTS: usize = 100;
+
+/// `Record` holds the named fields shared by a `FakeDefinition` and a `Map`, together with the record-level options.
+///
+/// The fields are generated in dependency order and returned in definition order. With `unique_by`, each listed
+/// combination of fields (e.g. `["first_name", "last_name"]`) is unique across all records generated during a run.
+/// With `constraints`, every boolean expression (e.g. `end_date > start_date`) holds for every record.
+/// Records breaking either are regenerated, after restoring the state of the stateful fields (e.g. the counter of a
+/// `sequence`) so that a rejected record leaves no trace.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::expression::Expression;
+/// # use fake_cli::fake::record::{Record, RecordOptions};
+/// # use fake_cli::{FakeDefinitionElement, GenerationContext};
+/// # use indexmap::IndexMap;
+/// # use serde_json::json;
+/// # let digit = json!({ "fake_type": "digit", "lang": "EN" });
+/// # let fields: IndexMap<String, FakeDefinitionElement> = ["min_price", "max_price"]
+/// #     .into_iter()
+/// #     .map(|name| (name.to_string(), FakeDefinitionElement::generate(&digit).unwrap()))
+/// #     .collect();
+/// let options = RecordOptions { constraints: vec![Expression::parse("min_price <= max_price").unwrap()], ..Default::default() };
+/// let record = Record::new(fields, options).unwrap();
+/// let value = record.generate(&mut GenerationContext::new()).unwrap();
+/// println!("Fake record: {:?}", value);
+/// ```
+#[derive(Debug)]
+pub struct Record {
+    fields: IndexMap<String, FakeDefinitionElement>,
+    evaluation_order: Vec<usize>,
+    options: RecordOptions,
+    seen: RefCell<Vec<IndexSet<String>>>,
+}
+
+/// `RecordOptions` are the record-level options of a `FakeDefinition` or a `Map`.
+///
+/// * `unique_by`: Lists of fields whose combined values are unique across all records.
+/// * `constraints`: Boolean expressions every record satisfies.
+#[derive(Debug, Default)]
+pub struct RecordOptions {
+    pub unique_by: Vec<Vec<String>>,
+    pub constraints: Vec<Expression>,
+}
+
+impl Record {
+    /// Creates a new `Record`. Returns an error when the fields refer to each other circularly,
+    /// when an `expr` field or a constraint is applied to fields of the wrong type, when a constraint is not
+    /// a boolean expression, or when `unique_by` lists an undefined field.
+    pub fn new(fields: IndexMap<String, FakeDefinitionElement>, options: RecordOptions) -> Result<Self> {
+        let evaluation_order = evaluation_order(&fields)?;
+        let types = Record::field_types(&fields, &evaluation_order)?;
+
+        for key in options.unique_by.iter().flatten() {
+            if !fields.contains_key(key) {
+                Err(anyhow!("unique_by: {} is an undefined field", key))?
+            }
+        }
+
+        for constraint in &options.constraints {
+            let constraint_type = constraint.infer(&|name| types.get(name).copied()).map_err(|e| anyhow!("constraints: {}", e))?;
+            if !constraint_type.is(ValueType::Boolean) {
+                Err(anyhow!("constraints: \"{}\" should be a boolean expression but is a {}", constraint.source(), constraint_type))?
+            }
+        }
+
+        let seen = RefCell::new(vec![IndexSet::new(); options.unique_by.len()]);
+        Ok(Self { fields, evaluation_order, options, seen })
+    }
+
+    /// Infers the type of every field in evaluation order, so that the type of an `expr` field is known
+    /// to the `expr` fields referring to it. Returns an error when an `expr` field or a `when` condition has a type error.
+    fn field_types<'a>(fields: &'a IndexMap<String, FakeDefinitionElement>, evaluation_order: &[usize]) -> Result<HashMap<&'a str, ValueType>> {
+        let mut types = HashMap::new();
+        for &index in evaluation_order {
+            let (key, field) = fields.get_index(index).ok_or(anyhow!("undefined field index {}", index))?;
+            if let Some(conditional) = field.conditional() {
+                conditional.check(&|name| types.get(name).copied()).map_err(|e| anyhow!("{}: {}", key, e))?;
+            }
+            let field_type = match field.expr() {
+                Some(expr) => expr.infer(&|name| types.get(name).copied()).map_err(|e| anyhow!("{}: {}", key, e))?,
+                None => field.value_type(),
+            };
+            types.insert(key.as_str(), field_type);
+        }
+
+        Ok(types)
+    }
+
+    /// Returns the names referred to by the fields and constraints which have to be resolved by an enclosing map.
+    pub fn references(&self) -> Vec<String> {
+        let mut references = unresolved_references(&self.fields);
+        for reference in self.options.constraints.iter().flat_map(Expression::references) {
+            if !self.fields.contains_key(reference.as_str()) && !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+
+        references
+    }
+
+    /// Returns the names of the dataset entities the fields sample values from.
+    pub fn entity_references(&self) -> Vec<String> {
+        self.fields.values().flat_map(|e| e.entity_references()).collect()
+    }
+
+    /// Appends the number of seen `unique_by` combinations, and the state of the stateful fields, to `states`.
+    pub fn save_state(&self, states: &mut Vec<ElementState>) {
+        states.push(ElementState::Record(self.seen.borrow().iter().map(IndexSet::len).collect()));
+        for field in self.fields.values() {
+            field.save_state(states);
+        }
+    }
+
+    /// Forgets the `unique_by` combinations seen since the state was saved, and restores the state of the fields.
+    pub fn restore_state(&self, states: &mut dyn Iterator<Item = ElementState>) {
+        if let Some(ElementState::Record(lens)) = states.next() {
+            for (seen, len) in self.seen.borrow_mut().iter_mut().zip(lens) {
+                seen.truncate(len);
+            }
+        }
+        for field in self.fields.values() {
+            field.restore_state(states);
+        }
+    }
+
+    /// Returns the JSON Schema of the generated records, where the fields omitted by a `when` condition are optional.
+    /// The type of an `expr` field is inferred from the types of the fields it refers to.
+    pub fn output_schema(&self) -> Value {
+        let types = Record::field_types(&self.fields, &self.evaluation_order).unwrap_or_default();
+
+        let mut properties = serde_json::Map::new();
+        let mut required = Vec::new();
+        for (key, field) in &self.fields {
+            let (schema, always) = match field.expr() {
+                Some(expr) => (expr.infer(&|name| types.get(name).copied()).unwrap_or(ValueType::Any).output_schema(), true),
+                None => field.field_output_schema(),
+            };
+            properties.insert(key.clone(), schema);
+            if always {
+                required.push(key.as_str());
+            }
+        }
+
+        json!({ "type": "object", "properties": properties, "required": required, "additionalProperties": false })
+    }
+
+    /// Generates the fields, regenerating the record while it repeats a `unique_by` combination or fails a constraint.
+    pub fn generate(&self, context: &mut GenerationContext) -> Result<serde_json::Map<String, Value>> {
+        if self.options.unique_by.is_empty() && self.options.constraints.is_empty() {
+            return generate_fields(&self.fields, &self.evaluation_order, context);
+        }
+
+        let mut states = Vec::new();
+        self.save_state(&mut states);
+
+        let mut failure = String::new();
+        for attempt in 0..MAX_RECORD_ATTEMPTS {
+            if attempt > 0 {
+                self.restore_state(&mut states.iter().cloned());
+            }
+
+            let record = generate_fields(&self.fields, &self.evaluation_order, context)?;
+
+            if let Some(constraint) = self.failing_constraint(&record, context)? {
+                failure = format!("constraint \"{}\"", constraint.source());
+                continue;
+            }
+
+            let combinations: Vec<String> = self
+                .options
+                .unique_by
+                .iter()
+                .map(|keys| Value::Array(keys.iter().map(|key| record.get(key).cloned().unwrap_or(Value::Null)).collect()).to_string())
+                .collect();
+
+            let mut seen = self.seen.borrow_mut();
+            match combinations.iter().zip(seen.iter()).position(|(combination, seen)| seen.contains(combination)) {
+                Some(index) => failure = format!("unique_by ({})", self.options.unique_by[index].join(", ")),
+                None => {
+                    for (combination, seen) in combinations.into_iter().zip(seen.iter_mut()) {
+                        seen.insert(combination);
+                    }
+                    return Ok(record);
+                }
+            }
+        }
+
+        Err(anyhow!("{} could not be satisfied after {} attempts", failure, MAX_RECORD_ATTEMPTS))
+    }
+
+    /// Returns the first constraint `record` fails. The constraints see the fields of the record as well as
+    /// the fields of the enclosing maps.
+    fn failing_constraint(&self, record: &serde_json::Map<String, Value>, context: &mut GenerationContext) -> Result<Option<&Expression>> {
+        context.push_scope();
+        for (key, value) in record {
+            context.insert(key.clone(), value.clone());
+        }
+        let results = self
+            .options
+            .constraints
+            .iter()
+            .map(|constraint| constraint.evaluate(context).map(|value| (constraint, value)))
+            .collect::<Result<Vec<(&Expression, Value)>>>();
+        context.pop_scope();
+
+        for (constraint, value) in results? {
+            match value.as_bool() {
+                Some(true) => (),
+                Some(false) => return Ok(Some(constraint)),
+                None => Err(anyhow!("constraints: \"{}\" should be a boolean but got {}", constraint.source(), value))?,
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl RecordOptions {
+    /// Returns whether `key` holds a record option rather than the definition of a field named `key`.
+    /// A field definition is always an object, so options are never objects.
+    pub fn is_option(key: &str, value: &Value) -> bool {
+        ["unique_by", "constraints"].contains(&key) && !value.is_object()
+    }
+
+    /// Parses the record options of a `FakeDefinition` or a `Map` from its settings.
+    pub fn from_json(setting: &serde_json::Map<String, Value>) -> Result<Self> {
+        let mut options = RecordOptions::default();
+        for (key, value) in setting.iter().filter(|(key, value)| RecordOptions::is_option(key, value)) {
+            match key.as_str() {
+                "unique_by" => options.unique_by = RecordOptions::parse_unique_by(value)?,
+                _ => options.constraints = RecordOptions::parse_constraints(value)?,
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Parses the `unique_by` option: a list of field name lists.
+    fn parse_unique_by(unique_by_value: &Value) -> Result<Vec<Vec<String>>> {
+        let error = || anyhow!("unique_by should be a list of field name lists, e.g. [[\"first_name\", \"last_name\"]]");
+
+        unique_by_value
+            .as_array()
+            .ok_or_else(error)?
+            .iter()
+            .map(|keys| {
+                keys.as_array()
+                    .filter(|keys| !keys.is_empty())
+                    .ok_or_else(error)?
+                    .iter()
+                    .map(|key| key.as_str().map(str::to_string).ok_or_else(error))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Parses the `constraints` option: a list of boolean expressions.
+    fn parse_constraints(constraints_value: &Value) -> Result<Vec<Expression>> {
+        let error = || anyhow!("constraints should be a list of expressions, e.g. [\"end_date > start_date\"]");
+
+        constraints_value
+            .as_array()
+            .ok_or_else(error)?
+            .iter()
+            .map(|constraint| Expression::parse(constraint.as_str().ok_or_else(error)?).map_err(|e| anyhow!("constraints: {}", e)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Record, RecordOptions};
+    use crate::fake::expression::Expression;
+    use crate::fake::fake_definition_element::FakeDefinitionElement;
+    use crate::fake::generation_context::GenerationContext;
+    use indexmap::IndexMap;
+    use serde_json::{json, Value};
+    use std::collections::HashSet;
+
+    fn fields(json: Value) -> IndexMap<String, FakeDefinitionElement> {
+        json.as_object()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), FakeDefinitionElement::generate(v).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_record_generate_with_unique_by() {
+        let fields = fields(json!({
+            "a": { "fake_type": "boolean", "lang": "EN", "ratio": 50 },
+            "b": { "fake_type": "boolean", "lang": "EN", "ratio": 50 }
+        }));
+        let record = Record::new(fields, RecordOptions { unique_by: vec![vec!["a".to_string(), "b".to_string()]], ..Default::default() }).unwrap();
+        let mut context = GenerationContext::new();
+
+        let combinations: HashSet<String> = (0..4).map(|_| Value::Object(record.generate(&mut context).unwrap()).to_string()).collect();
+
+        assert_eq!(combinations.len(), 4, "Every combination should be generated once");
+        assert!(record.generate(&mut context).is_err(), "Should return an error when the combinations are exhausted");
+    }
+
+    #[test]
+    fn test_record_generate_with_sequence_and_constraints() {
+        let fields = fields(json!({
+            "id": { "fake_type": "sequence" },
+            "a": { "fake_type": "digit", "lang": "EN" },
+            "b": { "fake_type": "digit", "lang": "EN" },
+            "tags": { "fake_type": "array", "count": 1, "tag": { "fake_type": "digit", "lang": "EN", "unique": true } }
+        }));
+        let options = RecordOptions { constraints: vec![Expression::parse("a < b").unwrap()], ..Default::default() };
+        let record = Record::new(fields, options).unwrap();
+        let mut context = GenerationContext::new();
+
+        let records: Vec<_> = (0..5).map(|_| record.generate(&mut context).unwrap()).collect();
+
+        let ids: Vec<&Value> = records.iter().map(|record| &record["id"]).collect();
+        assert_eq!(ids, vec![&json!(1), &json!(2), &json!(3), &json!(4), &json!(5)], "Rejected records should not advance the sequence");
+        assert!(records.iter().all(|record| record["a"].as_u64() < record["b"].as_u64()));
+    }
+
+    #[test]
+    fn test_record_new_with_expr() {
+        let valid = fields(json!({
+            "price": { "fake_type": "digit", "lang": "EN" },
+            "total": { "fake_type": "expr", "expr": "price * 2" },
+            "label": { "fake_type": "expr", "expr": "'total: ' + total" }
+        }));
+        assert!(Record::new(valid, RecordOptions::default()).is_ok());
+
+        let invalid = fields(json!({
+            "name": { "fake_type": "name", "lang": "EN" },
+            "upper_name": { "fake_type": "expr", "expr": "upper(name)" },
+            "double": { "fake_type": "expr", "expr": "upper_name * 2" }
+        }));
+        let error = Record::new(invalid, RecordOptions::default()).unwrap_err();
+        assert!(error.to_string().contains("double"), "Should name the field with the type error: {}", error);
+    }
+
+    #[test]
+    fn test_record_new_with_undefined_unique_by() {
+        let fields = fields(json!({ "a": { "fake_type": "digit", "lang": "EN" } }));
+
+        assert!(Record::new(fields, RecordOptions { unique_by: vec![vec!["b".to_string()]], ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn test_record_generate_with_constraints() {
+        let fields = fields(json!({
+            "min_price": { "fake_type": "digit", "lang": "EN" },
+            "max_price": { "fake_type": "digit", "lang": "EN" }
+        }));
+        let options = RecordOptions { constraints: vec![Expression::parse("min_price <= max_price").unwrap()], ..Default::default() };
+        let record = Record::new(fields, options).unwrap();
+        let mut context = GenerationContext::new();
+
+        for _ in 0..20 {
+            let value = record.generate(&mut context).unwrap();
+            assert!(value["min_price"].as_u64() <= value["max_price"].as_u64(), "Every record should satisfy the constraint: {:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_record_generate_with_unsatisfiable_constraint() {
+        let fields = fields(json!({ "a": { "fake_type": "boolean", "lang": "EN", "ratio": 50 } }));
+        let options = RecordOptions { constraints: vec![Expression::parse("a and not a").unwrap()], ..Default::default() };
+        let record = Record::new(fields, options).unwrap();
+
+        let error = record.generate(&mut GenerationContext::new()).unwrap_err();
+
+        assert!(error.to_string().contains("a and not a"), "Should name the unsatisfied constraint: {}", error);
+    }
+
+    #[test]
+    fn test_record_new_with_invalid_constraint() {
+        let fields = || fields(json!({ "name": { "fake_type": "name", "lang": "EN" } }));
+
+        let not_boolean = RecordOptions { constraints: vec![Expression::parse("upper(name)").unwrap()], ..Default::default() };
+        assert!(Record::new(fields(), not_boolean).is_err(), "Should return an error for a constraint which is not boolean");
+
+        let type_error = RecordOptions { constraints: vec![Expression::parse("name * 2 > 1").unwrap()], ..Default::default() };
+        assert!(Record::new(fields(), type_error).is_err(), "Should return an error for a constraint with a type error");
+    }
+
+    #[test]
+    fn test_record_references_with_constraints() {
+        let fields = fields(json!({ "max_price": { "fake_type": "digit", "lang": "EN" } }));
+        let options = RecordOptions { constraints: vec![Expression::parse("max_price >= min_price").unwrap()], ..Default::default() };
+        let record = Record::new(fields, options).unwrap();
+
+        assert_eq!(record.references(), vec!["min_price".to_string()], "Only the names outside of the record should be unresolved");
+    }
+
+    #[test]
+    fn test_record_options_from_json() {
+        let setting = json!({ "unique_by": [["a", "b"], ["c"]], "constraints": ["a < c"], "a": {} });
+        let options = RecordOptions::from_json(setting.as_object().unwrap()).unwrap();
+        assert_eq!(options.unique_by, vec![vec!["a".to_string(), "b".to_string()], vec!["c".to_string()]]);
+        assert_eq!(options.constraints.len(), 1);
+
+        assert!(RecordOptions::is_option("constraints", &json!([])));
+        assert!(!RecordOptions::is_option("constraints", &json!({ "fake_type": "name" })), "An object should be a field definition");
+        assert!(RecordOptions::parse_unique_by(&json!(["a", "b"])).is_err());
+        assert!(RecordOptions::parse_unique_by(&json!([[]])).is_err());
+        assert!(RecordOptions::parse_constraints(&json!("a < b")).is_err());
+        assert!(RecordOptions::parse_constraints(&json!(["a <"])).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::fake_type::FakeElement;
+use crate::fake::fake_type::params::{ArrayParams, ConditionalParams, ConstantParams, DescribeParams, ExprParams, ReferenceParams, SequenceParams, TemplateParams};
+use crate::fake::lang::LOCALES;
+use crate::fake::type_info::{OtherKeys, ParamInfo, ParamType, TypeInfo};
+use crate::fake::fake_type::boolean::boolean::Boolean;
+use crate::fake::fake_type::internet::domain_suffix::DomainSuffix;
+use crate::fake::fake_type::internet::free_email::FreeEmail;
+use crate::fake::fake_type::internet::free_email_provider::FreeEmailProvider;
+use crate::fake::fake_type::internet::ip::IP;
+use crate::fake::fake_type::internet::ip_v4::IPv4;
+use crate::fake::fake_type::internet::ip_v6::IPv6;
+use crate::fake::fake_type::internet::mac_address::MACAddress;
+use crate::fake::fake_type::internet::password::Password;
+use crate::fake::fake_type::internet::safe_email::SafeEmail;
+use crate::fake::fake_type::internet::user_agent::UserAgent;
+use crate::fake::fake_type::internet::username::Username;
+use crate::fake::fake_type::lorem::paragraph::Paragraph;
+use crate::fake::fake_type::lorem::paragraphs::Paragraphs;
+use crate::fake::fake_type::lorem::sentence::Sentence;
+use crate::fake::fake_type::lorem::sentences::Sentences;
+use crate::fake::fake_type::lorem::word::Word;
+use crate::fake::fake_type::lorem::words::Words;
+use crate::fake::fake_type::name::first_name::FirstName;
+use crate::fake::fake_type::name::last_name::LastName;
+use crate::fake::fake_type::name::name::Name;
+use crate::fake::fake_type::name::name_with_title::NameWithTitle;
+use crate::fake::fake_type::name::suffix::Suffix;
+use crate::fake::fake_type::name::title::Title;
+use crate::fake::fake_type::number::digit::Digit;
+use crate::fake::fake_type::number::number_with_format::NumberWithFormat;
+
+/// A `Constructor` builds the `FakeDefinitionElement` of a fake type from its settings in a definition.
+///
+/// It receives the settings object, the `fake_type` name and the `Registry` it is registered in, which it uses to
+/// parse nested definition elements.
+pub type Constructor = Arc<dyn Fn(&serde_json::Map<String, Value>, &str, &Registry) -> Result<FakeDefinitionElement> + Send + Sync>;
+
+/// `Registry` maps every `fake_type` name to the `Constructor` of its `FakeDefinitionElement` and its `TypeInfo`.
+///
+/// The built-in fake types are registered in `Registry::builtin`. A downstream crate adds its own fake types by
+/// registering a constructor returning a `Custom` element on a copy of it, and parses definitions with that registry.
+/// A fake type registered with the name of an existing one replaces it.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::{Custom, FakeDefinition, FakeType, GenerationContext, Registry};
+/// # use anyhow::Result;
+/// # use serde_json::{json, Value};
+/// #
+/// # #[derive(Debug)]
+/// # struct Sku { prefix: String }
+/// #
+/// # impl Sku {
+/// #     fn new(prefix: &str) -> Self { Self { prefix: prefix.to_string() } }
+/// # }
+/// #
+/// # impl FakeType for Sku {
+/// #     type Response = String;
+/// #     fn fake_apply(&self, _: &mut GenerationContext) -> String { format!("{}-0001", self.prefix) }
+/// #     fn to_value(&self, context: &mut GenerationContext) -> Result<Value> { Ok(Value::String(self.fake_apply(context))) }
+/// # }
+/// #
+/// let mut registry = Registry::with_builtin_types();
+/// registry.register("sku", |setting, fake_type, _registry| {
+///     let prefix = setting.get("prefix").and_then(Value::as_str).unwrap_or("SKU");
+///     Ok(Custom::new(fake_type.to_string(), Sku::new(prefix)).into())
+/// });
+/// let fd = FakeDefinition::from_json_with_registry(&json!({ "sku": { "fake_type": "sku", "prefix": "ABC" } }), &registry).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct Registry {
+    types: IndexMap<String, RegisteredType>,
+}
+
+/// A fake type registered in a `Registry`.
+#[derive(Clone)]
+struct RegisteredType {
+    info: TypeInfo,
+    constructor: Constructor,
+}
+
+impl Registry {
+    /// Creates an empty `Registry`, without any fake type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the `Registry` of the built-in fake types, which definitions are parsed with by default.
+    pub fn builtin() -> &'static Registry {
+        static BUILTIN: OnceLock<Registry> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut registry = Registry::new();
+            registry.register_builtin_types();
+            registry
+        })
+    }
+
+    /// Creates a `Registry` with the built-in fake types, to register more fake types on.
+    pub fn with_builtin_types() -> Self {
+        Registry::builtin().clone()
+    }
+
+    /// Registers `constructor` for the fake type `fake_type`, replacing the fake type already registered with that name.
+    /// The fake type is described as a `custom` fake type whose keys are not described; use `register_type` to describe it.
+    pub fn register<F>(&mut self, fake_type: impl Into<String>, constructor: F) -> &mut Self
+    where
+        F: Fn(&serde_json::Map<String, Value>, &str, &Registry) -> Result<FakeDefinitionElement> + Send + Sync + 'static,
+    {
+        self.register_type(TypeInfo::new(fake_type, "custom", "A fake type registered by the application").other_keys(OtherKeys::Any), constructor)
+    }
+
+    /// Registers `constructor` for the fake type described by `info`, replacing the fake type already registered
+    /// with that name.
+    pub fn register_type<F>(&mut self, info: TypeInfo, constructor: F) -> &mut Self
+    where
+        F: Fn(&serde_json::Map<String, Value>, &str, &Registry) -> Result<FakeDefinitionElement> + Send + Sync + 'static,
+    {
+        self.types.insert(info.name.clone(), RegisteredType { info, constructor: Arc::new(constructor) });
+        self
+    }
+
+    /// Returns whether a fake type is registered with the name `fake_type`.
+    pub fn contains(&self, fake_type: &str) -> bool {
+        self.types.contains_key(fake_type)
+    }
+
+    /// Returns the names of the registered fake types, in their registration order.
+    pub fn fake_types(&self) -> impl Iterator<Item = &str> {
+        self.types.keys().map(String::as_str)
+    }
+
+    /// Returns the `TypeInfo` of every registered fake type, in their registration order.
+    pub fn type_infos(&self) -> impl Iterator<Item = &TypeInfo> {
+        self.types.values().map(|registered| &registered.info)
+    }
+
+    /// Returns the `TypeInfo` of the fake type `fake_type`, if it is registered.
+    pub fn type_info(&self, fake_type: &str) -> Option<&TypeInfo> {
+        self.types.get(fake_type).map(|registered| &registered.info)
+    }
+
+    /// Builds the `FakeDefinitionElement` of `fake_type` from its settings.
+    /// Returns an error when no fake type is registered with that name.
+    pub fn construct(&self, setting: &serde_json::Map<String, Value>, fake_type: &str) -> Result<FakeDefinitionElement> {
+        let registered = self.types.get(fake_type).ok_or(anyhow!("{} is missing fake_type", fake_type))?;
+        (registered.constructor)(setting, fake_type, self)
+    }
+
+    /// Registers the fake type `T`, built from its `Params`, which also describe its parameters.
+    /// Such a fake type generates its values in every supported language.
+    fn register_element<T>(&mut self, info: TypeInfo) -> &mut Self
+    where
+        T: FakeElement + Into<FakeDefinitionElement> + 'static,
+    {
+        let info = info.params(T::Params::describe()).locales(&LOCALES);
+        self.register_type(info, |setting, fake_type, _| FakeDefinitionElement::generate_element::<T>(setting, fake_type))
+    }
+
+    fn register_builtin_types(&mut self) {
+        // Lorem
+        self.register_element::<Word>(TypeInfo::new("word", "lorem", "A lorem ipsum word"));
+        self.register_element::<Words>(TypeInfo::new("words", "lorem", "An array of lorem ipsum words").example(json!({ "fake_type": "words", "min": 2, "max": 4 })));
+        self.register_element::<Sentence>(TypeInfo::new("sentence", "lorem", "A lorem ipsum sentence of min to max words").example(json!({ "fake_type": "sentence", "min": 3, "max": 6 })));
+        self.register_element::<Sentences>(TypeInfo::new("sentences", "lorem", "An array of min to max lorem ipsum sentences").example(json!({ "fake_type": "sentences", "min": 1, "max": 3 })));
+        self.register_element::<Paragraph>(TypeInfo::new("paragraph", "lorem", "A lorem ipsum paragraph of min to max sentences").example(json!({ "fake_type": "paragraph", "min": 1, "max": 3 })));
+        self.register_element::<Paragraphs>(TypeInfo::new("paragraphs", "lorem", "An array of min to max lorem ipsum paragraphs").example(json!({ "fake_type": "paragraphs", "min": 1, "max": 3 })));
+
+        // Name
+        self.register_element::<FirstName>(TypeInfo::new("first_name", "name", "A first name"));
+        self.register_element::<LastName>(TypeInfo::new("last_name", "name", "A last name"));
+        self.register_element::<Title>(TypeInfo::new("title", "name", "A title such as Dr."));
+        self.register_element::<Suffix>(TypeInfo::new("suffix", "name", "A name suffix such as Jr."));
+        self.register_element::<Name>(TypeInfo::new("name", "name", "A full name"));
+        self.register_element::<NameWithTitle>(TypeInfo::new("name_with_title", "name", "A full name with a title"));
+
+        // Number
+        self.register_element::<Digit>(TypeInfo::new("digit", "number", "A number from 0 to 9"));
+        self.register_element::<NumberWithFormat>(TypeInfo::new("number_with_format", "number", "A string of digits in a format").example(json!({ "fake_type": "number_with_format", "format": "###-####" })));
+
+        // Boolean
+        self.register_element::<Boolean>(TypeInfo::new("boolean", "boolean", "true with the given ratio, false otherwise"));
+
+        // Internet
+        self.register_element::<FreeEmailProvider>(TypeInfo::new("free_email_provider", "internet", "The domain of a free email provider"));
+        self.register_element::<DomainSuffix>(TypeInfo::new("domain_suffix", "internet", "A domain suffix such as com"));
+        self.register_element::<FreeEmail>(TypeInfo::new("free_email", "internet", "An email address at a free email provider"));
+        self.register_element::<SafeEmail>(TypeInfo::new("safe_email", "internet", "An email address at a reserved example domain"));
+        self.register_element::<Username>(TypeInfo::new("username", "internet", "A username"));
+        self.register_element::<Password>(TypeInfo::new("password", "internet", "A password of min to max characters").example(json!({ "fake_type": "password", "min": 8, "max": 16 })));
+        self.register_element::<IPv4>(TypeInfo::new("ip_v4", "internet", "An IPv4 address"));
+        self.register_element::<IPv6>(TypeInfo::new("ip_v6", "internet", "An IPv6 address"));
+        self.register_element::<IP>(TypeInfo::new("ip", "internet", "An IPv4 or IPv6 address"));
+        self.register_element::<MACAddress>(TypeInfo::new("mac_address", "internet", "A MAC address"));
+        self.register_element::<UserAgent>(TypeInfo::new("user_agent", "internet", "A browser user agent"));
+
+        // FakeCliOriginal
+        self.register_type(
+            TypeInfo::new("array", "structure", "An array of generated elements. The element is the only other key, or items")
+                .params(ArrayParams::describe())
+                .other_keys(OtherKeys::Definitions)
+                .example(json!({ "fake_type": "array", "min_count": 1, "max_count": 3, "tag": { "fake_type": "word" } })),
+            FakeDefinitionElement::generate_array,
+        );
+        self.register_type(
+            TypeInfo::new("map", "structure", "An object of generated fields, given as the other keys or under fields")
+                .params(vec![
+                    ParamInfo::new("fields", ParamType::Fields, "The fields, when they are not given as the other keys").optional(),
+                    ParamInfo::new("unique_by", ParamType::Any, "The lists of fields whose combination is unique across the generated objects").optional(),
+                    ParamInfo::new("constraints", ParamType::Any, "The boolean expressions every generated object satisfies").optional(),
+                ])
+                .other_keys(OtherKeys::Definitions)
+                .example(json!({ "fake_type": "map", "id": { "fake_type": "sequence" }, "name": { "fake_type": "name" } })),
+            FakeDefinitionElement::generate_map,
+        );
+        self.register_type(
+            TypeInfo::new("constant", "value", "The same value every time").params(ConstantParams::describe()).example(json!({ "fake_type": "constant", "value": "active" })),
+            |setting, fake_type, _| FakeDefinitionElement::generate_constant(setting, fake_type),
+        );
+        self.register_type(
+            TypeInfo::new("template", "value", "A string rendered from a template of fields and inline generators")
+                .params(TemplateParams::describe())
+                .locales(&LOCALES)
+                .example(json!({ "fake_type": "template", "template": "{{first_name}}.{{last_name}}@example.com" })),
+            FakeDefinitionElement::generate_template,
+        );
+        self.register_type(
+            TypeInfo::new("ref", "dataset", "A value sampled from the records of another entity of a dataset")
+                .params(ReferenceParams::describe())
+                .example(json!({ "fake_type": "ref", "ref": "users.id" }))
+                .example_output(json!(42)),
+            |setting, fake_type, _| FakeDefinitionElement::generate_reference(setting, fake_type),
+        );
+        self.register_type(
+            TypeInfo::new("sequence", "value", "An incrementing number, optionally formatted").params(SequenceParams::describe()).example(json!({ "fake_type": "sequence", "format": "ORD-######" })),
+            |setting, fake_type, _| FakeDefinitionElement::generate_sequence(setting, fake_type),
+        );
+        self.register_type(
+            TypeInfo::new("expr", "value", "A value computed from other fields with an expression").params(ExprParams::describe()).example(json!({ "fake_type": "expr", "expr": "round(random() * 100)" })),
+            |setting, fake_type, _| FakeDefinitionElement::generate_expr(setting, fake_type),
+        );
+        self.register_type(
+            TypeInfo::new("if", "structure", "One of two elements depending on a condition")
+                .params(ConditionalParams::describe())
+                .example(json!({ "fake_type": "if", "if": "random() < 0.5", "then": { "fake_type": "word" }, "else": { "fake_type": "constant", "value": null } })),
+            FakeDefinitionElement::generate_if,
+        );
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry").field("fake_types", &self.types.keys().collect::<Vec<_>>()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+    use crate::fake::fake_definition::FakeDefinition;
+    use crate::fake::fake_definition_element::FakeDefinitionElement;
+    use crate::fake::fake_type::constant::Constant;
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::{json, Value};
+
+    fn registry_with_sku() -> Registry {
+        let mut registry = Registry::with_builtin_types();
+        registry.register("sku", |setting, fake_type, _| {
+            let prefix = setting.get("prefix").and_then(Value::as_str).unwrap_or("SKU");
+            Ok(Constant::new(fake_type.to_string(), json!(format!("{}-0001", prefix))).into())
+        });
+        registry
+    }
+
+    #[test]
+    fn test_registry_builtin() {
+        let registry = Registry::builtin();
+
+        assert!(registry.contains("word") && registry.contains("array") && registry.contains("if"));
+        assert!(!registry.contains("sku"));
+        assert_eq!(registry.fake_types().next(), Some("word"), "Fake types should keep their registration order");
+        assert!(Registry::new().fake_types().next().is_none(), "A new registry should be empty");
+    }
+
+    #[test]
+    fn test_registry_type_infos() {
+        let registry = Registry::builtin();
+
+        assert_eq!(registry.type_infos().count(), registry.fake_types().count(), "Every fake type should be described");
+        for info in registry.type_infos().filter(|info| info.example_output.is_none()) {
+            let example = FakeDefinitionElement::generate(&info.example).unwrap_or_else(|e| panic!("The example of {} should be valid: {}", info.name, e));
+            assert!(example.to_value(&mut GenerationContext::with_seed(1)).is_ok(), "The example of {} should generate", info.name);
+        }
+
+        let password = registry.type_info("password").unwrap();
+        assert_eq!(password.category, "internet");
+        assert_eq!(password.params.iter().map(|param| param.name.as_str()).collect::<Vec<_>>(), vec!["lang", "min", "max"]);
+        assert!(password.locales.contains(&"JA_JP".to_string()));
+        assert_eq!(registry.type_info("boolean").unwrap().param("ratio").unwrap().maximum, Some(100));
+    }
+
+    #[test]
+    fn test_registry_register() {
+        let registry = registry_with_sku();
+        let element = FakeDefinitionElement::generate_with_registry(&json!({ "fake_type": "sku", "prefix": "ABC" }), &registry).unwrap();
+
+        assert!(registry.contains("sku"));
+        assert_eq!(registry.type_info("sku").unwrap().category, "custom");
+        assert!(!Registry::builtin().contains("sku"), "Registering on a copy should leave the built-in registry unchanged");
+        assert_eq!(element.to_value(&mut GenerationContext::new()).unwrap(), json!("ABC-0001"));
+        assert!(FakeDefinitionElement::generate(&json!({ "fake_type": "sku" })).is_err(), "Should return an error for an unregistered fake type");
+    }
+
+    #[test]
+    fn test_registry_nested() {
+        let definition = json!({
+            "skus": { "fake_type": "array", "count": 2, "sku": { "fake_type": "sku", "unique": false } },
+            "item": { "fake_type": "map", "sku": { "fake_type": "sku", "prefix": "XYZ" } },
+            "optional": { "fake_type": "if", "if": "true", "then": { "fake_type": "sku" } }
+        });
+        let fd = FakeDefinition::from_json_with_registry(&definition, &registry_with_sku()).unwrap();
+
+        assert_eq!(fd.to_value(&mut GenerationContext::new()).unwrap(), json!({
+            "skus": ["SKU-0001", "SKU-0001"],
+            "item": { "sku": "XYZ-0001" },
+            "optional": "SKU-0001"
+        }), "Nested definition elements should be parsed with the same registry");
+        assert!(FakeDefinition::from_json(&definition).is_err());
+    }
+}
@@ -0,0 +1,388 @@
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use crate::fake::substitution::substitute_variables;
+use crate::file;
+
+/// Resolves every `{"$ref": ...}` in `json` into the definition it refers to, and removes the top-level `definitions`.
+///
+/// * `{"$ref": "#/definitions/address"}` refers to a named definition, or to any JSON pointer, of the same file.
+/// * `{"$ref": "common/address.json"}` includes another file, relative to the file it is written in.
+/// * `{"$ref": "common/definitions.json#/definitions/address"}` refers to a named definition of another file.
+///
+/// The other keys next to `$ref` override the keys of the definition, e.g. to add a `when` condition.
+/// `path` is the file `json` is loaded from; without it, files are included relative to the working directory.
+/// Returns an error when a `$ref` is undefined, can not be loaded, or includes itself.
+///
+/// A `$ref` including itself is only allowed with `max_depth`, to generate tree-shaped data such as comment threads.
+/// It is expanded until `max_depth` levels of the definition are nested, and each nested level is only generated
+/// with the `probability` (`1` by default). Beyond `max_depth`, the `$ref` is omitted, so that an enclosing map omits
+/// the field and an enclosing array leaves the element out.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::resolver::resolve_refs;
+/// # use serde_json::json;
+/// let json = resolve_refs(&json!({
+///     "definitions": { "city": { "fake_type": "constant", "value": "Tokyo" } },
+///     "home": { "$ref": "#/definitions/city" }
+/// }), None).unwrap();
+/// println!("Resolved definition: {}", json);
+/// ```
+pub fn resolve_refs(json: &Value, path: Option<&Path>) -> Result<Value> {
+    let document = Document { path: path.map(Path::to_path_buf), root: json.clone() };
+    Resolver::default().resolve_document(&document)
+}
+
+/// Resolves every `{"$ref": ...}` in `json` like `resolve_refs`, substituting the `${VAR}` variables of each `$ref`
+/// with the value `variable` looks up before it is followed, e.g. `{"$ref": "${DIR}/address.json"}`.
+/// The other strings are left for `substitute_variables`.
+pub fn resolve_refs_with_variables(json: &Value, path: Option<&Path>, variable: &dyn Fn(&str) -> Option<String>) -> Result<Value> {
+    let document = Document { path: path.map(Path::to_path_buf), root: json.clone() };
+    Resolver { stack: vec![], variable: Some(variable) }.resolve_document(&document)
+}
+
+/// A definition file, or the definition given in memory when `path` is `None`.
+struct Document {
+    path: Option<PathBuf>,
+    root: Value,
+}
+
+/// Follows `$ref`s, keeping the `$ref`s being resolved to detect inclusion cycles.
+/// Looks up the value of a variable by its name.
+type Variable<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+#[derive(Default)]
+struct Resolver<'a> {
+    stack: Vec<String>,
+    /// Looks up the variables substituted in the `$ref`s, which are followed as written without it.
+    variable: Option<Variable<'a>>,
+}
+
+/// The `max_depth` and `probability` of a `$ref` which may include itself.
+struct Recursion {
+    max_depth: usize,
+    probability: f64,
+}
+
+/// The definition a `$ref` is resolved to.
+enum Resolved {
+    Definition(Value),
+    /// The definition, nested in itself.
+    Recursion(Value),
+    /// Nothing, as the definition is already nested `max_depth` times.
+    Stopped,
+}
+
+impl Resolver<'_> {
+    /// Resolves a whole document. Its `definitions` are only there to be referred to, so they are left out.
+    fn resolve_document(&mut self, document: &Document) -> Result<Value> {
+        match &document.root {
+            Value::Object(map) => map
+                .iter()
+                .filter(|(key, value)| !is_definitions(key, value))
+                .map(|(key, value)| Ok((key.clone(), self.resolve(value, document)?)))
+                .collect::<Result<serde_json::Map<String, Value>>>()
+                .map(Value::Object),
+            root => self.resolve(root, document),
+        }
+    }
+
+    fn resolve(&mut self, value: &Value, document: &Document) -> Result<Value> {
+        match value {
+            Value::Object(map) => match map.get("$ref") {
+                Some(reference) => {
+                    let reference = reference.as_str().ok_or(anyhow!("$ref should be a string, e.g. \"#/definitions/address\""))?;
+                    let reference = &self.substitute(reference)?;
+                    let recursion = Recursion::from_json(map, reference)?;
+                    let (resolved, probability) = match self.resolve_reference(reference, recursion.as_ref(), document)? {
+                        Resolved::Definition(resolved) => (resolved, 1.0),
+                        Resolved::Recursion(resolved) => (resolved, recursion.map_or(1.0, |recursion| recursion.probability)),
+                        Resolved::Stopped => return Ok(omitted()),
+                    };
+
+                    let overrides = map.iter().filter(|(key, _)| !["$ref", "max_depth", "probability"].contains(&key.as_str())).collect::<Vec<_>>();
+                    let resolved = match (resolved, overrides.is_empty()) {
+                        (resolved, true) => resolved,
+                        (Value::Object(mut resolved), false) => {
+                            for (key, value) in overrides {
+                                resolved.insert(key.clone(), self.resolve(value, document)?);
+                            }
+                            Value::Object(resolved)
+                        }
+                        _ => Err(anyhow!("$ref: {} should refer to a definition object to be used together with other keys", reference))?,
+                    };
+
+                    match probability < 1.0 {
+                        true => Ok(json!({ "fake_type": "if", "if": format!("random() < {}", probability), "then": resolved })),
+                        false => Ok(resolved),
+                    }
+                }
+                None => map
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), self.resolve(value, document)?)))
+                    .collect::<Result<serde_json::Map<String, Value>>>()
+                    .map(Value::Object),
+            },
+            Value::Array(array) => array.iter().map(|value| self.resolve(value, document)).collect::<Result<Vec<Value>>>().map(Value::Array),
+            value => Ok(value.clone()),
+        }
+    }
+
+    /// Substitutes the variables of the `$ref` `reference`, when there is a `variable` to look them up.
+    fn substitute(&self, reference: &str) -> Result<String> {
+        match self.variable {
+            Some(variable) => match substitute_variables(&Value::String(reference.to_string()), variable)? {
+                Value::String(reference) => Ok(reference),
+                reference => Ok(reference.to_string()),
+            },
+            None => Ok(reference.to_string()),
+        }
+    }
+
+    fn resolve_reference(&mut self, reference: &str, recursion: Option<&Recursion>, document: &Document) -> Result<Resolved> {
+        let (file_reference, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+
+        let included;
+        let target_document = match file_reference {
+            "" => document,
+            file_reference => {
+                let path = match &document.path {
+                    Some(path) => path.parent().unwrap_or(Path::new("")).join(file_reference),
+                    None => PathBuf::from(file_reference),
+                };
+                let root = file::load_json(&path).map_err(|e| anyhow!("$ref: {} could not be loaded, {}", reference, e))?;
+                included = Document { path: Some(path), root };
+                &included
+            }
+        };
+
+        let key = format!("{}#{}", target_document.path.as_deref().map(canonical_name).unwrap_or_default(), pointer);
+        let depth = self.stack.iter().filter(|k| **k == key).count();
+        match (depth, recursion) {
+            (0, _) => (),
+            (_, Some(recursion)) if depth >= recursion.max_depth => return Ok(Resolved::Stopped),
+            (_, Some(_)) => (),
+            (_, None) => {
+                let start = self.stack.iter().position(|k| *k == key).unwrap_or(0);
+                Err(anyhow!("$ref: circular inclusion {} -> {}. set max_depth on the $ref to generate it recursively", self.stack[start..].join(" -> "), key))?
+            }
+        }
+
+        self.stack.push(key);
+        let resolved = match pointer {
+            "" => self.resolve_document(target_document),
+            pointer => match target_document.root.pointer(pointer) {
+                Some(target) => self.resolve(target, target_document),
+                None => Err(anyhow!("$ref: {} is undefined", reference)),
+            },
+        };
+        self.stack.pop();
+
+        match depth {
+            0 => Ok(Resolved::Definition(resolved?)),
+            _ => Ok(Resolved::Recursion(resolved?)),
+        }
+    }
+}
+
+impl Recursion {
+    /// Parses the `max_depth` and `probability` next to the `$ref` `reference`, if any.
+    fn from_json(map: &serde_json::Map<String, Value>, reference: &str) -> Result<Option<Self>> {
+        let probability = match map.get("probability") {
+            Some(probability_value) => {
+                let probability = probability_value.as_f64().filter(|p| (0.0..=1.0).contains(p));
+                Some(probability.ok_or(anyhow!("$ref: {}, probability should be a number from 0 to 1", reference))?)
+            }
+            None => None,
+        };
+
+        match (map.get("max_depth"), probability) {
+            (Some(max_depth_value), probability) => {
+                let max_depth = max_depth_value.as_u64().filter(|d| *d > 0).ok_or(anyhow!("$ref: {}, max_depth should be a positive integer", reference))? as usize;
+                Ok(Some(Self { max_depth, probability: probability.unwrap_or(1.0) }))
+            }
+            (None, Some(_)) => Err(anyhow!("$ref: {}, probability can only be used together with max_depth", reference)),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+/// Returns a definition which is never generated.
+fn omitted() -> Value {
+    json!({ "fake_type": "if", "if": "false", "then": { "fake_type": "constant", "value": null } })
+}
+
+/// Returns whether `key` is the `definitions` section rather than a field named `definitions`,
+/// which is always a definition object with a `fake_type`.
+fn is_definitions(key: &str, value: &Value) -> bool {
+    key == "definitions" && value.is_object() && !value.get("fake_type").is_some_and(Value::is_string)
+}
+
+/// Returns the name `path` is told apart by in inclusion cycles, whichever way it is written.
+fn canonical_name(path: &Path) -> String {
+    path.canonicalize().unwrap_or(path.to_path_buf()).display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_refs, resolve_refs_with_variables};
+    use crate::fake::fake_definition::FakeDefinition;
+    use crate::fake::generation_context::GenerationContext;
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fake-cli-resolver-{}-{}", name, std::process::id()));
+        fs::create_dir_all(dir.join("common")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolve_refs() {
+        let json = json!({
+            "definitions": {
+                "city": { "fake_type": "constant", "value": "Tokyo" },
+                "address": { "fake_type": "map", "city": { "$ref": "#/definitions/city" } }
+            },
+            "home": { "$ref": "#/definitions/address" },
+            "office": { "$ref": "#/definitions/address", "when": "true" }
+        });
+
+        let resolved = resolve_refs(&json, None).unwrap();
+
+        let address = json!({ "fake_type": "map", "city": { "fake_type": "constant", "value": "Tokyo" } });
+        assert_eq!(resolved["home"], address, "Nested $refs should be resolved");
+        assert_eq!(resolved["office"]["when"], json!("true"), "Keys next to $ref should be kept");
+        assert!(resolved.get("definitions").is_none(), "definitions should be removed");
+    }
+
+    #[test]
+    fn test_resolve_refs_with_files() {
+        let dir = temp_dir("files");
+        fs::write(dir.join("common/address.json"), json!({
+            "definitions": { "city": { "fake_type": "constant", "value": "Osaka" } },
+            "fake_type": "map",
+            "city": { "$ref": "#/definitions/city" },
+            "zip": { "$ref": "zip.json" }
+        }).to_string()).unwrap();
+        fs::write(dir.join("common/zip.json"), json!({ "fake_type": "constant", "value": "530-0001" }).to_string()).unwrap();
+        fs::write(dir.join("common/definitions.json"), json!({ "definitions": { "country": { "fake_type": "constant", "value": "JP" } } }).to_string()).unwrap();
+
+        let json = json!({
+            "address": { "$ref": "common/address.json" },
+            "country": { "$ref": "common/definitions.json#/definitions/country" }
+        });
+        let resolved = resolve_refs(&json, Some(&dir.join("definition.json"))).unwrap();
+
+        assert_eq!(resolved, json!({
+            "address": {
+                "fake_type": "map",
+                "city": { "fake_type": "constant", "value": "Osaka" },
+                "zip": { "fake_type": "constant", "value": "530-0001" }
+            },
+            "country": { "fake_type": "constant", "value": "JP" }
+        }), "Files should be included relative to the file they are referred from");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_refs_with_variables() {
+        let dir = temp_dir("variables");
+        fs::write(dir.join("common/zip.json"), json!({ "fake_type": "constant", "value": "${ZIP}" }).to_string()).unwrap();
+        fs::write(dir.join("common/address.json"), json!({ "fake_type": "map", "zip": { "$ref": "${NAME}.json" } }).to_string()).unwrap();
+        let variable = |name: &str| match name {
+            "DIR" => Some("common".to_string()),
+            "NAME" => Some("zip".to_string()),
+            _ => None,
+        };
+
+        let json = json!({ "address": { "$ref": "${DIR}/address.json" } });
+        let resolved = resolve_refs_with_variables(&json, Some(&dir.join("definition.json")), &variable).unwrap();
+
+        assert_eq!(resolved["address"]["zip"], json!({ "fake_type": "constant", "value": "${ZIP}" }), "The variables of $refs should be substituted, also in included files, and other strings left");
+        assert!(resolve_refs(&json, Some(&dir.join("definition.json"))).is_err(), "A $ref should be followed as written without variables");
+        assert!(resolve_refs_with_variables(&json!({ "a": { "$ref": "${UNDEFINED}.json" } }), None, &variable).is_err(), "Should return an error for an unset variable");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_refs_with_cycle() {
+        let local = json!({
+            "definitions": {
+                "a": { "fake_type": "map", "b": { "$ref": "#/definitions/b" } },
+                "b": { "fake_type": "map", "a": { "$ref": "#/definitions/a" } }
+            },
+            "a": { "$ref": "#/definitions/a" }
+        });
+        let error = resolve_refs(&local, None).unwrap_err();
+        assert!(error.to_string().contains("circular"), "Should return an error for circular definitions: {}", error);
+
+        let dir = temp_dir("cycle");
+        fs::write(dir.join("common/a.json"), json!({ "fake_type": "map", "b": { "$ref": "b.json" } }).to_string()).unwrap();
+        fs::write(dir.join("common/b.json"), json!({ "fake_type": "map", "a": { "$ref": "../common/a.json" } }).to_string()).unwrap();
+        let files = json!({ "a": { "$ref": "common/a.json" } });
+        assert!(resolve_refs(&files, Some(&dir.join("definition.json"))).is_err(), "Should return an error for circular inclusions");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_refs_with_recursion() {
+        let json = json!({
+            "definitions": {
+                "comment": {
+                    "fake_type": "map",
+                    "body": { "fake_type": "word", "lang": "EN" },
+                    "replies": {
+                        "fake_type": "array",
+                        "count": 2,
+                        "items": { "$ref": "#/definitions/comment", "max_depth": 3 }
+                    }
+                }
+            },
+            "thread": { "$ref": "#/definitions/comment" }
+        });
+        let fd = FakeDefinition::from_json(&resolve_refs(&json, None).unwrap()).unwrap();
+
+        fn depth(comment: &Value) -> usize {
+            1 + comment["replies"].as_array().unwrap().iter().map(depth).max().unwrap_or(0)
+        }
+        let value = fd.to_value(&mut GenerationContext::new()).unwrap();
+        assert_eq!(depth(&value["thread"]), 3, "Replies should be nested down to max_depth: {}", value);
+        assert_eq!(value["thread"]["replies"][0]["replies"][0]["replies"], json!([]), "Replies beyond max_depth should be left out");
+    }
+
+    #[test]
+    fn test_resolve_refs_with_recursion_probability() {
+        let json = json!({
+            "definitions": {
+                "category": {
+                    "fake_type": "map",
+                    "name": { "fake_type": "word", "lang": "EN" },
+                    "child": { "$ref": "#/definitions/category", "max_depth": 5, "probability": 0.0 }
+                }
+            },
+            "root": { "$ref": "#/definitions/category" }
+        });
+        let fd = FakeDefinition::from_json(&resolve_refs(&json, None).unwrap()).unwrap();
+
+        let value = fd.to_value(&mut GenerationContext::new()).unwrap();
+        assert!(value["root"].get("name").is_some(), "The first level should always be generated");
+        assert!(value["root"].get("child").is_none(), "Should not recurse with a probability of 0: {}", value);
+
+        let without_max_depth = json!({ "definitions": { "a": { "fake_type": "map", "a": { "$ref": "#/definitions/a", "probability": 0.5 } } }, "a": { "$ref": "#/definitions/a" } });
+        assert!(resolve_refs(&without_max_depth, None).is_err(), "Should return an error for a probability without max_depth");
+
+        let with_invalid_probability = json!({ "definitions": { "a": { "fake_type": "map", "a": { "$ref": "#/definitions/a", "max_depth": 2, "probability": 2 } } }, "a": { "$ref": "#/definitions/a" } });
+        assert!(resolve_refs(&with_invalid_probability, None).is_err(), "Should return an error for a probability above 1");
+    }
+
+    #[test]
+    fn test_resolve_refs_with_invalid_ref() {
+        assert!(resolve_refs(&json!({ "a": { "$ref": "#/definitions/undefined" } }), None).is_err(), "Should return an error for an undefined definition");
+        assert!(resolve_refs(&json!({ "a": { "$ref": "undefined.json" } }), None).is_err(), "Should return an error for a missing file");
+        assert!(resolve_refs(&json!({ "a": { "$ref": 1 } }), None).is_err(), "Should return an error for a non-string $ref");
+    }
+}
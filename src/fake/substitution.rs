@@ -0,0 +1,120 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Replaces every `${VAR}` and `${VAR:-default}` in the strings of `json` with the value `variable` looks up,
+/// falling back to `default` when the variable is not set. `$${` is written as a literal `${`.
+///
+/// A string made of a single placeholder is coerced to a number or a boolean when the value is one, so that
+/// `"count": "${N}"` becomes a number. Any other string stays a string.
+/// Returns an error when a variable without a default is not set, or when a placeholder is malformed.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::fake::substitution::substitute_variables;
+/// # use serde_json::json;
+/// let json = substitute_variables(&json!({ "count": "${N:-10}", "domain": "${DOMAIN}" }), &|name| match name {
+///     "DOMAIN" => Some("example.com".to_string()),
+///     _ => None,
+/// }).unwrap();
+/// println!("Substituted definition: {}", json);
+/// ```
+pub fn substitute_variables(json: &Value, variable: &dyn Fn(&str) -> Option<String>) -> Result<Value> {
+    match json {
+        Value::String(text) => substitute_string(text, variable),
+        Value::Array(array) => array.iter().map(|value| substitute_variables(value, variable)).collect::<Result<Vec<Value>>>().map(Value::Array),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), substitute_variables(value, variable)?)))
+            .collect::<Result<serde_json::Map<String, Value>>>()
+            .map(Value::Object),
+        value => Ok(value.clone()),
+    }
+}
+
+fn substitute_string(text: &str, variable: &dyn Fn(&str) -> Option<String>) -> Result<Value> {
+    if !text.contains("${") {
+        return Ok(Value::String(text.to_string()));
+    }
+
+    let mut substituted = String::new();
+    let mut is_single_placeholder = false;
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            substituted.push_str(&rest[..start - 1]);
+            substituted.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        let end = rest[start..].find('}').ok_or(anyhow!("variable: unterminated placeholder in \"{}\"", text))? + start;
+        let (name, default) = match rest[start + 2..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&rest[start + 2..end], None),
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            Err(anyhow!("variable: invalid variable name \"{}\" in \"{}\"", name, text))?
+        }
+
+        let value = variable(name)
+            .or(default.map(str::to_string))
+            .ok_or(anyhow!("variable: {} is not set. set it with --set {}=<value> or the environment, or give a default with ${{{}:-<default>}}", name, name, name))?;
+        is_single_placeholder = start == 0 && end + 1 == text.len();
+        substituted.push_str(&rest[..start]);
+        substituted.push_str(&value);
+        rest = &rest[end + 1..];
+    }
+    substituted.push_str(rest);
+
+    match serde_json::from_str::<Value>(&substituted) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_))) if is_single_placeholder => Ok(value),
+        _ => Ok(Value::String(substituted)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::substitute_variables;
+    use serde_json::{json, Value};
+
+    fn variable(name: &str) -> Option<String> {
+        match name {
+            "DOMAIN" => Some("example.com".to_string()),
+            "N" => Some("25".to_string()),
+            "RATIO" => Some("0.5".to_string()),
+            "ENABLED" => Some("true".to_string()),
+            "ZIP" => Some("007".to_string()),
+            _ => None,
+        }
+    }
+
+    fn substitute(json: Value) -> Value {
+        substitute_variables(&json, &variable).unwrap()
+    }
+
+    #[test]
+    fn test_substitute_variables() {
+        assert_eq!(substitute(json!({ "email": "{{username}}@${DOMAIN}" })), json!({ "email": "{{username}}@example.com" }));
+        assert_eq!(substitute(json!(["${TENANT:-acme}", "${DOMAIN:-other.com}"])), json!(["acme", "example.com"]), "The default should only be used for unset variables");
+        assert_eq!(substitute(json!("$${DOMAIN}")), json!("${DOMAIN}"), "An escaped placeholder should be kept literally");
+        assert_eq!(substitute(json!({ "count": 3 })), json!({ "count": 3 }));
+    }
+
+    #[test]
+    fn test_substitute_variables_with_coercion() {
+        assert_eq!(substitute(json!({ "count": "${N}" })), json!({ "count": 25 }));
+        assert_eq!(substitute(json!("${RATIO}")), json!(0.5));
+        assert_eq!(substitute(json!("${ENABLED}")), json!(true));
+        assert_eq!(substitute(json!("${COUNT:-10}")), json!(10));
+        assert_eq!(substitute(json!("${ZIP}")), json!("007"), "Values which are not JSON numbers should stay strings");
+        assert_eq!(substitute(json!("N=${N}")), json!("N=25"), "Placeholders within a string should stay strings");
+    }
+
+    #[test]
+    fn test_substitute_variables_fail() {
+        assert!(substitute_variables(&json!("${UNDEFINED}"), &variable).is_err(), "Should return an error for an unset variable");
+        assert!(substitute_variables(&json!("${DOMAIN"), &variable).is_err(), "Should return an error for an unterminated placeholder");
+        assert!(substitute_variables(&json!("${DO MAIN}"), &variable).is_err(), "Should return an error for an invalid variable name");
+    }
+}
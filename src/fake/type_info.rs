@@ -0,0 +1,230 @@
+use std::fmt;
+use serde::Serialize;
+use serde_json::Value;
+
+/// `ParamType` is the type of the value a parameter of a fake type takes.
+///
+/// * `String`, `Integer`, `Boolean`: A JSON string, integer or boolean.
+/// * `Expression`: A string holding an expression on other fields, e.g. `price * quantity`.
+/// * `Definition`: A nested definition element, e.g. `{ "fake_type": "word" }`.
+/// * `Definitions`: An array of nested definition elements.
+/// * `Fields`: An object of named definition elements.
+/// * `Any`: Any JSON value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamType {
+    String,
+    Integer,
+    Boolean,
+    Expression,
+    Definition,
+    Definitions,
+    Fields,
+    Any,
+}
+
+/// `OtherKeys` is what the keys of a definition element which are not parameters of its fake type are.
+///
+/// * `None`: Every key is a parameter, or `fake_type`, `unique` or `when`.
+/// * `Definitions`: The other keys are nested definition elements, e.g. the fields of a `map`.
+/// * `Any`: The other keys are not described, e.g. for a fake type registered without its parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtherKeys {
+    #[default]
+    None,
+    Definitions,
+    Any,
+}
+
+/// `ParamInfo` describes a parameter of a fake type: its name, the type of its value, whether it is required or
+/// its default, and the values it is limited to.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::{ParamInfo, ParamType};
+/// # use serde_json::json;
+/// let ratio = ParamInfo::new("ratio", ParamType::Integer, "The percentage of true values").default(json!(50)).range(0, 100);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParamInfo {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub param_type: ParamType,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<i64>,
+    #[serde(rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    pub description: String,
+}
+
+/// `TypeInfo` describes a fake type registered in a `Registry`: its name, category, parameters, what its other keys
+/// are, the languages it generates values in, and an example definition element together with its output.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::{ParamInfo, ParamType, TypeInfo};
+/// # use serde_json::json;
+/// let sku = TypeInfo::new("sku", "custom", "A stock keeping unit such as ABC-01234")
+///     .params(vec![ParamInfo::new("prefix", ParamType::String, "The prefix of the SKU").default(json!("SKU"))])
+///     .example(json!({ "fake_type": "sku", "prefix": "ABC" }))
+///     .example_output(json!("ABC-01234"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypeInfo {
+    pub name: String,
+    pub category: String,
+    pub description: String,
+    pub params: Vec<ParamInfo>,
+    pub locales: Vec<String>,
+    pub other_keys: OtherKeys,
+    pub example: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example_output: Option<Value>,
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ParamType::String => "string",
+            ParamType::Integer => "integer",
+            ParamType::Boolean => "boolean",
+            ParamType::Expression => "expression",
+            ParamType::Definition => "definition",
+            ParamType::Definitions => "definitions",
+            ParamType::Fields => "fields",
+            ParamType::Any => "any",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl ParamInfo {
+    /// Creates a new required `ParamInfo`.
+    pub fn new(name: impl Into<String>, param_type: ParamType, description: impl Into<String>) -> Self {
+        Self { name: name.into(), param_type, required: true, default: None, minimum: None, maximum: None, values: vec![], deprecated: false, description: description.into() }
+    }
+
+    /// Makes the parameter optional, without a default.
+    pub fn optional(mut self) -> Self {
+        self.required = false;
+        self
+    }
+
+    /// Makes the parameter optional, defaulting to `default`.
+    pub fn default(mut self, default: Value) -> Self {
+        self.required = false;
+        self.default = Some(default);
+        self
+    }
+
+    /// Limits an integer parameter to the range from `minimum` to `maximum`, both inclusive.
+    pub fn range(mut self, minimum: i64, maximum: i64) -> Self {
+        self.minimum = Some(minimum);
+        self.maximum = Some(maximum);
+        self
+    }
+
+    /// Limits an integer parameter to `minimum` or more.
+    pub fn minimum(mut self, minimum: i64) -> Self {
+        self.minimum = Some(minimum);
+        self
+    }
+
+    /// Marks the parameter as deprecated: it is still accepted but should no longer be set.
+    pub fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+
+    /// Limits a string parameter to one of `values`.
+    pub fn values(mut self, values: &[&str]) -> Self {
+        self.values = values.iter().map(|value| value.to_string()).collect();
+        self
+    }
+}
+
+impl TypeInfo {
+    /// Creates a new `TypeInfo` without parameters, whose example is the fake type with no other setting.
+    pub fn new(name: impl Into<String>, category: impl Into<String>, description: impl Into<String>) -> Self {
+        let name = name.into();
+        let example = serde_json::json!({ "fake_type": name });
+        Self { name, category: category.into(), description: description.into(), params: vec![], locales: vec![], other_keys: OtherKeys::None, example, example_output: None }
+    }
+
+    pub fn params(mut self, params: Vec<ParamInfo>) -> Self {
+        self.params = params;
+        self
+    }
+
+    pub fn locales(mut self, locales: &[&str]) -> Self {
+        self.locales = locales.iter().map(|locale| locale.to_string()).collect();
+        self
+    }
+
+    /// Sets what the keys which are not parameters are.
+    pub fn other_keys(mut self, other_keys: OtherKeys) -> Self {
+        self.other_keys = other_keys;
+        self
+    }
+
+    /// Sets the example definition element, which is generated to show an example output.
+    pub fn example(mut self, example: Value) -> Self {
+        self.example = example;
+        self
+    }
+
+    /// Sets the example output, for a fake type whose example can not be generated on its own, e.g. a `ref`.
+    pub fn example_output(mut self, example_output: Value) -> Self {
+        self.example_output = Some(example_output);
+        self
+    }
+
+    /// Returns the parameter named `name`, if any.
+    pub fn param(&self, name: &str) -> Option<&ParamInfo> {
+        self.params.iter().find(|param| param.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParamInfo, ParamType, TypeInfo};
+    use serde_json::json;
+
+    #[test]
+    fn test_type_info_serialize() {
+        let info = TypeInfo::new("boolean", "boolean", "A boolean value")
+            .params(vec![ParamInfo::new("ratio", ParamType::Integer, "The percentage of true values").default(json!(50)).range(0, 100)])
+            .locales(&["EN"]);
+
+        assert_eq!(serde_json::to_value(&info).unwrap(), json!({
+            "name": "boolean",
+            "category": "boolean",
+            "description": "A boolean value",
+            "params": [{ "name": "ratio", "type": "integer", "required": false, "default": 50, "minimum": 0, "maximum": 100, "description": "The percentage of true values" }],
+            "locales": ["EN"],
+            "other_keys": "none",
+            "example": { "fake_type": "boolean" }
+        }));
+    }
+
+    #[test]
+    fn test_param_info() {
+        let sort = ParamInfo::new("sort", ParamType::String, "The order").optional().values(&["asc", "desc"]);
+
+        assert!(!sort.required);
+        assert_eq!(sort.default, None);
+        assert_eq!(sort.values, vec!["asc".to_string(), "desc".to_string()]);
+        assert_eq!(ParamType::Definitions.to_string(), "definitions");
+        assert_eq!(TypeInfo::new("word", "lorem", "A word").param("lang"), None);
+    }
+}
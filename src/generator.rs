@@ -0,0 +1,309 @@
+use std::path::Path;
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use serde_json::Value;
+use crate::fake::dataset::Dataset;
+use crate::fake::fake_definition::FakeDefinition;
+use crate::fake::fake_definition_element::FakeDefinitionElement;
+use crate::fake::generation_context::GenerationContext;
+use crate::fake::registry::Registry;
+use crate::fake::resolver::resolve_refs_with_variables;
+use crate::fake::substitution::substitute_variables;
+use crate::file;
+
+/// `GeneratorBuilder` configures and parses a definition into a `Generator`.
+///
+/// Fake types defined outside of this crate are registered on the builder, on top of the built-in ones.
+///
+/// The definition is parsed from a `Value`, a JSON `&str` or a file. Its `$ref`s are resolved, relative to the file
+/// when parsed from a `Path`, and its `${VAR}` variables are substituted from the variables set on the builder and
+/// then from the environment.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fake_cli::Generator;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let mut generator = Generator::builder()
+///     .seed(42)
+///     .variable("DOMAIN", "example.com")
+///     .from_path("definition.json")?;
+/// let users = generator.generate_n(10)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct GeneratorBuilder {
+    seed: Option<u64>,
+    variables: Vec<(String, String)>,
+    registry: Option<Registry>,
+}
+
+/// `Generator` generates records from a parsed `FakeDefinition`, or the records of every entity from a `Dataset`.
+///
+/// A single `GenerationContext` is shared by everything a generator generates, so unique values and sequences
+/// continue across calls, and every record is given the next record index.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::Generator;
+/// # use anyhow::Result;
+/// # use serde_json::Value;
+/// # fn main() -> Result<()> {
+/// let mut generator = Generator::builder().from_json_str(r#"{ "id": { "fake_type": "sequence" } }"#)?;
+/// let first = generator.generate()?;
+/// let next_ten: Vec<Value> = generator.iter().take(10).collect::<Result<_>>()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Generator {
+    definition: Definition,
+    context: GenerationContext,
+    record_index: usize,
+}
+
+/// The parsed definition of a `Generator`.
+#[derive(Debug)]
+enum Definition {
+    Record(FakeDefinition),
+    Dataset(Dataset),
+}
+
+impl GeneratorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seeds the random values, so that the same seed generates the same data.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the variable substituted for `${key}`. It takes precedence over the environment variable `key`.
+    pub fn variable(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.variables.push((key.into(), value.into()));
+        self
+    }
+
+    /// Registers the fake type `fake_type`, which the definition can then use like a built-in fake type.
+    /// See `Registry::register`.
+    pub fn register<F>(mut self, fake_type: impl Into<String>, constructor: F) -> Self
+    where
+        F: Fn(&serde_json::Map<String, Value>, &str, &Registry) -> Result<FakeDefinitionElement> + Send + Sync + 'static,
+    {
+        self.registry.get_or_insert_with(Registry::with_builtin_types).register(fake_type, constructor);
+        self
+    }
+
+    /// Parses the definition with the fake types of `registry` instead of the built-in ones.
+    pub fn registry(mut self, registry: Registry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Parses the definition from a `Value`. `$ref`s to files are resolved relative to the working directory.
+    pub fn from_value(self, json: &Value) -> Result<Generator> {
+        self.build(json, None)
+    }
+
+    /// Parses the definition from a JSON string. `$ref`s to files are resolved relative to the working directory.
+    pub fn from_json_str(self, json: &str) -> Result<Generator> {
+        self.build(&serde_json::from_str(json)?, None)
+    }
+
+    /// Parses the definition from a JSON file. `$ref`s to files are resolved relative to the file.
+    pub fn from_path<P: AsRef<Path>>(self, path: P) -> Result<Generator> {
+        self.build(&file::load_json(&path)?, Some(path.as_ref()))
+    }
+
+    fn build(self, json: &Value, path: Option<&Path>) -> Result<Generator> {
+        let json = resolve_refs_with_variables(json, path, &|name| self.variable_value(name))?;
+        let json = substitute_variables(&json, &|name| self.variable_value(name))?;
+
+        let registry = self.registry.as_ref().unwrap_or(Registry::builtin());
+        let definition = match Dataset::is_dataset(&json) {
+            true => Definition::Dataset(Dataset::from_json_with_registry(&json, registry)?),
+            false => Definition::Record(FakeDefinition::from_json_with_registry(&json, registry)?),
+        };
+        let context = match self.seed {
+            Some(seed) => GenerationContext::with_seed(seed),
+            None => GenerationContext::new(),
+        };
+
+        Ok(Generator { definition, context, record_index: 0 })
+    }
+
+    /// Looks up the variable `name`, where the last one set wins, and then the environment variable.
+    fn variable_value(&self, name: &str) -> Option<String> {
+        match self.variables.iter().rev().find(|(key, _)| key == name) {
+            Some((_, value)) => Some(value.clone()),
+            None => std::env::var(name).ok(),
+        }
+    }
+}
+
+impl Generator {
+    pub fn builder() -> GeneratorBuilder {
+        GeneratorBuilder::new()
+    }
+
+    /// Returns whether the definition is a dataset of entities rather than a single record definition.
+    pub fn is_dataset(&self) -> bool {
+        matches!(self.definition, Definition::Dataset(_))
+    }
+
+    /// Returns the JSON Schema of what `generate` returns: a record, or the records of every entity of a dataset.
+    pub fn output_schema(&self) -> Value {
+        match &self.definition {
+            Definition::Record(fake_definition) => fake_definition.output_schema(),
+            Definition::Dataset(dataset) => dataset.output_schema(),
+        }
+    }
+
+    /// Generates one record. For a dataset, generates the records of every entity as an object keyed by entity.
+    pub fn generate(&mut self) -> Result<Value> {
+        match &self.definition {
+            Definition::Record(_) => self.next_record(),
+            Definition::Dataset(_) => {
+                let entities = self.generate_dataset()?;
+                Ok(Value::Object(entities.into_iter().map(|(name, records)| (name, Value::Array(records))).collect()))
+            }
+        }
+    }
+
+    /// Generates `count` records. Returns an error for a dataset, whose entities set their own counts.
+    pub fn generate_n(&mut self, count: usize) -> Result<Vec<Value>> {
+        self.iter().take(count).collect()
+    }
+
+    /// Returns an endless iterator of records, generated one by one. Returns an error for a dataset.
+    pub fn iter(&mut self) -> Records<'_> {
+        Records { generator: self }
+    }
+
+    /// Generates the records of every entity of a dataset, keyed by entity in their definition order.
+    /// Returns an error for a single record definition.
+    pub fn generate_dataset(&mut self) -> Result<IndexMap<String, Vec<Value>>> {
+        match &self.definition {
+            Definition::Dataset(dataset) => dataset.generate(&mut self.context),
+            Definition::Record(_) => Err(anyhow!("the definition is not a dataset. please define entities to generate a dataset")),
+        }
+    }
+
+    fn next_record(&mut self) -> Result<Value> {
+        match &self.definition {
+            Definition::Record(fake_definition) => {
+                self.context.set_record_index(self.record_index);
+                self.record_index += 1;
+                fake_definition.to_value(&mut self.context)
+            }
+            Definition::Dataset(_) => Err(anyhow!("count can not be used with a dataset. please set count on each entity")),
+        }
+    }
+}
+
+/// `Records` is the iterator returned by `Generator::iter`.
+pub struct Records<'a> {
+    generator: &'a mut Generator,
+}
+
+impl Iterator for Records<'_> {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.generator.next_record())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Generator;
+    use crate::fake::fake_type::custom::Custom;
+    use crate::fake::fake_type::FakeType;
+    use crate::fake::generation_context::GenerationContext;
+    use rand::Rng;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_generator_generate() {
+        let mut generator = Generator::builder().from_json_str(r#"{ "id": { "fake_type": "sequence" }, "index": { "fake_type": "template", "template": "{{record_index}}" } }"#).unwrap();
+
+        assert_eq!(generator.generate().unwrap(), json!({ "id": 1, "index": "0" }));
+        assert_eq!(generator.generate_n(2).unwrap(), vec![json!({ "id": 2, "index": "1" }), json!({ "id": 3, "index": "2" })], "Records should continue across calls");
+
+        let ids: Vec<Value> = generator.iter().take(2).map(|record| record.unwrap()["id"].clone()).collect();
+        assert_eq!(ids, vec![json!(4), json!(5)]);
+    }
+
+    #[test]
+    fn test_generator_builder() {
+        let definition = json!({
+            "definitions": { "domain": { "fake_type": "constant", "value": "${DOMAIN}" } },
+            "domain": { "$ref": "#/definitions/domain" },
+            "word": { "fake_type": "word", "lang": "EN" }
+        });
+        let generate = |seed: u64| Generator::builder().seed(seed).variable("DOMAIN", "example.com").from_value(&definition).unwrap().generate_n(5).unwrap();
+
+        let records = generate(1);
+        assert!(records.iter().all(|record| record["domain"] == json!("example.com")), "$refs and variables should be resolved");
+        assert_eq!(records, generate(1), "The same seed should generate the same records");
+
+        assert!(Generator::builder().from_json_str("{").is_err(), "Should return an error for invalid JSON");
+        assert!(Generator::builder().from_path("undefined.json").is_err(), "Should return an error for a missing file");
+    }
+
+    #[derive(Debug)]
+    struct Sku;
+
+    impl FakeType for Sku {
+        type Response = String;
+
+        fn fake_apply(&self, context: &mut GenerationContext) -> Self::Response {
+            format!("SKU-{:04}", context.rng().gen_range(0..10000))
+        }
+
+        fn to_value(&self, context: &mut GenerationContext) -> anyhow::Result<Value> {
+            Ok(Value::String(self.fake_apply(context)))
+        }
+    }
+
+    #[test]
+    fn test_generator_builder_register() {
+        let definition = json!({
+            "sku": { "fake_type": "sku" },
+            "related": { "fake_type": "array", "count": 2, "sku": { "fake_type": "sku" } },
+            "label": { "fake_type": "template", "template": "{{sku | lower}}: {{word}}" }
+        });
+        let mut generator = Generator::builder()
+            .register("sku", |_, fake_type, _| Ok(Custom::new(fake_type.to_string(), Sku).into()))
+            .from_value(&definition)
+            .unwrap();
+        let record = generator.generate().unwrap();
+
+        assert!(record["sku"].as_str().is_some_and(|sku| sku.starts_with("SKU-")), "Should generate a registered fake type but got {}", record);
+        assert_eq!(record["related"].as_array().map(Vec::len), Some(2));
+        assert!(record["label"].as_str().is_some_and(|label| label.starts_with("sku-")), "A template should render a registered fake type but got {}", record);
+        assert!(Generator::builder().from_value(&definition).is_err(), "Should return an error for an unregistered fake type");
+    }
+
+    #[test]
+    fn test_generator_generate_dataset() {
+        let dataset = json!({
+            "entities": {
+                "users": { "count": 2, "definition": { "id": { "fake_type": "sequence" } } }
+            }
+        });
+        let mut generator = Generator::builder().from_value(&dataset).unwrap();
+
+        assert!(generator.is_dataset());
+        assert_eq!(generator.generate().unwrap(), json!({ "users": [{ "id": 1 }, { "id": 2 }] }));
+        assert!(generator.generate_n(2).is_err(), "Should return an error for a count on a dataset");
+
+        let mut records = Generator::builder().from_value(&json!({ "id": { "fake_type": "sequence" } })).unwrap();
+        assert!(records.generate_dataset().is_err(), "Should return an error for a record definition");
+    }
+}
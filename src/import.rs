@@ -0,0 +1,8 @@
+/// The `json_schema` module imports a JSON Schema as a definition generating data which satisfies it.
+pub mod json_schema;
+
+/// The `openapi` module imports the schemas of an OpenAPI 3 document as definitions, one per component or operation.
+pub mod openapi;
+
+/// The `sql` module imports the `CREATE TABLE` statements of a SQL script as a dataset, one entity per table.
+pub mod sql;
@@ -0,0 +1,762 @@
+use std::collections::{HashMap, HashSet};
+use anyhow::{anyhow, Result};
+use serde_json::{json, Map, Value};
+
+/// The probability a value with an `example` or `examples` is generated as one of them, rather than generated from
+/// the rest of its schema, so that the examples do not repeat in every record.
+pub const EXAMPLE_PROBABILITY: f64 = 0.2;
+
+/// The number of levels a `$ref` including itself, e.g. the children of a tree node, is nested.
+pub const RECURSION_MAX_DEPTH: u64 = 3;
+
+/// The format of a `number_with_format` generating a version 4 UUID.
+pub(crate) const UUID_FORMAT: &str = "########-####-4###-8###-############";
+
+/// The JSON pointers whose members are named schemas, which are imported as named `definitions`.
+const DEFINITIONS_POINTERS: [&str; 3] = ["/definitions", "/$defs", "/components/schemas"];
+
+/// `ImportOptions` configures how a JSON Schema, or the schemas of an OpenAPI document, are imported.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ImportOptions {
+    /// The probability a property which is not `required` is generated with. Every property is generated when `None`.
+    pub optional_probability: Option<f64>,
+}
+
+/// Imports a JSON Schema of an object as a definition, which generates data that satisfies the schema.
+///
+/// * `type`: `string` is a `word`, `integer` and `number` are an `expr` picking a number from `minimum` to
+///   `maximum`, or within `exclusiveMinimum` and `exclusiveMaximum`, where a `number` is a multiple of `multipleOf`, or
+///   has two decimal places without it, `boolean` is a `boolean`, `array` is an `array` of `items` and `object` is a `map` of `properties`.
+///   A `null` in a list of types makes the value `null` one time in ten.
+/// * `format`: `email` is a `safe_email`, `uuid` a `number_with_format`, `date-time` and `date` an `expr` of a date
+///   within the last year, `ipv4` and `ipv6` an `ip_v4` and `ip_v6`, and `uri` and `hostname` a `template`.
+/// * `minLength` and `maxLength` make a string a `password` of that length.
+/// * `minItems`, `maxItems` and `uniqueItems` are the `min_count`, `max_count` and `unique` of an `array`.
+/// * `enum` is an `expr` picking one of the values, and `const` is a `constant`.
+/// * `example` and `examples` are generated with `EXAMPLE_PROBABILITY`, and a value generated from the rest of the
+///   schema otherwise. They are ignored for an identifier, a property named `id` or ending with `_id` or `Id`, or a
+///   `uuid`, which should not repeat.
+/// * `oneOf` and `anyOf` pick one of the schemas, and `allOf` merges the `properties` and `required` of the schemas.
+/// * `nullable`, as written in OpenAPI 3.0, makes the value `null` one time in ten as well.
+/// * A property which is not `required` is generated like any other property, or only with the `optional_probability`
+///   of `options`, with a `when` condition.
+/// * A `$ref` to a schema under `definitions`, `$defs` or `components/schemas` becomes a `$ref` to a named definition
+///   of the same name. A `$ref` including itself is nested up to `RECURSION_MAX_DEPTH` levels.
+///
+/// Returns an error when the root is not an object with `properties`, or when a `$ref` is not a local JSON pointer.
+///
+/// # Example
+///
+/// ```
+/// # use fake_cli::import::json_schema::{import_json_schema, ImportOptions};
+/// # use fake_cli::Generator;
+/// # use anyhow::Result;
+/// # use serde_json::json;
+/// # fn main() -> Result<()> {
+/// let definition = import_json_schema(&json!({
+///     "type": "object",
+///     "required": ["email"],
+///     "properties": { "email": { "type": "string", "format": "email" }, "age": { "type": "integer", "minimum": 18 } }
+/// }), &ImportOptions::default())?;
+/// let mut generator = Generator::builder().from_value(&definition)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn import_json_schema(schema: &Value, options: &ImportOptions) -> Result<Value> {
+    let mut importer = Importer::new(schema, options);
+    let fields = importer.root_fields(schema, "")?;
+    importer.into_definition(fields)
+}
+
+/// `Importer` imports the schemas of a document, keeping the named definitions the `$ref`s refer to.
+pub(crate) struct Importer<'a> {
+    document: &'a Value,
+    options: ImportOptions,
+    /// The name of the definition every imported `$ref` refers to, keyed by its JSON pointer.
+    names: HashMap<String, String>,
+    /// The imported named definitions, in the order they are first referred to.
+    definitions: Map<String, Value>,
+    /// The names of the definitions every definition refers to, to find the `$ref`s which include themselves.
+    references: HashMap<String, Vec<String>>,
+    /// The definitions being imported, innermost last.
+    importing: Vec<String>,
+    /// The JSON pointers of the schemas being inlined, to detect an inlined schema including itself.
+    inlining: Vec<String>,
+}
+
+impl<'a> Importer<'a> {
+    pub(crate) fn new(document: &'a Value, options: &ImportOptions) -> Self {
+        Self { document, options: *options, names: HashMap::new(), definitions: Map::new(), references: HashMap::new(), importing: vec![], inlining: vec![] }
+    }
+
+    /// Imports the `properties` of the object `schema`, found at the JSON pointer `path`, as the fields of a definition.
+    pub(crate) fn root_fields(&mut self, schema: &Value, path: &str) -> Result<Map<String, Value>> {
+        match self.record_schema(schema, path)? {
+            Some((schema, path)) => self.properties(&schema, &path),
+            None => Err(anyhow!("jsonschema: {}, the root schema should be an object with properties", display_path(path))),
+        }
+    }
+
+    /// Returns the schema of the objects `schema` describes and its JSON pointer, following a `$ref` to it and merging
+    /// its `allOf`, or `None` when it does not describe objects with `properties`.
+    pub(crate) fn record_schema(&self, schema: &Value, path: &str) -> Result<Option<(Map<String, Value>, String)>> {
+        let (schema, path) = match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => (self.target(reference)?, local_pointer(reference)?.to_string()),
+            None => (schema, path.to_string()),
+        };
+        let schema = match schema.as_object() {
+            Some(schema) => self.merge_all_of(schema, &path, &mut vec![])?,
+            None => return Ok(None),
+        };
+
+        match schema.get("properties").is_some_and(Value::is_object) {
+            true => Ok(Some((schema, path))),
+            false => Ok(None),
+        }
+    }
+
+    /// Returns the definition of `fields`, together with the named definitions they refer to.
+    pub(crate) fn into_definition(self, fields: Map<String, Value>) -> Result<Value> {
+        if fields.contains_key("definitions") && !self.definitions.is_empty() {
+            Err(anyhow!("jsonschema: a root property named definitions can not be imported together with $refs"))?
+        }
+        self.into_document(fields)
+    }
+
+    /// Returns `body` preceded by the named definitions it refers to, such as the `entities` of a dataset.
+    pub(crate) fn into_document(mut self, body: Map<String, Value>) -> Result<Value> {
+        let mut document = Map::new();
+        if !self.definitions.is_empty() {
+            let mut definitions = std::mem::take(&mut self.definitions);
+            for (name, value) in definitions.iter_mut() {
+                self.limit_recursion(name, value);
+            }
+            document.insert("definitions".to_string(), Value::Object(definitions));
+        }
+        document.extend(body);
+
+        Ok(Value::Object(document))
+    }
+
+    /// Imports the definition element generating the values of `schema`, found at the JSON pointer `path`.
+    pub(crate) fn element(&mut self, schema: &Value, path: &str) -> Result<Value> {
+        let schema = schema.as_object().ok_or(anyhow!("jsonschema: {}, a schema should be an object", display_path(path)))?;
+
+        if let Some(reference) = schema.get("$ref") {
+            let reference = reference.as_str().ok_or(anyhow!("jsonschema: {}, $ref should be a string", display_path(path)))?;
+            return self.reference(reference);
+        }
+        let schema = &self.merge_all_of(schema, path, &mut vec![])?;
+
+        let types: Vec<&str> = match schema.get("type") {
+            Some(Value::String(schema_type)) => vec![schema_type.as_str()],
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        let schema_type = types.iter().find(|schema_type| **schema_type != "null").copied();
+        if schema_type.is_none() && !types.is_empty() {
+            return Ok(json!({ "fake_type": "constant", "value": null }));
+        }
+
+        let element = self.value_element(schema, schema_type, path)?;
+        match types.contains(&"null") || schema.get("nullable").and_then(Value::as_bool).unwrap_or(false) {
+            true => Ok(nullable_element(element)),
+            false => Ok(element),
+        }
+    }
+
+    /// Imports the element generating the values of `schema` other than `null`, where `schema_type` is its type.
+    fn value_element(&mut self, schema: &Map<String, Value>, schema_type: Option<&str>, path: &str) -> Result<Value> {
+        if let Some(value) = schema.get("const") {
+            return Ok(json!({ "fake_type": "constant", "value": value }));
+        }
+        if let Some(values) = schema.get("enum") {
+            let values = values.as_array().filter(|values| !values.is_empty()).ok_or(anyhow!("jsonschema: {}, enum should be a non-empty array", display_path(path)))?;
+            return Ok(choice(values));
+        }
+        let examples = match (schema.get("example"), schema.get("examples")) {
+            (Some(example), _) => vec![example.clone()],
+            (None, Some(Value::Array(examples))) => examples.clone(),
+            _ => vec![],
+        };
+        if schema.contains_key("example") || schema.contains_key("examples") {
+            let mut generated = schema.clone();
+            generated.remove("example");
+            generated.remove("examples");
+            let element = self.value_element(&generated, schema_type, path)?;
+            return match examples.is_empty() || is_identifier(schema, path) {
+                true => Ok(element),
+                false => Ok(json!({ "fake_type": "if", "if": format!("random() < {}", EXAMPLE_PROBABILITY), "then": choice(&examples), "else": element })),
+            };
+        }
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(schemas) = schema.get(keyword) {
+                let schemas = schemas.as_array().filter(|schemas| !schemas.is_empty()).ok_or(anyhow!("jsonschema: {}, {} should be a non-empty array", display_path(path), keyword))?;
+                let elements = schemas
+                    .iter()
+                    .enumerate()
+                    .map(|(index, schema)| self.element(schema, &format!("{}/{}/{}", path, keyword, index)))
+                    .collect::<Result<Vec<Value>>>()?;
+                return Ok(pick(&elements));
+            }
+        }
+
+        match schema_type.unwrap_or_else(|| implicit_type(schema)) {
+            "string" => Ok(string(schema)),
+            "integer" => Ok(integer(schema)),
+            "number" => Ok(number(schema)),
+            "boolean" => Ok(json!({ "fake_type": "boolean" })),
+            "array" => self.array(schema, path),
+            "object" => self.object(schema, path),
+            schema_type => Err(anyhow!("jsonschema: {}, type {} is not supported", display_path(path), schema_type)),
+        }
+    }
+
+    /// Returns `schema` with the schemas of its `allOf` merged into it. The keys of `schema` come first, then the
+    /// `properties` and `required` of every schema are put together. `merging` holds the `$ref`s being merged.
+    fn merge_all_of(&self, schema: &Map<String, Value>, path: &str, merging: &mut Vec<String>) -> Result<Map<String, Value>> {
+        let Some(schemas) = schema.get("allOf") else {
+            return Ok(schema.clone());
+        };
+        let schemas = schemas.as_array().ok_or(anyhow!("jsonschema: {}, allOf should be an array", display_path(path)))?;
+
+        let mut merged = schema.clone();
+        merged.remove("allOf");
+        for (index, part) in schemas.iter().enumerate() {
+            let reference = part.get("$ref").and_then(Value::as_str);
+            let (part, part_path) = match reference {
+                Some(reference) => {
+                    if merging.iter().any(|merging| merging == reference) {
+                        Err(anyhow!("jsonschema: {}, allOf includes {} in itself", display_path(path), reference))?
+                    }
+                    merging.push(reference.to_string());
+                    (self.target(reference)?, local_pointer(reference)?.to_string())
+                }
+                None => (part, format!("{}/allOf/{}", path, index)),
+            };
+            let part = part.as_object().ok_or(anyhow!("jsonschema: {}, a schema should be an object", display_path(&part_path)))?;
+            let part = self.merge_all_of(part, &part_path, merging)?;
+            if reference.is_some() {
+                merging.pop();
+            }
+
+            for (key, value) in part {
+                match (key.as_str(), merged.get_mut(&key), value) {
+                    ("properties", Some(Value::Object(properties)), Value::Object(value)) => {
+                        value.into_iter().for_each(|(name, property)| {
+                            properties.entry(name).or_insert(property);
+                        });
+                    }
+                    ("required", Some(Value::Array(required)), Value::Array(value)) => {
+                        value.into_iter().for_each(|name| {
+                            if !required.contains(&name) {
+                                required.push(name);
+                            }
+                        });
+                    }
+                    (_, Some(_), _) => (),
+                    (_, None, value) => {
+                        merged.insert(key, value);
+                    }
+                }
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// Imports the `properties` of `schema` as fields, where the properties which are not `required` are optional when
+    /// an `optional_probability` is set.
+    fn properties(&mut self, schema: &Map<String, Value>, path: &str) -> Result<Map<String, Value>> {
+        let required: Vec<&str> = schema.get("required").and_then(Value::as_array).map(|required| required.iter().filter_map(Value::as_str).collect()).unwrap_or_default();
+
+        let mut fields = Map::new();
+        for (name, property) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+            let element = self.element(property, &format!("{}/properties/{}", path, escape(name)))?;
+            let element = match (required.contains(&name.as_str()), self.options.optional_probability) {
+                (false, Some(probability)) => optional_element(element, probability),
+                _ => element,
+            };
+            fields.insert(name.clone(), element);
+        }
+
+        Ok(fields)
+    }
+
+    fn object(&mut self, schema: &Map<String, Value>, path: &str) -> Result<Value> {
+        let fields = self.properties(schema, path)?;
+        match fields.is_empty() {
+            true => Ok(json!({ "fake_type": "constant", "value": {} })),
+            false => Ok(json!({ "fake_type": "map", "fields": fields })),
+        }
+    }
+
+    /// Imports an array of `items`, or a fixed-shape array of `prefixItems` or a list of `items`.
+    fn array(&mut self, schema: &Map<String, Value>, path: &str) -> Result<Value> {
+        let tuple = match (schema.get("prefixItems"), schema.get("items")) {
+            (Some(Value::Array(items)), _) => Some(("prefixItems", items)),
+            (None, Some(Value::Array(items))) => Some(("items", items)),
+            _ => None,
+        };
+        if let Some((keyword, items)) = tuple {
+            let elements = items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| self.element(item, &format!("{}/{}/{}", path, keyword, index)))
+                .collect::<Result<Vec<Value>>>()?;
+            return Ok(json!({ "fake_type": "array", "tuple": elements }));
+        }
+
+        let items = match schema.get("items") {
+            Some(items) => self.element(items, &format!("{}/items", path))?,
+            None => json!({ "fake_type": "word" }),
+        };
+        let max_items = schema.get("maxItems").and_then(Value::as_u64);
+        let min_items = schema.get("minItems").and_then(Value::as_u64).unwrap_or(1).min(max_items.unwrap_or(u64::MAX));
+        let max_items = max_items.unwrap_or(min_items.max(1) + 2);
+
+        let mut array = json!({ "fake_type": "array", "min_count": min_items, "max_count": max_items });
+        if schema.get("uniqueItems").and_then(Value::as_bool).unwrap_or(false) {
+            array["unique"] = json!(true);
+        }
+        array["items"] = items;
+        Ok(array)
+    }
+
+    /// Imports the schema `reference` refers to. A schema under `definitions` is imported once as a named
+    /// definition, and any other schema is imported in place.
+    fn reference(&mut self, reference: &str) -> Result<Value> {
+        let pointer = local_pointer(reference)?;
+        let target = self.target(reference)?;
+
+        let name = match DEFINITIONS_POINTERS.iter().find_map(|definitions| pointer.strip_prefix(definitions).and_then(|name| name.strip_prefix('/'))) {
+            Some(name) if !name.contains('/') => unescape(name),
+            _ => {
+                if self.inlining.iter().any(|inlining| inlining == pointer) {
+                    Err(anyhow!("jsonschema: $ref {} includes itself. move it under definitions to import it", reference))?
+                }
+                self.inlining.push(pointer.to_string());
+                let element = self.element(target, pointer);
+                self.inlining.pop();
+                return element;
+            }
+        };
+
+        let name = match self.names.get(pointer) {
+            Some(name) => name.clone(),
+            None => {
+                let mut unique = name.clone();
+                let mut number = 2;
+                while self.definitions.contains_key(&unique) {
+                    unique = format!("{}{}", name, number);
+                    number += 1;
+                }
+                self.names.insert(pointer.to_string(), unique.clone());
+                self.definitions.insert(unique.clone(), Value::Null);
+
+                self.importing.push(unique.clone());
+                let element = self.element(target, pointer);
+                self.importing.pop();
+                self.definitions.insert(unique.clone(), element?);
+                unique
+            }
+        };
+
+        if let Some(importing) = self.importing.last() {
+            self.references.entry(importing.clone()).or_default().push(name.clone());
+        }
+        Ok(json!({ "$ref": format!("#/definitions/{}", escape(&name)) }))
+    }
+
+    /// Returns the schema the local `$ref` `reference` refers to.
+    fn target(&self, reference: &str) -> Result<&'a Value> {
+        let pointer = local_pointer(reference)?;
+        self.document.pointer(pointer).ok_or(anyhow!("jsonschema: $ref {} is undefined", reference))
+    }
+
+    /// Sets `max_depth` on the `$ref`s in the definition `name` which lead back to it.
+    fn limit_recursion(&self, name: &str, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                let target = map.get("$ref").and_then(Value::as_str).and_then(|reference| reference.strip_prefix("#/definitions/")).map(unescape);
+                match target {
+                    Some(target) if self.reaches(&target, name) => {
+                        map.insert("max_depth".to_string(), json!(RECURSION_MAX_DEPTH));
+                    }
+                    _ => map.values_mut().for_each(|value| self.limit_recursion(name, value)),
+                }
+            }
+            Value::Array(array) => array.iter_mut().for_each(|value| self.limit_recursion(name, value)),
+            _ => (),
+        }
+    }
+
+    /// Returns whether the definition `from` refers to the definition `to`, directly or through other definitions.
+    fn reaches(&self, from: &str, to: &str) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![from.to_string()];
+        while let Some(name) = stack.pop() {
+            if name == to {
+                return true;
+            }
+            if visited.insert(name.clone()) {
+                stack.extend(self.references.get(&name).into_iter().flatten().cloned());
+            }
+        }
+        false
+    }
+}
+
+/// Returns whether `schema`, found at the JSON pointer `path`, is of an identifier: a property named `id` or ending with
+/// `_id` or `Id`, or a `uuid`.
+fn is_identifier(schema: &Map<String, Value>, path: &str) -> bool {
+    let name = match path.rsplit_once("/properties/") {
+        Some((_, name)) if !name.contains('/') => unescape(name),
+        _ => String::new(),
+    };
+    name == "id" || name.ends_with("_id") || name.ends_with("Id") || schema.get("format").and_then(Value::as_str) == Some("uuid")
+}
+
+/// Returns the type of a schema without a `type`, from the keywords it uses.
+fn implicit_type(schema: &Map<String, Value>) -> &'static str {
+    if schema.contains_key("properties") {
+        "object"
+    } else if schema.contains_key("items") || schema.contains_key("prefixItems") {
+        "array"
+    } else if schema.contains_key("minimum") || schema.contains_key("maximum") {
+        "number"
+    } else {
+        "string"
+    }
+}
+
+fn string(schema: &Map<String, Value>) -> Value {
+    match schema.get("format").and_then(Value::as_str) {
+        Some("email") | Some("idn-email") => return json!({ "fake_type": "safe_email" }),
+        Some("uuid") => return json!({ "fake_type": "number_with_format", "format": UUID_FORMAT }),
+        Some("date-time") => return json!({ "fake_type": "expr", "expr": "add_days(today(), -floor(random() * 365)) + 'T00:00:00Z'" }),
+        Some("date") => return json!({ "fake_type": "expr", "expr": "add_days(today(), -floor(random() * 365))" }),
+        Some("ipv4") => return json!({ "fake_type": "ip_v4" }),
+        Some("ipv6") => return json!({ "fake_type": "ip_v6" }),
+        Some("uri") | Some("uri-reference") | Some("iri") | Some("url") => return json!({ "fake_type": "template", "template": "https://www.{{word}}.{{domain_suffix}}/{{word}}" }),
+        Some("hostname") | Some("idn-hostname") => return json!({ "fake_type": "template", "template": "{{word}}.{{domain_suffix}}" }),
+        _ => (),
+    }
+
+    let min_length = schema.get("minLength").and_then(Value::as_u64);
+    let max_length = schema.get("maxLength").and_then(Value::as_u64);
+    match (min_length, max_length) {
+        (None, None) => json!({ "fake_type": "word" }),
+        (min_length, max_length) => {
+            let min = min_length.unwrap_or(max_length.unwrap_or(0).min(1));
+            let max = max_length.unwrap_or(min + 16).max(min);
+            json!({ "fake_type": "password", "min": min, "max": max + 1 })
+        }
+    }
+}
+
+/// Returns the range of the integers from `minimum` to `maximum`, both inclusive.
+fn integer_range(schema: &Map<String, Value>) -> (i64, i64) {
+    let minimum = match (schema.get("exclusiveMinimum").and_then(Value::as_f64), schema.get("minimum").and_then(Value::as_f64)) {
+        (Some(exclusive), _) => Some((exclusive.floor() as i64).saturating_add(1)),
+        (None, Some(minimum)) => Some(minimum.ceil() as i64),
+        _ => None,
+    };
+    let maximum = match (schema.get("exclusiveMaximum").and_then(Value::as_f64), schema.get("maximum").and_then(Value::as_f64)) {
+        (Some(exclusive), _) => Some((exclusive.ceil() as i64).saturating_sub(1)),
+        (None, Some(maximum)) => Some(maximum.floor() as i64),
+        _ => None,
+    };
+
+    match (minimum, maximum) {
+        (Some(minimum), Some(maximum)) => (minimum, maximum.max(minimum)),
+        (Some(minimum), None) => (minimum, minimum.saturating_add(1000)),
+        (None, Some(maximum)) => (maximum.saturating_sub(1000).min(0), maximum),
+        (None, None) => (0, 1000),
+    }
+}
+
+/// Imports an integer from `minimum` to `maximum`. The number of integers is counted in `i128`, as it exceeds `i64`
+/// for the whole range of an `int64`.
+fn integer(schema: &Map<String, Value>) -> Value {
+    let (minimum, maximum) = integer_range(schema);
+    let count = maximum as i128 - minimum as i128 + 1;
+    json!({ "fake_type": "expr", "expr": format!("{} + floor(random() * {})", minimum, count) })
+}
+
+/// Imports a number from `minimum` to `maximum`, which is a multiple of `multipleOf`, or of a hundredth without it.
+/// The bounds of `exclusiveMinimum` and `exclusiveMaximum` are moved to the next multiple within them.
+fn number(schema: &Map<String, Value>) -> Value {
+    let step = schema.get("multipleOf").and_then(Value::as_f64).filter(|step| *step > 0.0).unwrap_or(0.01);
+    let places = (0..6).find(|places| (step * 10f64.powi(*places)).fract().abs() < 1e-9).unwrap_or(6);
+    let (scale, multiple) = (10i64.pow(places as u32), (step * 10f64.powi(places)).round().max(1.0));
+    // The bounds in multiples of `step`, rounded first so that e.g. 0.3 / 0.1 is 3 rather than 2.9999999999999996.
+    let multiples = |value: f64| (value / step * 1e6).round() / 1e6;
+
+    let minimum = match (schema.get("exclusiveMinimum").and_then(Value::as_f64), schema.get("minimum").and_then(Value::as_f64)) {
+        (Some(exclusive), _) => Some(multiples(exclusive).floor() + 1.0),
+        (None, Some(minimum)) => Some(multiples(minimum).ceil()),
+        _ => None,
+    };
+    let maximum = match (schema.get("exclusiveMaximum").and_then(Value::as_f64), schema.get("maximum").and_then(Value::as_f64)) {
+        (Some(exclusive), _) => Some(multiples(exclusive).ceil() - 1.0),
+        (None, Some(maximum)) => Some(multiples(maximum).floor()),
+        _ => None,
+    };
+    let span = multiples(1000.0).floor().max(1.0);
+    let (minimum, maximum) = match (minimum, maximum) {
+        (Some(minimum), Some(maximum)) => (minimum, maximum.max(minimum)),
+        (Some(minimum), None) => (minimum, minimum + span),
+        (None, Some(maximum)) => ((maximum - span).min(0.0), maximum),
+        (None, None) => (0.0, span),
+    };
+
+    let expr = format!("({} + floor(random() * {}))", minimum, maximum - minimum + 1.0);
+    let expr = match multiple {
+        1.0 => expr,
+        multiple => format!("{} * {}", expr, multiple),
+    };
+    let expr = match scale {
+        1 => expr,
+        scale => format!("{} / {}", expr, scale),
+    };
+    json!({ "fake_type": "expr", "expr": expr })
+}
+
+/// Returns an element picking one of `values` with the same probability. Scalar values are picked by a single
+/// `expr`, and any other values by nested `if`s.
+pub(crate) fn choice(values: &[Value]) -> Value {
+    if let [value] = values {
+        return json!({ "fake_type": "constant", "value": value });
+    }
+
+    match values.iter().map(literal).collect::<Option<Vec<String>>>() {
+        Some(literals) => {
+            let (last, others) = literals.split_last().unwrap_or((&literals[0], &[]));
+            let expr = others
+                .iter()
+                .enumerate()
+                .rev()
+                .fold(last.clone(), |otherwise, (index, literal)| format!("if(random() * {} < 1, {}, {})", literals.len() - index, literal, otherwise));
+            json!({ "fake_type": "expr", "expr": expr })
+        }
+        None => pick(&values.iter().map(|value| json!({ "fake_type": "constant", "value": value })).collect::<Vec<Value>>()),
+    }
+}
+
+/// Returns an element generating the value of one of `elements`, picked with the same probability by nested `if`s.
+pub(crate) fn pick(elements: &[Value]) -> Value {
+    match elements {
+        [element] => element.clone(),
+        [element, others @ ..] => json!({
+            "fake_type": "if",
+            "if": format!("random() * {} < 1", elements.len()),
+            "then": element,
+            "else": pick(others)
+        }),
+        [] => json!({ "fake_type": "constant", "value": null }),
+    }
+}
+
+/// Returns `value` as a literal of an expression, if it can be written as one.
+fn literal(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) if !text.contains('\'') => Some(format!("'{}'", text)),
+        Value::String(text) if !text.contains('"') => Some(format!("\"{}\"", text)),
+        Value::Number(_) | Value::Bool(_) | Value::Null => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Makes `element` generate `null` one time in ten.
+pub(crate) fn nullable_element(element: Value) -> Value {
+    json!({ "fake_type": "if", "if": "random() < 0.9", "then": element, "else": { "fake_type": "constant", "value": null } })
+}
+
+/// Makes the field `element` generated with `probability`.
+fn optional_element(mut element: Value, probability: f64) -> Value {
+    element["when"] = json!(format!("random() < {}", probability));
+    element
+}
+
+/// Returns the JSON pointer of the local `$ref` `reference`, e.g. `/definitions/address` of `#/definitions/address`.
+fn local_pointer(reference: &str) -> Result<&str> {
+    reference.strip_prefix('#').ok_or(anyhow!("jsonschema: $ref {} is not supported. only $refs within the same document, such as #/definitions/address, can be imported", reference))
+}
+
+fn display_path(path: &str) -> String {
+    format!("#{}", path)
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{choice, import_json_schema, ImportOptions};
+    use crate::generator::Generator;
+    use serde_json::{json, Value};
+
+    fn generate(definition: &Value, count: usize) -> Vec<Value> {
+        Generator::builder().seed(1).from_value(definition).unwrap().generate_n(count).unwrap()
+    }
+
+    #[test]
+    fn test_import_json_schema() {
+        let definition = import_json_schema(&json!({
+            "type": "object",
+            "required": ["id", "email", "age", "role", "tags", "address"],
+            "properties": {
+                "id": { "type": "string", "format": "uuid" },
+                "email": { "type": "string", "format": "email" },
+                "age": { "type": "integer", "minimum": 18, "maximum": 65 },
+                "role": { "enum": ["admin", "member", "guest"] },
+                "tags": { "type": "array", "items": { "type": "string", "minLength": 3, "maxLength": 5 }, "minItems": 1, "maxItems": 4, "uniqueItems": true },
+                "address": { "type": "object", "required": ["city"], "properties": { "city": { "type": "string" }, "zip": { "type": "string" } } },
+                "nickname": { "type": ["string", "null"] }
+            }
+        }), &ImportOptions { optional_probability: Some(0.5) }).unwrap();
+
+        assert_eq!(definition["email"], json!({ "fake_type": "safe_email" }));
+        assert_eq!(definition["age"], json!({ "fake_type": "expr", "expr": "18 + floor(random() * 48)" }));
+        assert_eq!(definition["tags"], json!({ "fake_type": "array", "min_count": 1, "max_count": 4, "unique": true, "items": { "fake_type": "password", "min": 3, "max": 6 } }));
+        assert_eq!(definition["address"]["fields"]["zip"]["when"], json!("random() < 0.5"), "A property which is not required should be optional");
+        assert_eq!(definition["nickname"]["fake_type"], json!("if"));
+
+        let records = generate(&definition, 50);
+        for record in &records {
+            assert_eq!(record["id"].as_str().unwrap().len(), 36);
+            assert!((18..=65).contains(&record["age"].as_i64().unwrap()), "age should be within the range: {}", record["age"]);
+            assert!(["admin", "member", "guest"].contains(&record["role"].as_str().unwrap()));
+            let tags = record["tags"].as_array().unwrap();
+            assert!((1..=4).contains(&tags.len()) && tags.iter().all(|tag| (3..=5).contains(&tag.as_str().unwrap().chars().count())));
+            assert!(record["address"]["city"].is_string());
+        }
+        assert!(records.iter().any(|record| record["address"].get("zip").is_none()) && records.iter().any(|record| record["address"].get("zip").is_some()));
+        assert!(records.iter().any(|record| record.get("nickname").is_some_and(Value::is_null)));
+    }
+
+    #[test]
+    fn test_import_json_schema_optional() {
+        let schema = json!({ "type": "object", "required": ["id"], "properties": { "id": { "type": "integer" }, "nickname": { "type": "string" } } });
+
+        let definition = import_json_schema(&schema, &ImportOptions::default()).unwrap();
+        assert_eq!(definition["nickname"], json!({ "fake_type": "word" }), "A property which is not required should be generated by default");
+        assert!(generate(&definition, 20).iter().all(|record| record["nickname"].is_string()));
+
+        let definition = import_json_schema(&schema, &ImportOptions { optional_probability: Some(0.0) }).unwrap();
+        assert_eq!(definition["nickname"]["when"], json!("random() < 0"));
+        assert!(generate(&definition, 20).iter().all(|record| record.get("nickname").is_none() && record["id"].is_number()));
+    }
+
+    #[test]
+    fn test_import_json_schema_refs() {
+        let definition = import_json_schema(&json!({
+            "$ref": "#/$defs/customer",
+            "$defs": {
+                "customer": {
+                    "type": "object",
+                    "required": ["billing", "shipping", "category"],
+                    "properties": {
+                        "billing": { "$ref": "#/$defs/address" },
+                        "shipping": { "$ref": "#/$defs/address" },
+                        "category": { "$ref": "#/$defs/category" }
+                    }
+                },
+                "address": { "type": "object", "required": ["city"], "properties": { "city": { "type": "string" } } },
+                "category": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": { "name": { "type": "string" }, "children": { "type": "array", "items": { "$ref": "#/$defs/category" } } }
+                }
+            }
+        }), &ImportOptions::default()).unwrap();
+
+        assert_eq!(definition["billing"], json!({ "$ref": "#/definitions/address" }));
+        assert_eq!(definition["shipping"], json!({ "$ref": "#/definitions/address" }), "A $ref should be imported once");
+        assert_eq!(definition["definitions"].as_object().unwrap().keys().collect::<Vec<_>>(), vec!["address", "category"]);
+        assert_eq!(definition["definitions"]["category"]["fields"]["children"]["items"], json!({ "$ref": "#/definitions/category", "max_depth": 3 }), "A $ref including itself should be limited");
+
+        let records = generate(&definition, 5);
+        assert!(records.iter().all(|record| record["billing"]["city"].is_string() && record["category"]["name"].is_string()));
+    }
+
+    #[test]
+    fn test_import_json_schema_fail() {
+        assert!(import_json_schema(&json!({ "type": "string" }), &ImportOptions::default()).is_err(), "Should return an error for a root which is not an object");
+        assert!(import_json_schema(&json!({ "properties": { "a": { "$ref": "other.json#/a" } } }), &ImportOptions::default()).is_err(), "Should return an error for a $ref to another file");
+        assert!(import_json_schema(&json!({ "properties": { "a": { "$ref": "#/definitions/undefined" } } }), &ImportOptions::default()).is_err(), "Should return an error for an undefined $ref");
+        assert!(import_json_schema(&json!({ "properties": { "a": { "type": "object", "properties": { "b": { "$ref": "#/properties/a" } } } } }), &ImportOptions::default()).is_err(), "Should return an error for an inlined $ref including itself");
+    }
+
+    #[test]
+    fn test_import_json_schema_examples() {
+        let definition = import_json_schema(&json!({
+            "type": "object",
+            "required": ["id", "user_id", "name", "color"],
+            "properties": {
+                "id": { "type": "integer", "minimum": 1, "maximum": 1000000, "example": 1 },
+                "user_id": { "type": "string", "format": "uuid", "example": "3fa85f64-5717-4562-b3fc-2c963f66afa6" },
+                "name": { "type": "string", "example": "Rex" },
+                "color": { "type": "string", "examples": ["red", "blue"] }
+            }
+        }), &ImportOptions::default()).unwrap();
+
+        assert_eq!(definition["id"], json!({ "fake_type": "expr", "expr": "1 + floor(random() * 1000000)" }), "The example of an identifier should be ignored");
+        assert_eq!(definition["user_id"]["fake_type"], json!("number_with_format"));
+        assert_eq!(definition["name"], json!({ "fake_type": "if", "if": "random() < 0.2", "then": { "fake_type": "constant", "value": "Rex" }, "else": { "fake_type": "word" } }));
+
+        let records = generate(&definition, 50);
+        assert!(records.iter().any(|record| record["name"] == json!("Rex")) && records.iter().any(|record| record["name"] != json!("Rex")), "The example should be one of the generated values");
+        assert!(records.iter().any(|record| !["red", "blue"].contains(&record["color"].as_str().unwrap())));
+        assert!(records.iter().filter(|record| record["id"] == json!(1)).count() <= 1);
+    }
+
+    #[test]
+    fn test_import_json_schema_int64() {
+        let definition = import_json_schema(&json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": { "id": { "type": "integer", "format": "int64", "minimum": i64::MIN, "maximum": i64::MAX } }
+        }), &ImportOptions::default()).unwrap();
+
+        assert_eq!(definition["id"], json!({ "fake_type": "expr", "expr": "-9223372036854775808 + floor(random() * 18446744073709551616)" }));
+        let ids: Vec<f64> = generate(&definition, 20).iter().map(|record| record["id"].as_f64().unwrap()).collect();
+        assert!(ids.iter().all(|id| (i64::MIN as f64..=i64::MAX as f64).contains(id)) && ids.windows(2).any(|ids| ids[0] != ids[1]), "The whole range should be generated: {:?}", ids);
+    }
+
+    #[test]
+    fn test_import_json_schema_number() {
+        let definition = import_json_schema(&json!({
+            "type": "object",
+            "required": ["ratio", "score", "tenth"],
+            "properties": {
+                "ratio": { "type": "number", "exclusiveMinimum": 0, "maximum": 1 },
+                "score": { "type": "number", "minimum": 0, "maximum": 10, "multipleOf": 0.5 },
+                "tenth": { "type": "number", "exclusiveMinimum": 0.3, "exclusiveMaximum": 0.5, "multipleOf": 0.1 }
+            }
+        }), &ImportOptions::default()).unwrap();
+
+        assert_eq!(definition["ratio"], json!({ "fake_type": "expr", "expr": "(1 + floor(random() * 100)) / 100" }), "An exclusive minimum should not be generated");
+        assert_eq!(definition["score"], json!({ "fake_type": "expr", "expr": "(0 + floor(random() * 21)) * 5 / 10" }));
+        for record in generate(&definition, 50) {
+            let ratio = record["ratio"].as_f64().unwrap();
+            assert!(ratio > 0.0 && ratio <= 1.0, "ratio should be within its exclusive minimum: {}", ratio);
+            let score = record["score"].as_f64().unwrap();
+            assert!((0.0..=10.0).contains(&score) && (score * 2.0).fract() == 0.0, "score should be a multiple of 0.5: {}", score);
+            assert_eq!(record["tenth"], json!(0.4));
+        }
+    }
+
+    #[test]
+    fn test_choice() {
+        assert_eq!(choice(&[json!("a")]), json!({ "fake_type": "constant", "value": "a" }));
+        assert_eq!(choice(&[json!("a"), json!(1), json!(null)]), json!({ "fake_type": "expr", "expr": "if(random() * 3 < 1, 'a', if(random() * 2 < 1, 1, null))" }));
+        assert_eq!(choice(&[json!({ "a": 1 }), json!("it's")])["fake_type"], json!("if"), "Values which can not be written in an expression should be picked with if");
+    }
+}
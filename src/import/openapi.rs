@@ -0,0 +1,351 @@
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use serde_json::{json, Map, Number, Value};
+use crate::import::json_schema::{ImportOptions, Importer};
+
+/// The number of records every entity of an imported dataset generates.
+pub const DATASET_COUNT: u64 = 1;
+
+/// The HTTP methods of the operations of a path item.
+const METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// `OpenApiSchemas` is which schemas of an OpenAPI document are imported as definitions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenApiSchemas {
+    /// Every object schema under `components.schemas`, named after it.
+    Components,
+    /// The JSON request body and responses of every operation, named `<operationId>_request` and
+    /// `<operationId>_<status>`, or after the method and the path when the operation has no `operationId`.
+    Operations,
+}
+
+/// Parses an OpenAPI document written in YAML or JSON, which is also YAML.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fake_cli::import::openapi::parse_openapi;
+/// # use anyhow::Result;
+/// # use std::fs;
+/// # fn main() -> Result<()> {
+/// let document = parse_openapi(&fs::read_to_string("openapi.yaml")?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_openapi(text: &str) -> Result<Value> {
+    let yaml: serde_yaml::Value = serde_yaml::from_str(text).map_err(|error| anyhow!("openapi: {}", error))?;
+    json_value(yaml)
+}
+
+/// Imports the `schemas` of an OpenAPI 3 document as one definition each, keyed by its name. Every definition holds
+/// the named definitions its `$ref`s refer to, so that it can be written to a file of its own.
+///
+/// The schemas are imported as `import_json_schema` imports a JSON Schema, together with the `example`, `nullable`,
+/// `oneOf` and `allOf` of OpenAPI. A response of an array is imported as its `items`, and schemas which are not
+/// objects with `properties`, such as an `enum` of strings, are only imported where they are referred to.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fake_cli::import::json_schema::ImportOptions;
+/// # use fake_cli::import::openapi::{import_openapi, parse_openapi, OpenApiSchemas};
+/// # use anyhow::Result;
+/// # use std::fs;
+/// # fn main() -> Result<()> {
+/// # let document = parse_openapi(&fs::read_to_string("openapi.yaml")?)?;
+/// for (name, definition) in import_openapi(&document, OpenApiSchemas::Components, &ImportOptions::default())? {
+///     fs::write(format!("{}.json", name), serde_json::to_string_pretty(&definition)?)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn import_openapi(document: &Value, schemas: OpenApiSchemas, options: &ImportOptions) -> Result<IndexMap<String, Value>> {
+    let mut definitions = IndexMap::new();
+    for (name, schema, path) in record_schemas(document, schemas)? {
+        let mut importer = Importer::new(document, options);
+        if importer.record_schema(schema, &path)?.is_some() {
+            let fields = importer.root_fields(schema, &path)?;
+            definitions.insert(name, importer.into_definition(fields)?);
+        }
+    }
+
+    Ok(definitions)
+}
+
+/// Imports the `schemas` of an OpenAPI 3 document as a dataset, with an entity generating `DATASET_COUNT` records of
+/// each schema. The named definitions the `$ref`s refer to are shared by every entity.
+pub fn import_openapi_dataset(document: &Value, schemas: OpenApiSchemas, options: &ImportOptions) -> Result<Value> {
+    let mut importer = Importer::new(document, options);
+    let mut entities = Map::new();
+    for (name, schema, path) in record_schemas(document, schemas)? {
+        if importer.record_schema(schema, &path)?.is_some() {
+            let fields = importer.root_fields(schema, &path)?;
+            entities.insert(name, json!({ "count": DATASET_COUNT, "definition": fields }));
+        }
+    }
+    if entities.is_empty() {
+        Err(anyhow!("openapi: the document has no object schemas with properties to import"))?
+    }
+
+    let mut dataset = Map::new();
+    dataset.insert("entities".to_string(), Value::Object(entities));
+    importer.into_document(dataset)
+}
+
+/// Returns the name, the schema and the JSON pointer of every schema of `document` which may describe records.
+fn record_schemas(document: &Value, schemas: OpenApiSchemas) -> Result<Vec<(String, &Value, String)>> {
+    match document.get("openapi").and_then(Value::as_str) {
+        Some(version) if version.starts_with('3') => (),
+        _ => Err(anyhow!("openapi: only OpenAPI 3 documents, with an openapi version such as 3.0.3, can be imported"))?,
+    }
+
+    match schemas {
+        OpenApiSchemas::Components => Ok(document
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(name, schema)| (name.clone(), schema, format!("/components/schemas/{}", escape(name))))
+            .collect()),
+        OpenApiSchemas::Operations => operation_schemas(document),
+    }
+}
+
+/// Returns the schemas of the JSON request body and responses of every operation of `document`.
+fn operation_schemas(document: &Value) -> Result<Vec<(String, &Value, String)>> {
+    let mut schemas = vec![];
+    for (path, item) in document.get("paths").and_then(Value::as_object).into_iter().flatten() {
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let pointer = format!("/paths/{}/{}", escape(path), method);
+            let name = match operation.get("operationId").and_then(Value::as_str) {
+                Some(operation_id) => operation_id.to_string(),
+                None => operation_name(method, path),
+            };
+
+            if let Some(request_body) = operation.get("requestBody") {
+                let (request_body, pointer) = dereference(document, request_body, format!("{}/requestBody", pointer))?;
+                if let Some((schema, pointer)) = json_schema(document, request_body, pointer)? {
+                    schemas.push((format!("{}_request", name), schema, pointer));
+                }
+            }
+            for (status, response) in operation.get("responses").and_then(Value::as_object).into_iter().flatten() {
+                let (response, pointer) = dereference(document, response, format!("{}/responses/{}", pointer, escape(status)))?;
+                if let Some((schema, pointer)) = json_schema(document, response, pointer)? {
+                    schemas.push((format!("{}_{}", name, status), schema, pointer));
+                }
+            }
+        }
+    }
+
+    Ok(schemas)
+}
+
+/// Returns the schema of the JSON content of a request body or a response, or of the `items` when it is an array.
+fn json_schema<'a>(document: &'a Value, body: &'a Value, pointer: String) -> Result<Option<(&'a Value, String)>> {
+    let content = body.get("content").and_then(Value::as_object).into_iter().flatten();
+    let media = content
+        .filter(|(media_type, _)| media_type.contains("json") || media_type.as_str() == "*/*")
+        .min_by_key(|(media_type, _)| !media_type.starts_with("application/json"));
+    let Some((schema, pointer)) = media.and_then(|(media_type, media)| media.get("schema").map(|schema| (schema, format!("{}/content/{}/schema", pointer, escape(media_type))))) else {
+        return Ok(None);
+    };
+
+    let (target, target_pointer) = dereference(document, schema, pointer.clone())?;
+    match (target.get("type").and_then(Value::as_str), target.get("items")) {
+        (Some("array"), Some(items)) => Ok(Some((items, format!("{}/items", target_pointer)))),
+        _ => Ok(Some((schema, pointer))),
+    }
+}
+
+/// Follows a `$ref` to another object of `document`, such as a response under `components.responses`.
+fn dereference<'a>(document: &'a Value, value: &'a Value, pointer: String) -> Result<(&'a Value, String)> {
+    match value.get("$ref").and_then(Value::as_str) {
+        Some(reference) => {
+            let target = reference.strip_prefix('#').ok_or(anyhow!("openapi: #{}, $ref {} is not supported. only $refs within the same document can be imported", pointer, reference))?;
+            let value = document.pointer(target).ok_or(anyhow!("openapi: #{}, $ref {} is undefined", pointer, reference))?;
+            Ok((value, target.to_string()))
+        }
+        None => Ok((value, pointer)),
+    }
+}
+
+/// Names an operation without an `operationId` after its method and path, e.g. `get_users_id` for `GET /users/{id}`.
+fn operation_name(method: &str, path: &str) -> String {
+    let segments: Vec<String> = std::iter::once(method.to_string())
+        .chain(path.split('/').filter(|segment| !segment.is_empty()).map(|segment| {
+            segment.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect::<String>().trim_matches('_').to_string()
+        }))
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    segments.join("_")
+}
+
+/// Converts a YAML value to a JSON value. The keys of a mapping, such as the `200` of the responses, become strings.
+fn json_value(yaml: serde_yaml::Value) -> Result<Value> {
+    match yaml {
+        serde_yaml::Value::Null => Ok(Value::Null),
+        serde_yaml::Value::Bool(value) => Ok(Value::Bool(value)),
+        serde_yaml::Value::Number(number) => match (number.as_u64(), number.as_i64(), number.as_f64()) {
+            (Some(value), _, _) => Ok(json!(value)),
+            (None, Some(value), _) => Ok(json!(value)),
+            (None, None, Some(value)) => Number::from_f64(value).map(Value::Number).ok_or(anyhow!("openapi: {} is not a JSON number", value)),
+            _ => Err(anyhow!("openapi: {} is not a JSON number", number)),
+        },
+        serde_yaml::Value::String(value) => Ok(Value::String(value)),
+        serde_yaml::Value::Sequence(values) => values.into_iter().map(json_value).collect::<Result<Vec<Value>>>().map(Value::Array),
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut map = Map::new();
+            for (key, value) in mapping {
+                let key = match key {
+                    serde_yaml::Value::String(key) => key,
+                    serde_yaml::Value::Number(key) => key.to_string(),
+                    serde_yaml::Value::Bool(key) => key.to_string(),
+                    key => Err(anyhow!("openapi: {:?} is not supported as a key", key))?,
+                };
+                map.insert(key, json_value(value)?);
+            }
+            Ok(Value::Object(map))
+        }
+        serde_yaml::Value::Tagged(tagged) => json_value(tagged.value),
+    }
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{import_openapi, import_openapi_dataset, operation_name, parse_openapi, OpenApiSchemas};
+    use crate::import::json_schema::ImportOptions;
+    use crate::generator::Generator;
+    use serde_json::json;
+
+    const DOCUMENT: &str = r##"
+openapi: 3.0.3
+info: { title: Pets, version: "1.0" }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        200:
+          description: The pets
+          content:
+            application/json:
+              schema: { type: array, items: { $ref: "#/components/schemas/Pet" } }
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: "#/components/schemas/NewPet" }
+      responses:
+        "201": { $ref: "#/components/responses/Created" }
+        "204": { description: No content }
+components:
+  responses:
+    Created:
+      description: Created
+      content:
+        application/json:
+          schema: { $ref: "#/components/schemas/Pet" }
+  schemas:
+    Status: { type: string, enum: [available, sold] }
+    NewPet:
+      type: object
+      required: [name, status]
+      properties:
+        name: { type: string, example: Rex }
+        status: { $ref: "#/components/schemas/Status" }
+        tag: { type: string, nullable: true }
+    Pet:
+      allOf:
+        - $ref: "#/components/schemas/NewPet"
+        - type: object
+          required: [id, owner]
+          properties:
+            id: { type: integer, minimum: 1, maximum: 100, example: 7 }
+            owner:
+              oneOf:
+                - { type: object, required: [email], properties: { email: { type: string, format: email } } }
+                - { type: object, required: [phone], properties: { phone: { type: string, minLength: 10, maxLength: 10 } } }
+"##;
+
+    #[test]
+    fn test_parse_openapi() {
+        let document = parse_openapi(DOCUMENT).unwrap();
+
+        assert!(document["paths"]["/pets"]["get"]["responses"]["200"].is_object(), "A status code should be a string key");
+        assert_eq!(parse_openapi(r#"{"openapi": "3.1.0", "paths": {}}"#).unwrap(), json!({ "openapi": "3.1.0", "paths": {} }), "JSON should also be parsed");
+        assert!(parse_openapi("a: [").is_err());
+    }
+
+    #[test]
+    fn test_import_openapi_components() {
+        let document = parse_openapi(DOCUMENT).unwrap();
+
+        let definitions = import_openapi(&document, OpenApiSchemas::Components, &ImportOptions { optional_probability: Some(0.5) }).unwrap();
+
+        assert_eq!(definitions.keys().collect::<Vec<_>>(), vec!["NewPet", "Pet"], "A schema which is not an object should be skipped");
+        let pet = &definitions["Pet"];
+        assert_eq!(pet["definitions"]["Status"], json!({ "fake_type": "expr", "expr": "if(random() * 2 < 1, 'available', 'sold')" }));
+        assert_eq!(pet["name"]["then"], json!({ "fake_type": "constant", "value": "Rex" }), "The example should be one of the generated values");
+        assert_eq!(pet["name"]["else"], json!({ "fake_type": "word" }));
+        assert_eq!(pet["id"], json!({ "fake_type": "expr", "expr": "1 + floor(random() * 100)" }), "The example of an id should be ignored");
+        assert_eq!(pet["tag"]["when"], json!("random() < 0.5"));
+        assert_eq!(pet["tag"]["then"], json!({ "fake_type": "word" }), "A nullable value should be null one time in ten");
+        assert_eq!(pet.as_object().unwrap().keys().collect::<Vec<_>>(), vec!["definitions", "name", "status", "tag", "id", "owner"], "allOf should be merged");
+
+        let records = Generator::builder().seed(1).from_value(pet).unwrap().generate_n(30).unwrap();
+        for record in &records {
+            assert!((1..=100).contains(&record["id"].as_i64().unwrap()));
+            assert!(["available", "sold"].contains(&record["status"].as_str().unwrap()));
+            assert!(record["owner"]["email"].is_string() != record["owner"]["phone"].is_string(), "One of the owners should be generated: {}", record["owner"]);
+        }
+        assert!(records.iter().any(|record| record["owner"]["email"].is_string()) && records.iter().any(|record| record["owner"]["phone"].is_string()));
+    }
+
+    #[test]
+    fn test_import_openapi_operations() {
+        let document = parse_openapi(DOCUMENT).unwrap();
+
+        let definitions = import_openapi(&document, OpenApiSchemas::Operations, &ImportOptions::default()).unwrap();
+
+        assert_eq!(definitions.keys().collect::<Vec<_>>(), vec!["listPets_200", "post_pets_request", "post_pets_201"]);
+        assert!(definitions["listPets_200"]["owner"].is_object(), "An array should be imported as its items");
+        assert!(definitions["post_pets_request"].get("id").is_none());
+        assert!(definitions["post_pets_201"]["id"].is_object(), "A $ref to a response should be followed");
+    }
+
+    #[test]
+    fn test_import_openapi_dataset() {
+        let document = parse_openapi(DOCUMENT).unwrap();
+
+        let dataset = import_openapi_dataset(&document, OpenApiSchemas::Components, &ImportOptions::default()).unwrap();
+
+        assert_eq!(dataset["entities"]["Pet"]["count"], json!(1));
+        assert_eq!(dataset["entities"]["Pet"]["definition"]["status"], json!({ "$ref": "#/definitions/Status" }));
+        assert!(dataset["definitions"]["Status"].is_object(), "The named definitions should be shared by the entities");
+        let generated = Generator::builder().seed(1).from_value(&dataset).unwrap().generate().unwrap();
+        assert!(generated["Pet"][0]["id"].is_number() && generated["NewPet"][0]["name"].is_string());
+    }
+
+    #[test]
+    fn test_import_openapi_fail() {
+        assert!(import_openapi(&json!({ "swagger": "2.0" }), OpenApiSchemas::Components, &ImportOptions::default()).is_err(), "Should return an error for Swagger 2");
+        assert!(import_openapi_dataset(&json!({ "openapi": "3.0.0" }), OpenApiSchemas::Components, &ImportOptions::default()).is_err(), "Should return an error without schemas");
+        let external = json!({ "openapi": "3.0.0", "components": { "schemas": { "A": { "properties": { "b": { "$ref": "b.yaml" } } } } } });
+        assert!(import_openapi(&external, OpenApiSchemas::Components, &ImportOptions::default()).is_err(), "Should return an error for a $ref to another file");
+        assert!(import_openapi(&json!({ "openapi": "3.0.0" }), OpenApiSchemas::Operations, &ImportOptions::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_operation_name() {
+        assert_eq!(operation_name("get", "/users/{id}/orders"), "get_users_id_orders");
+        assert_eq!(operation_name("post", "/"), "post");
+        assert_eq!(operation_name("put", "/v1.0/user-groups"), "put_v1_0_user_groups");
+    }
+}
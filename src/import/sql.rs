@@ -0,0 +1,508 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Map, Value};
+use crate::import::json_schema::{choice, nullable_element, UUID_FORMAT};
+use crate::import::sql::parser::{parse, Column, Schema, Table};
+
+/// The `parser` module parses the `CREATE TABLE` statements of a SQL script into tables and columns.
+mod parser;
+
+/// The number of rows every table of an imported dataset generates.
+pub const DATASET_COUNT: u64 = 10;
+
+/// The tables whose `name` column is the name of a person, by their singular name.
+const PERSON_TABLES: [&str; 14] = ["user", "customer", "employee", "author", "person", "people", "member", "contact", "student", "teacher", "patient", "staff", "client", "player"];
+
+/// The columns holding the time a row is created at, which an `updated_at` column follows.
+const CREATED_COLUMNS: [&str; 4] = ["created_at", "created_on", "created", "inserted_at"];
+
+/// The expression of a date within the last year.
+const RECENT_DATE: &str = "add_days(today(), -floor(random() * 365))";
+
+/// The expression of the date of birth of an adult, from 18 to 78 years ago.
+const BIRTH_DATE: &str = "add_days(today(), -365 * 18 - floor(random() * 365 * 60))";
+
+/// The length of the longest word of a `sentence`, which bounds the length of a sentence of a number of words.
+const LONGEST_WORD: u64 = 14;
+
+/// The length of the longest value of the generators a column name suggests, e.g. a `first_name` and a `username`
+/// joined by `.`, which decides whether they fit in a `varchar(n)` column.
+const GENERATOR_LENGTHS: [(&str, u64); 8] = [
+    ("safe_email", 25),
+    ("first_name", 13),
+    ("last_name", 13),
+    ("name", 27),
+    ("username", 28),
+    ("ip_v4", 15),
+    ("mac_address", 17),
+    ("user_agent", 151),
+];
+
+/// `Kind` is how the values of a column type are generated.
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Serial,
+    /// An integer of up to the largest value of the type, such as 127 for `tinyint`.
+    Integer(i64),
+    Decimal,
+    Float,
+    Boolean,
+    Timestamp,
+    Date,
+    Time,
+    Uuid,
+    Json,
+    Inet,
+    MacAddress,
+    Enum(Vec<Value>),
+    /// A string of up to the length of the type, such as `varchar(255)`.
+    Text,
+    /// A string of exactly the length of the type, such as `char(2)`.
+    FixedText,
+    /// A string of any length, such as `text`.
+    LongText,
+}
+
+/// Imports the `CREATE TABLE` statements of a SQL script as a dataset, with an entity generating `DATASET_COUNT`
+/// rows of each table.
+///
+/// * Column types: integers are an `expr` from 1 to 1000, or to 127 for a `tinyint`, and `serial` a `sequence`, `numeric(p, s)` an `expr` with `s`
+///   decimal places up to the largest number of the type, `boolean` a `boolean`, `timestamp` and `date` an `expr` of a
+///   date within the last year, at a random time of day for `timestamp`, `uuid` a `number_with_format`, and
+///   `varchar(n)` a `word`, or a `password` of up to `n` characters for short strings.
+/// * Column names: `email`, `first_name`, `last_name`, `username`, `phone`, `url`, `title`, `description`, `age`,
+///   `birth_date`, `updated_at` and the like are generated as their names suggest, within the length of a `varchar(n)`.
+/// * Constraints: a column without `NOT NULL` is `null` one time in ten, a `UNIQUE` column and a `PRIMARY KEY` are
+///   `unique`, an integer `PRIMARY KEY` is a `sequence`, a `FOREIGN KEY` is a `ref` to the referenced column, and
+///   `CHECK (column IN (...))` and enum types pick one of their values. A `UNIQUE` constraint or a `PRIMARY KEY` of more
+///   than one column is a `unique_by` of the record.
+///
+/// A foreign key to its own table refers to the row itself, or is `null` when it may be. Returns an error when the
+/// script has no `CREATE TABLE` statement, or a statement can not be parsed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fake_cli::import::sql::import_sql;
+/// # use fake_cli::Generator;
+/// # use anyhow::Result;
+/// # use std::fs;
+/// # fn main() -> Result<()> {
+/// let dataset = import_sql(&fs::read_to_string("schema.sql")?)?;
+/// let records = Generator::builder().from_value(&dataset)?.generate_dataset()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn import_sql(sql: &str) -> Result<Value> {
+    let schema = parse(sql)?;
+    if schema.tables.is_empty() {
+        Err(anyhow!("sql: the script has no CREATE TABLE statement to import"))?
+    }
+
+    let mut entities = Map::new();
+    for table in &schema.tables {
+        entities.insert(table.name.clone(), json!({ "count": DATASET_COUNT, "definition": table_definition(table, &schema) }));
+    }
+
+    Ok(json!({ "entities": entities }))
+}
+
+fn table_definition(table: &Table, schema: &Schema) -> Value {
+    let mut definition = Map::new();
+    for column in &table.columns {
+        definition.insert(column.name.clone(), column_element(table, column, schema));
+    }
+
+    let unique_by: Vec<&Vec<String>> = table.unique.iter().chain(Some(&table.primary_key).filter(|primary_key| primary_key.len() > 1)).collect();
+    if !unique_by.is_empty() {
+        definition.insert("unique_by".to_string(), json!(unique_by));
+    }
+
+    Value::Object(definition)
+}
+
+fn column_element(table: &Table, column: &Column, schema: &Schema) -> Value {
+    let primary_key = matches!(table.primary_key.as_slice(), [name] if name.eq_ignore_ascii_case(&column.name));
+    let unique = column.unique || primary_key;
+
+    let element = match reference_element(table, column, schema, unique) {
+        Some(element) => return element,
+        None if !column.values.is_empty() => choice(&column.values),
+        None => {
+            let kind = kind(column, schema);
+            match kind {
+                Kind::Serial | Kind::Integer(_) if column.identity || primary_key => json!({ "fake_type": "sequence" }),
+                kind => {
+                    let mut element = type_element(table, column, kind);
+                    if unique {
+                        element["unique"] = json!(true);
+                    }
+                    element
+                }
+            }
+        }
+    };
+
+    let element = match column.array {
+        true => json!({ "fake_type": "array", "min_count": 1, "max_count": 3, "items": element }),
+        false => element,
+    };
+    match column.not_null || primary_key {
+        true => element,
+        false => nullable_element(element),
+    }
+}
+
+/// Returns the `ref` of a foreign key to a table of the script, sampling every referenced row at most once when the
+/// column is unique. A foreign key to its own table refers to the row itself, or is `null` when it may be.
+fn reference_element(table: &Table, column: &Column, schema: &Schema, unique: bool) -> Option<Value> {
+    let (referenced_table, referenced_column) = column.references.as_ref()?;
+    let referenced_table = schema.tables.iter().find(|table| table.name.eq_ignore_ascii_case(referenced_table))?;
+    let referenced_column = match (referenced_column, referenced_table.primary_key.as_slice()) {
+        (Some(referenced_column), _) => referenced_column.as_str(),
+        (None, [primary_key]) => primary_key.as_str(),
+        (None, _) => "id",
+    };
+    let referenced_column = referenced_table.column(referenced_column).map(|column| column.name.as_str()).unwrap_or(referenced_column);
+
+    if std::ptr::eq(referenced_table, table) {
+        return match column.not_null {
+            true => Some(json!({ "fake_type": "expr", "expr": referenced_column })),
+            false => Some(json!({ "fake_type": "constant", "value": null })),
+        };
+    }
+
+    let mut element = json!({ "fake_type": "ref", "ref": format!("{}.{}", referenced_table.name, referenced_column) });
+    if unique {
+        element["cardinality"] = json!("1:1");
+    }
+    match column.not_null {
+        true => Some(element),
+        false => Some(nullable_element(element)),
+    }
+}
+
+/// Returns how the values of the type of `column` are generated. A type unknown to the importer is taken as text.
+fn kind(column: &Column, schema: &Schema) -> Kind {
+    let base = column.data_type.split(' ').next().unwrap_or_default();
+    match base {
+        "smallserial" | "serial" | "bigserial" | "serial2" | "serial4" | "serial8" => Kind::Serial,
+        "tinyint" if column.args == [json!(1)] => Kind::Boolean,
+        "tinyint" => Kind::Integer(i8::MAX.into()),
+        "smallint" | "int2" => Kind::Integer(i16::MAX.into()),
+        "int" | "integer" | "int4" | "int8" | "mediumint" | "bigint" => Kind::Integer(i64::MAX),
+        "numeric" | "decimal" | "number" | "money" => Kind::Decimal,
+        "real" | "float" | "float4" | "float8" | "double" => Kind::Float,
+        "bool" | "boolean" | "bit" => Kind::Boolean,
+        "timestamp" | "timestamptz" | "datetime" | "datetime2" | "smalldatetime" => Kind::Timestamp,
+        "date" => Kind::Date,
+        "time" | "timetz" => Kind::Time,
+        "uuid" | "uniqueidentifier" => Kind::Uuid,
+        "json" | "jsonb" => Kind::Json,
+        "inet" | "cidr" => Kind::Inet,
+        "macaddr" | "macaddr8" => Kind::MacAddress,
+        "enum" | "set" => Kind::Enum(column.args.clone()),
+        "character" | "char" | "nchar" | "bpchar" if !column.data_type.contains("varying") => Kind::FixedText,
+        "text" | "tinytext" | "mediumtext" | "longtext" | "clob" | "citext" => Kind::LongText,
+        _ => match schema.enums.get(&column.data_type) {
+            Some(values) => Kind::Enum(values.clone()),
+            None => Kind::Text,
+        },
+    }
+}
+
+fn type_element(table: &Table, column: &Column, kind: Kind) -> Value {
+    let name = column.name.to_lowercase();
+    let length = column.args.first().and_then(Value::as_u64);
+
+    match kind {
+        Kind::Serial => json!({ "fake_type": "sequence" }),
+        Kind::Integer(largest) => match name.as_str() {
+            "age" => integer_between(18, 80.min(largest)),
+            "quantity" | "qty" => integer_between(1, 10),
+            "rating" | "stars" => integer_between(1, 5),
+            _ => integer_between(1, 1000.min(largest)),
+        },
+        Kind::Decimal => decimal(column.args.first().and_then(Value::as_u64), column.args.get(1).and_then(Value::as_u64)),
+        Kind::Float => json!({ "fake_type": "expr", "expr": "floor(random() * 100000) / 100" }),
+        Kind::Boolean => json!({ "fake_type": "boolean" }),
+        Kind::Timestamp => json!({ "fake_type": "expr", "expr": format!("{} + ' ' + {}", date_expr(table, &name), time_expr()) }),
+        Kind::Date => json!({ "fake_type": "expr", "expr": date_expr(table, &name) }),
+        Kind::Time => json!({ "fake_type": "expr", "expr": time_expr() }),
+        Kind::Uuid => json!({ "fake_type": "number_with_format", "format": UUID_FORMAT }),
+        Kind::Json => json!({ "fake_type": "constant", "value": {} }),
+        Kind::Inet => json!({ "fake_type": "ip_v4" }),
+        Kind::MacAddress => json!({ "fake_type": "mac_address" }),
+        Kind::Enum(values) if !values.is_empty() => choice(&values),
+        Kind::Enum(_) => json!({ "fake_type": "word" }),
+        Kind::Text | Kind::FixedText | Kind::LongText => match (text_element(&table.name, &name), kind, length) {
+            (Some(element), Kind::LongText, _) | (Some(element), _, None) => element,
+            (Some(element), kind, Some(length)) => bounded_text(element, length).unwrap_or_else(|| plain_text(kind, Some(length))),
+            (None, kind, length) => plain_text(kind, length),
+        },
+    }
+}
+
+/// Returns the element of a text column its name suggests nothing about, a string of up to its `length`.
+fn plain_text(kind: Kind, length: Option<u64>) -> Value {
+    match (kind, length) {
+        (Kind::FixedText, length) => json!({ "fake_type": "password", "min": length.unwrap_or(1), "max": length.unwrap_or(1) + 1 }),
+        (Kind::Text, Some(length)) if length < 16 => json!({ "fake_type": "password", "min": length.div_ceil(2), "max": length + 1 }),
+        (Kind::LongText, _) => json!({ "fake_type": "sentence", "min": 3, "max": 8 }),
+        _ => json!({ "fake_type": "word" }),
+    }
+}
+
+/// Returns the element of a text column its name suggests, e.g. a `safe_email` of an `email` column.
+fn text_element(table: &str, name: &str) -> Option<Value> {
+    let element = match name {
+        "email" | "email_address" | "mail" => json!({ "fake_type": "safe_email" }),
+        _ if name.ends_with("_email") => json!({ "fake_type": "safe_email" }),
+        "first_name" | "firstname" | "given_name" | "forename" => json!({ "fake_type": "first_name" }),
+        "last_name" | "lastname" | "surname" | "family_name" => json!({ "fake_type": "last_name" }),
+        "full_name" | "fullname" | "display_name" => json!({ "fake_type": "name" }),
+        "name" if is_person_table(table) => json!({ "fake_type": "name" }),
+        "name" => json!({ "fake_type": "template", "template": "{{word}} {{word}}" }),
+        "username" | "user_name" | "login" | "handle" | "nickname" => json!({ "fake_type": "username" }),
+        "password" | "password_hash" | "password_digest" | "encrypted_password" | "passwd" => json!({ "fake_type": "password", "min": 12, "max": 21 }),
+        "phone" | "phone_number" | "mobile" | "telephone" | "tel" => json!({ "fake_type": "number_with_format", "format": "###-###-####" }),
+        "ip" | "ip_address" | "ip_addr" | "remote_ip" | "last_ip" => json!({ "fake_type": "ip_v4" }),
+        "url" | "website" | "homepage" | "link" => json!({ "fake_type": "template", "template": "https://www.{{word}}.{{domain_suffix}}/{{word}}" }),
+        _ if name.ends_with("_url") => json!({ "fake_type": "template", "template": "https://www.{{word}}.{{domain_suffix}}/{{word}}" }),
+        "domain" | "hostname" | "host" => json!({ "fake_type": "template", "template": "{{word}}.{{domain_suffix}}" }),
+        "user_agent" => json!({ "fake_type": "user_agent" }),
+        "mac" | "mac_address" => json!({ "fake_type": "mac_address" }),
+        "slug" => json!({ "fake_type": "template", "template": "{{word}}-{{word}}" }),
+        "title" | "subject" | "headline" => json!({ "fake_type": "sentence", "min": 2, "max": 6 }),
+        "description" | "bio" | "body" | "content" | "summary" | "comment" | "notes" | "note" | "message" => json!({ "fake_type": "paragraph", "min": 1, "max": 3 }),
+        "currency" | "currency_code" => choice(&[json!("USD"), json!("EUR"), json!("GBP"), json!("JPY")]),
+        _ => return None,
+    };
+    Some(element)
+}
+
+/// Returns `element` bounded to the `length` characters of a `varchar(length)` column, or `None` when its values can
+/// not be. A generator is kept when its values fit, the placeholders of a `template` share the length, a `password` and
+/// a `sentence` are generated with fewer characters and words, and a `paragraph` as a `sentence`.
+fn bounded_text(element: Value, length: u64) -> Option<Value> {
+    let fake_type = element["fake_type"].as_str()?;
+    match fake_type {
+        "template" => bounded_template(element["template"].as_str()?, length),
+        "password" => {
+            let max = element["max"].as_u64()?.min(length + 1);
+            let min = element["min"].as_u64()?.min(max - 1);
+            Some(json!({ "fake_type": "password", "min": min, "max": max }))
+        }
+        "sentence" | "paragraph" => {
+            // Every word is followed by a space or the closing period.
+            let words = (length / (LONGEST_WORD + 1)).min(element["max"].as_u64().filter(|_| fake_type == "sentence").map_or(u64::MAX, |max| max - 1));
+            let min = element["min"].as_u64().filter(|_| fake_type == "sentence").unwrap_or(1).min(words);
+            (words > 0).then(|| json!({ "fake_type": "sentence", "min": min, "max": words + 1 }))
+        }
+        "number_with_format" => element["format"].as_str().is_some_and(|format| format.chars().count() as u64 <= length).then_some(element),
+        "expr" => element["expr"].as_str()?.split('\'').skip(1).step_by(2).all(|literal| literal.chars().count() as u64 <= length).then_some(element),
+        _ => GENERATOR_LENGTHS.iter().any(|(generator, max)| *generator == fake_type && *max <= length).then_some(element),
+    }
+}
+
+/// Returns a `template` truncating its placeholders to share the `length` characters left by its text, or `None` when
+/// there are too few characters left.
+fn bounded_template(template: &str, length: u64) -> Option<Value> {
+    let placeholders = template.matches("{{").count() as u64;
+    let text: u64 = template.split("{{").map(|part| part.split_once("}}").map_or(part, |(_, text)| text).chars().count() as u64).sum();
+    let share = length.checked_sub(text)? / placeholders.max(1);
+    if share == 0 {
+        return None;
+    }
+
+    let template = template.replace("}}", &format!(" | truncate({})}}}}", share));
+    Some(json!({ "fake_type": "template", "template": template }))
+}
+
+/// Returns the expression of a time of day, e.g. `09:41:07`.
+fn time_expr() -> String {
+    format!("{} + ':' + {} + ':' + {}", two_digits(24), two_digits(60), two_digits(60))
+}
+
+/// Returns the expression of a number from 0 to `count - 1`, written with two digits, e.g. `07`.
+fn two_digits(count: u64) -> String {
+    format!("if(random() * {} < 10, '0' + str(floor(random() * 10)), str(10 + floor(random() * {})))", count, count - 10)
+}
+
+/// Returns the expression of a date column, which is a date of birth or a date within the last year. An `updated_at`
+/// column follows the `created_at` column of the same row by 1 to 30 days, so that it is later whatever their times.
+fn date_expr(table: &Table, name: &str) -> String {
+    let created = table.columns.iter().find(|column| {
+        column.not_null && CREATED_COLUMNS.contains(&column.name.to_lowercase().as_str()) && ["timestamp", "datetime", "date"].iter().any(|data_type| column.data_type.starts_with(data_type))
+    });
+
+    match (name, created) {
+        ("birth_date" | "birthdate" | "birthday" | "date_of_birth" | "dob", _) => BIRTH_DATE.to_string(),
+        ("updated_at" | "updated_on" | "updated" | "modified_at" | "modified", Some(created)) => format!("add_days({}, 1 + floor(random() * 30))", created.name),
+        _ => RECENT_DATE.to_string(),
+    }
+}
+
+fn integer_between(minimum: i64, maximum: i64) -> Value {
+    json!({ "fake_type": "expr", "expr": format!("{} + floor(random() * {})", minimum, maximum - minimum + 1) })
+}
+
+/// Returns a number of `numeric(precision, scale)`, from 0 up to the largest number of the type, `10^(p - s) - 10^-s`.
+/// At most 9 digits are generated before the decimal point and 6 after it, and a `numeric` without a precision is
+/// generated as a `numeric(10, 2)`.
+fn decimal(precision: Option<u64>, scale: Option<u64>) -> Value {
+    let (precision, scale) = match precision {
+        Some(precision) => (precision, scale.unwrap_or(0)),
+        None => (10, 2),
+    };
+    let places = scale.min(6);
+    let digits = match precision.checked_sub(scale) {
+        Some(integer_digits) => integer_digits.min(9) + places,
+        None => precision.saturating_sub(scale - places),
+    };
+
+    match places {
+        0 => json!({ "fake_type": "expr", "expr": format!("floor(random() * {})", 10u64.pow(digits as u32)) }),
+        places => json!({ "fake_type": "expr", "expr": format!("floor(random() * {}) / {}", 10u64.pow(digits as u32), 10u64.pow(places as u32)) }),
+    }
+}
+
+fn is_person_table(table: &str) -> bool {
+    let table = table.to_lowercase();
+    PERSON_TABLES.iter().any(|person| table == *person || table.strip_suffix('s') == Some(person))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bounded_text, decimal, import_sql, time_expr};
+    use crate::fake::expression::Expression;
+    use crate::fake::generation_context::GenerationContext;
+    use crate::generator::Generator;
+    use serde_json::{json, Value};
+    use std::collections::HashSet;
+
+    const SCHEMA: &str = r#"
+        CREATE TYPE plan AS ENUM ('free', 'pro');
+        CREATE TABLE users (
+            id serial PRIMARY KEY,
+            email varchar(255) NOT NULL UNIQUE,
+            first_name varchar(50) NOT NULL,
+            username varchar(10) NOT NULL,
+            name varchar(100),
+            plan plan NOT NULL,
+            status varchar(10) NOT NULL CHECK (status IN ('active', 'banned')),
+            country char(2) NOT NULL,
+            manager_id int REFERENCES users (id),
+            created_at timestamp NOT NULL DEFAULT now(),
+            updated_at timestamp NOT NULL
+        );
+        CREATE TABLE orders (
+            id uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+            user_id int NOT NULL,
+            total numeric(8, 2) NOT NULL,
+            paid boolean NOT NULL DEFAULT false,
+            note text,
+            FOREIGN KEY (user_id) REFERENCES users
+        );
+        CREATE TABLE user_roles (user_id int NOT NULL REFERENCES users, role varchar(20) NOT NULL, PRIMARY KEY (user_id, role));
+    "#;
+
+    #[test]
+    fn test_import_sql() {
+        let dataset = import_sql(SCHEMA).unwrap();
+        let users = &dataset["entities"]["users"]["definition"];
+        let orders = &dataset["entities"]["orders"]["definition"];
+
+        assert_eq!(dataset["entities"]["users"]["count"], json!(10));
+        assert_eq!(users["id"], json!({ "fake_type": "sequence" }));
+        assert_eq!(users["email"], json!({ "fake_type": "safe_email", "unique": true }));
+        assert_eq!(users["first_name"], json!({ "fake_type": "first_name" }));
+        assert_eq!(users["name"]["then"], json!({ "fake_type": "name" }), "A nullable column should be null one time in ten");
+        assert_eq!(users["username"], json!({ "fake_type": "password", "min": 5, "max": 11 }), "A username longer than the column should not be used");
+        assert_eq!(users["country"], json!({ "fake_type": "password", "min": 2, "max": 3 }));
+        assert_eq!(users["manager_id"], json!({ "fake_type": "constant", "value": null }), "A nullable foreign key to its own table should be null");
+        assert!(users["updated_at"]["expr"].as_str().unwrap().starts_with("add_days(created_at, 1 + floor(random() * 30)) + ' ' + "));
+        assert_eq!(orders["id"]["unique"], json!(true));
+        assert_eq!(orders["user_id"], json!({ "fake_type": "ref", "ref": "users.id" }));
+        assert_eq!(orders["total"], json!({ "fake_type": "expr", "expr": "floor(random() * 100000000) / 100" }));
+        assert_eq!(dataset["entities"]["user_roles"]["definition"]["unique_by"], json!([["user_id", "role"]]));
+
+        let generated = Generator::builder().seed(1).from_value(&dataset).unwrap().generate().unwrap();
+        let user_ids: HashSet<&Value> = generated["users"].as_array().unwrap().iter().map(|user| &user["id"]).collect();
+        for user in generated["users"].as_array().unwrap() {
+            assert!(["free", "pro"].contains(&user["plan"].as_str().unwrap()));
+            assert!(["active", "banned"].contains(&user["status"].as_str().unwrap()));
+            assert!(user["updated_at"].as_str().unwrap() > user["created_at"].as_str().unwrap(), "updated_at should follow created_at: {}", user);
+            assert!(user["username"].as_str().unwrap().chars().count() <= 10);
+        }
+        let times: HashSet<&str> = generated["users"].as_array().unwrap().iter().map(|user| &user["created_at"].as_str().unwrap()[10..]).collect();
+        assert!(times.len() > 1, "A timestamp should have a random time of day: {:?}", times);
+        assert!(times.iter().all(|time| time.len() == 9 && time.starts_with(' ')), "A timestamp should be written as YYYY-MM-DD HH:MM:SS: {:?}", times);
+        for order in generated["orders"].as_array().unwrap() {
+            assert!(user_ids.contains(&order["user_id"]), "A foreign key should refer to a generated row: {}", order);
+            assert!((0.0..=999999.99).contains(&order["total"].as_f64().unwrap()));
+        }
+        let emails: HashSet<&Value> = generated["users"].as_array().unwrap().iter().map(|user| &user["email"]).collect();
+        assert_eq!(emails.len(), 10, "A unique column should not repeat");
+    }
+
+    #[test]
+    fn test_import_sql_self_reference() {
+        let dataset = import_sql("CREATE TABLE categories (id bigint GENERATED ALWAYS AS IDENTITY PRIMARY KEY, parent_id bigint NOT NULL REFERENCES categories (id), profile_id int UNIQUE REFERENCES profiles);").unwrap();
+        let categories = &dataset["entities"]["categories"]["definition"];
+
+        assert_eq!(categories["parent_id"], json!({ "fake_type": "expr", "expr": "id" }), "A foreign key to its own table should refer to the row itself");
+        assert_eq!(categories["profile_id"]["then"], json!({ "fake_type": "expr", "expr": "1 + floor(random() * 1000)", "unique": true }), "A foreign key to a table of another script should be generated by its type");
+    }
+
+    #[test]
+    fn test_import_sql_fail() {
+        assert!(import_sql("CREATE INDEX users_email ON users (email);").is_err(), "Should return an error without CREATE TABLE");
+        assert!(import_sql("CREATE TABLE users (id int,").is_err(), "Should return an error for an unclosed statement");
+    }
+
+    #[test]
+    fn test_import_sql_integer_widths() {
+        let dataset = import_sql("CREATE TABLE u (x tinyint NOT NULL, y smallint NOT NULL, age tinyint NOT NULL, z bigint NOT NULL);").unwrap();
+        let definition = &dataset["entities"]["u"]["definition"];
+
+        assert_eq!(definition["x"], json!({ "fake_type": "expr", "expr": "1 + floor(random() * 127)" }), "A tinyint should not exceed 127");
+        assert_eq!(definition["y"], json!({ "fake_type": "expr", "expr": "1 + floor(random() * 1000)" }));
+        assert_eq!(definition["age"], json!({ "fake_type": "expr", "expr": "18 + floor(random() * 63)" }));
+        assert_eq!(definition["z"], json!({ "fake_type": "expr", "expr": "1 + floor(random() * 1000)" }));
+    }
+
+    #[test]
+    fn test_bounded_text() {
+        assert_eq!(bounded_text(json!({ "fake_type": "safe_email" }), 255), Some(json!({ "fake_type": "safe_email" })));
+        assert_eq!(bounded_text(json!({ "fake_type": "safe_email" }), 20), None, "A generator longer than the column should not be used");
+        assert_eq!(bounded_text(json!({ "fake_type": "template", "template": "{{word}}-{{word}}" }), 21), Some(json!({ "fake_type": "template", "template": "{{word | truncate(10)}}-{{word | truncate(10)}}" })));
+        assert_eq!(bounded_text(json!({ "fake_type": "password", "min": 12, "max": 21 }), 8), Some(json!({ "fake_type": "password", "min": 8, "max": 9 })));
+        assert_eq!(bounded_text(json!({ "fake_type": "sentence", "min": 2, "max": 6 }), 50), Some(json!({ "fake_type": "sentence", "min": 2, "max": 4 })));
+        assert_eq!(bounded_text(json!({ "fake_type": "paragraph", "min": 1, "max": 3 }), 100), Some(json!({ "fake_type": "sentence", "min": 1, "max": 7 })));
+        assert_eq!(bounded_text(json!({ "fake_type": "number_with_format", "format": "###-###-####" }), 10), None, "A format longer than the column should not be used");
+        assert_eq!(bounded_text(json!({ "fake_type": "template", "template": "https://www.{{word}}.com" }), 16), None);
+    }
+
+    #[test]
+    fn test_time_expr() {
+        let expression = Expression::parse(&time_expr()).unwrap();
+        let mut context = GenerationContext::new();
+
+        for _ in 0..100 {
+            let time = expression.evaluate(&mut context).unwrap();
+            let parts: Vec<u32> = time.as_str().unwrap().split(':').map(|part| part.parse().unwrap()).collect();
+            assert!(time.as_str().unwrap().len() == 8 && parts[0] < 24 && parts[1] < 60 && parts[2] < 60, "Should be a time of day: {}", time);
+        }
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert_eq!(decimal(Some(10), Some(2)), json!({ "fake_type": "expr", "expr": "floor(random() * 10000000000) / 100" }));
+        assert_eq!(decimal(Some(5), Some(2)), json!({ "fake_type": "expr", "expr": "floor(random() * 100000) / 100" }), "numeric(5, 2) should be up to 999.99");
+        assert_eq!(decimal(Some(3), None), json!({ "fake_type": "expr", "expr": "floor(random() * 1000)" }));
+        assert_eq!(decimal(Some(2), Some(2)), json!({ "fake_type": "expr", "expr": "floor(random() * 100) / 100" }));
+        assert_eq!(decimal(Some(38), Some(10)), json!({ "fake_type": "expr", "expr": "floor(random() * 1000000000000000) / 1000000" }));
+        assert_eq!(decimal(Some(2), Some(4)), json!({ "fake_type": "expr", "expr": "floor(random() * 100) / 10000" }), "numeric(2, 4) should be up to 0.0099");
+        assert_eq!(decimal(None, None), json!({ "fake_type": "expr", "expr": "floor(random() * 10000000000) / 100" }));
+    }
+}
@@ -0,0 +1,663 @@
+use std::collections::HashMap;
+use anyhow::{anyhow, Result};
+use serde_json::{Number, Value};
+
+/// `Token` is a lexical token of a SQL script.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A keyword or an unquoted identifier, as written.
+    Word(String),
+    /// An identifier quoted with `"` or `` ` ``.
+    Quoted(String),
+    /// A string literal, quoted with `'` or dollar-quoted.
+    Text(String),
+    Number(String),
+    Symbol(char),
+}
+
+/// The words which may follow the first word of a type, such as the `varying` of `character varying` or the
+/// `with time zone` of `timestamp with time zone`.
+const TYPE_WORDS: [&str; 10] = ["varying", "precision", "with", "without", "time", "zone", "unsigned", "signed", "zerofill", "array"];
+
+/// The words starting a table constraint rather than a column in a `CREATE TABLE` statement.
+const CONSTRAINT_WORDS: [&str; 10] = ["constraint", "primary", "unique", "foreign", "check", "key", "index", "fulltext", "spatial", "exclude"];
+
+/// `Schema` is the tables of a SQL script, together with the values of its enum types.
+#[derive(Debug, Default)]
+pub struct Schema {
+    pub tables: Vec<Table>,
+    /// The values of every `CREATE TYPE ... AS ENUM`, keyed by the lowercase name of the type.
+    pub enums: HashMap<String, Vec<Value>>,
+}
+
+/// `Table` is a table of a `CREATE TABLE` statement, with the constraints `ALTER TABLE` statements add to it.
+#[derive(Debug, Default, PartialEq)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Column>,
+    pub primary_key: Vec<String>,
+    /// The columns of every `UNIQUE` constraint of more than one column.
+    pub unique: Vec<Vec<String>>,
+}
+
+/// `Column` is a column of a table, with the constraints on it.
+#[derive(Debug, Default, PartialEq)]
+pub struct Column {
+    pub name: String,
+    /// The lowercase type without its arguments, e.g. `character varying` of `character varying(255)`.
+    pub data_type: String,
+    /// The arguments of the type, e.g. the precision and the scale of `numeric(10, 2)` or the values of `enum('a', 'b')`.
+    pub args: Vec<Value>,
+    pub array: bool,
+    pub not_null: bool,
+    pub unique: bool,
+    /// Whether the database generates the value, as `serial`, `AUTO_INCREMENT` or `GENERATED AS IDENTITY` do.
+    pub identity: bool,
+    /// The table and the column a foreign key refers to. The column is the primary key of the table when omitted.
+    pub references: Option<(String, Option<String>)>,
+    /// The values a `CHECK (column IN (...))` constraint allows.
+    pub values: Vec<Value>,
+}
+
+/// `Constraint` is a table constraint, or a constraint added by `ALTER TABLE ... ADD`.
+#[derive(Debug, PartialEq)]
+enum Constraint {
+    PrimaryKey(Vec<String>),
+    Unique(Vec<String>),
+    ForeignKey(Vec<String>, String, Vec<String>),
+    Check(Option<String>, Vec<Value>),
+    Other,
+}
+
+impl Table {
+    /// Returns the column named `name`, whose case is ignored as an unquoted SQL identifier's is.
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|column| column.name.eq_ignore_ascii_case(name))
+    }
+
+    fn column_mut(&mut self, name: &str) -> Option<&mut Column> {
+        self.columns.iter_mut().find(|column| column.name.eq_ignore_ascii_case(name))
+    }
+
+    fn add_constraint(&mut self, constraint: Constraint) {
+        match constraint {
+            Constraint::PrimaryKey(columns) => {
+                for name in &columns {
+                    if let Some(column) = self.column_mut(name) {
+                        column.not_null = true;
+                    }
+                }
+                self.primary_key = columns;
+            }
+            Constraint::Unique(columns) => match columns.as_slice() {
+                [name] => self.column_mut(name).into_iter().for_each(|column| column.unique = true),
+                _ => self.unique.push(columns),
+            },
+            // A foreign key of more than one column can not be generated as a whole, so it is left out.
+            Constraint::ForeignKey(columns, table, referenced) => {
+                if let ([name], referenced) = (columns.as_slice(), referenced.as_slice()) {
+                    if let Some(column) = self.column_mut(name) {
+                        column.references = Some((table, referenced.first().cloned()));
+                    }
+                }
+            }
+            Constraint::Check(Some(name), values) => self.column_mut(&name).into_iter().for_each(|column| column.values = values.clone()),
+            Constraint::Check(None, _) | Constraint::Other => (),
+        }
+    }
+}
+
+/// Parses the `CREATE TABLE`, `CREATE TYPE ... AS ENUM` and `ALTER TABLE ... ADD` statements of a SQL script.
+/// Any other statement, such as `CREATE INDEX` or `INSERT`, is skipped.
+pub fn parse(sql: &str) -> Result<Schema> {
+    let tokens = tokenize(sql)?;
+    let mut schema = Schema::default();
+    for statement in tokens.split(|token| *token == Token::Symbol(';')) {
+        Parser { tokens: statement, position: 0 }.statement(&mut schema)?;
+    }
+
+    Ok(schema)
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    let text = |from: usize, to: usize| chars[from..to].iter().collect::<String>();
+
+    while position < chars.len() {
+        let c = chars[position];
+        let next = chars.get(position + 1).copied();
+
+        if c.is_whitespace() {
+            position += 1;
+        } else if (c == '-' && next == Some('-')) || c == '#' {
+            position += chars[position..].iter().position(|c| *c == '\n').unwrap_or(chars.len() - position);
+        } else if c == '/' && next == Some('*') {
+            let length = chars[position..].windows(2).position(|pair| pair == ['*', '/']).ok_or(anyhow!("sql: unclosed comment"))?;
+            position += length + 2;
+        } else if c == '\'' || c == '"' || c == '`' {
+            // A quote is escaped by doubling it, e.g. 'it''s'.
+            let mut value = String::new();
+            position += 1;
+            loop {
+                match (chars.get(position), chars.get(position + 1)) {
+                    (Some(d), Some(e)) if *d == c && *e == c => {
+                        value.push(c);
+                        position += 2;
+                    }
+                    (Some(d), _) if *d == c => break,
+                    (Some(d), _) => {
+                        value.push(*d);
+                        position += 1;
+                    }
+                    (None, _) => Err(anyhow!("sql: unclosed {}{}", c, value.chars().take(20).collect::<String>()))?,
+                }
+            }
+            position += 1;
+            tokens.push(match c {
+                '\'' => Token::Text(value),
+                _ => Token::Quoted(value),
+            });
+        } else if c == '$' && chars[position + 1..].iter().position(|c| *c == '$').is_some_and(|length| chars[position + 1..position + 1 + length].iter().all(|c| c.is_alphanumeric() || *c == '_')) {
+            // A dollar-quoted string such as the body of a function, $$...$$ or $body$...$body$.
+            let length = chars[position + 1..].iter().position(|c| *c == '$').unwrap_or(0);
+            let tag = text(position, position + length + 2);
+            let body = text(position + tag.chars().count(), chars.len());
+            let end = body.find(&tag).ok_or(anyhow!("sql: unclosed {}", tag))?;
+            tokens.push(Token::Text(body[..end].to_string()));
+            position += tag.chars().count() * 2 + body[..end].chars().count();
+        } else if c.is_ascii_digit() {
+            let length = chars[position..].iter().take_while(|c| c.is_ascii_digit() || **c == '.').count();
+            tokens.push(Token::Number(text(position, position + length)));
+            position += length;
+        } else if c.is_alphabetic() || c == '_' {
+            let length = chars[position..].iter().take_while(|c| c.is_alphanumeric() || **c == '_' || **c == '$').count();
+            tokens.push(Token::Word(text(position, position + length)));
+            position += length;
+        } else {
+            tokens.push(Token::Symbol(c));
+            position += 1;
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A parser of the tokens of a single statement.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.position);
+        self.position += 1;
+        token
+    }
+
+    /// Consumes the next token when it is the keyword `keyword`, whose case is ignored.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Consumes the next tokens when they are the keywords `keywords`, e.g. `IF NOT EXISTS`, or none of them otherwise.
+    fn keywords(&mut self, keywords: &[&str]) -> bool {
+        let position = self.position;
+        match keywords.iter().all(|keyword| self.keyword(keyword)) {
+            true => true,
+            false => {
+                self.position = position;
+                false
+            }
+        }
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.peek() == Some(&Token::Symbol(symbol))
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Word(name)) | Some(Token::Quoted(name)) => Ok(name.clone()),
+            token => Err(anyhow!("sql: expected a name but got {}", describe(token))),
+        }
+    }
+
+    /// Reads a name qualified with its schema, e.g. `public.users`, and returns its last part.
+    fn name(&mut self) -> Result<String> {
+        let mut name = self.identifier()?;
+        while self.is_symbol('.') {
+            self.position += 1;
+            name = self.identifier()?;
+        }
+        Ok(name)
+    }
+
+    /// Returns the tokens within the parentheses starting at the next token, and consumes them.
+    fn group(&mut self) -> Result<&'a [Token]> {
+        if !self.is_symbol('(') {
+            Err(anyhow!("sql: expected ( but got {}", describe(self.peek())))?
+        }
+
+        let start = self.position + 1;
+        let mut depth = 0;
+        while let Some(token) = self.next() {
+            match token {
+                Token::Symbol('(') => depth += 1,
+                Token::Symbol(')') if depth == 1 => return Ok(&self.tokens[start..self.position - 1]),
+                Token::Symbol(')') => depth -= 1,
+                _ => (),
+            }
+        }
+        Err(anyhow!("sql: unclosed ("))
+    }
+
+    /// Reads a parenthesized list of column names, e.g. `(user_id, role_id)`, skipping an order or a prefix length.
+    fn identifiers(&mut self) -> Result<Vec<String>> {
+        split(self.group()?)
+            .into_iter()
+            .filter(|tokens| !tokens.is_empty())
+            .map(|tokens| Parser { tokens, position: 0 }.identifier())
+            .collect()
+    }
+
+    fn statement(&mut self, schema: &mut Schema) -> Result<()> {
+        if self.keyword("create") {
+            self.keywords(&["or", "replace"]);
+            while ["global", "local", "temp", "temporary", "unlogged"].iter().any(|keyword| self.keyword(keyword)) {}
+
+            if self.keyword("table") {
+                self.keywords(&["if", "not", "exists"]);
+                let name = self.name()?;
+                // CREATE TABLE ... AS SELECT and CREATE TABLE ... LIKE do not list the columns.
+                if self.is_symbol('(') {
+                    let table = self.table(name)?;
+                    schema.tables.retain(|other| !other.name.eq_ignore_ascii_case(&table.name));
+                    schema.tables.push(table);
+                }
+            } else if self.keyword("type") {
+                let name = self.name()?;
+                if self.keywords(&["as", "enum"]) {
+                    schema.enums.insert(name.to_lowercase(), literals(self.group()?));
+                }
+            }
+        } else if self.keywords(&["alter", "table"]) {
+            self.keywords(&["if", "exists"]);
+            self.keyword("only");
+            let name = self.name()?;
+            let Some(table) = schema.tables.iter_mut().find(|table| table.name.eq_ignore_ascii_case(&name)) else {
+                return Ok(());
+            };
+
+            for action in split(&self.tokens[self.position..]) {
+                let mut parser = Parser { tokens: action, position: 0 };
+                if !parser.keyword("add") {
+                    continue;
+                }
+                match parser.is_constraint() {
+                    true => table.add_constraint(parser.constraint()?),
+                    false => {
+                        parser.keyword("column");
+                        parser.keywords(&["if", "not", "exists"]);
+                        let (column, constraints) = parser.column()?;
+                        table.columns.push(column);
+                        constraints.into_iter().for_each(|constraint| table.add_constraint(constraint));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn table(&mut self, name: String) -> Result<Table> {
+        let mut table = Table { name, ..Default::default() };
+        let mut constraints = vec![];
+        for element in split(self.group()?).into_iter().filter(|tokens| !tokens.is_empty()) {
+            let mut parser = Parser { tokens: element, position: 0 };
+            match parser.is_constraint() {
+                true => constraints.push(parser.constraint()?),
+                false => {
+                    let (column, column_constraints) = parser.column().map_err(|error| anyhow!("{} in table {}", error, table.name))?;
+                    table.columns.push(column);
+                    constraints.extend(column_constraints);
+                }
+            }
+        }
+        constraints.into_iter().for_each(|constraint| table.add_constraint(constraint));
+
+        Ok(table)
+    }
+
+    fn is_constraint(&self) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if CONSTRAINT_WORDS.contains(&word.to_lowercase().as_str()))
+    }
+
+    /// Reads a table constraint, e.g. `CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id)`.
+    fn constraint(&mut self) -> Result<Constraint> {
+        if self.keyword("constraint") {
+            self.identifier()?;
+        }
+
+        if self.keywords(&["primary", "key"]) {
+            Ok(Constraint::PrimaryKey(self.identifiers()?))
+        } else if self.keyword("unique") {
+            let _ = self.keyword("key") || self.keyword("index");
+            if !self.is_symbol('(') {
+                self.identifier()?;
+            }
+            self.keywords(&["nulls", "not", "distinct"]);
+            Ok(Constraint::Unique(self.identifiers()?))
+        } else if self.keywords(&["foreign", "key"]) {
+            if !self.is_symbol('(') {
+                self.identifier()?;
+            }
+            let columns = self.identifiers()?;
+            if !self.keyword("references") {
+                Err(anyhow!("sql: expected REFERENCES but got {}", describe(self.peek())))?
+            }
+            let (table, referenced) = self.references()?;
+            Ok(Constraint::ForeignKey(columns, table, referenced))
+        } else if self.keyword("check") {
+            let (column, values) = check_values(self.group()?);
+            Ok(Constraint::Check(column, values))
+        } else {
+            Ok(Constraint::Other)
+        }
+    }
+
+    /// Reads the table and the columns following `REFERENCES`, and skips the referential actions after them.
+    fn references(&mut self) -> Result<(String, Vec<String>)> {
+        let table = self.name()?;
+        let columns = match self.is_symbol('(') {
+            true => self.identifiers()?,
+            false => vec![],
+        };
+
+        loop {
+            if self.keyword("match") {
+                self.next();
+            } else if self.keyword("on") {
+                self.next();
+                let _ = self.keyword("cascade") || self.keyword("restrict") || self.keywords(&["no", "action"]) || self.keywords(&["set", "null"]) || self.keywords(&["set", "default"]);
+            } else {
+                return Ok((table, columns));
+            }
+        }
+    }
+
+    /// Reads a column definition, e.g. `email varchar(255) NOT NULL UNIQUE`, and the constraints on other columns of
+    /// the table it declares, such as the primary key.
+    fn column(&mut self) -> Result<(Column, Vec<Constraint>)> {
+        let mut column = Column { name: self.identifier()?, ..Default::default() };
+        let mut constraints = vec![];
+
+        let mut words: Vec<String> = vec![];
+        loop {
+            match self.peek() {
+                Some(Token::Word(word)) | Some(Token::Quoted(word)) if words.is_empty() || TYPE_WORDS.contains(&word.to_lowercase().as_str()) => {
+                    words.push(word.to_lowercase());
+                    self.position += 1;
+                }
+                Some(Token::Symbol('.')) if words.len() == 1 => {
+                    words.clear();
+                    self.position += 1;
+                }
+                Some(Token::Symbol('(')) if !words.is_empty() => column.args = literals(self.group()?),
+                Some(Token::Symbol('[')) => {
+                    while !matches!(self.next(), Some(Token::Symbol(']')) | None) {}
+                    column.array = true;
+                }
+                _ => break,
+            }
+        }
+        if words.last().is_some_and(|word| word == "array") {
+            words.pop();
+            column.array = true;
+        }
+        column.data_type = words.join(" ");
+        if column.data_type.is_empty() {
+            Err(anyhow!("sql: column {} should have a type", column.name))?
+        }
+
+        while let Some(token) = self.peek() {
+            if *token == Token::Symbol('(') {
+                self.group()?;
+                continue;
+            }
+            self.position += 1;
+            let Token::Word(word) = token else {
+                continue;
+            };
+
+            match word.to_lowercase().as_str() {
+                "not" if self.keyword("null") => column.not_null = true,
+                "primary" if self.keyword("key") => constraints.push(Constraint::PrimaryKey(vec![column.name.clone()])),
+                "unique" => {
+                    self.keyword("key");
+                    column.unique = true;
+                }
+                "references" => {
+                    let (table, referenced) = self.references()?;
+                    column.references = Some((table, referenced.into_iter().next()));
+                }
+                "check" if self.is_symbol('(') => {
+                    let (_, values) = check_values(self.group()?);
+                    if !values.is_empty() {
+                        column.values = values;
+                    }
+                }
+                "default" => column.identity |= self.default_value()?,
+                "auto_increment" | "autoincrement" | "identity" => column.identity = true,
+                _ => (),
+            }
+        }
+
+        Ok((column, constraints))
+    }
+
+    /// Skips a default value, e.g. `0`, `'draft'::text`, `now()` or `(now() AT TIME ZONE 'utc')`, and returns whether
+    /// it is the next value of a sequence, `nextval(...)`.
+    fn default_value(&mut self) -> Result<bool> {
+        self.keywords(&["on", "null"]);
+        if self.is_symbol('-') || self.is_symbol('+') {
+            self.position += 1;
+        }
+
+        let identity = match self.peek() {
+            Some(Token::Symbol('(')) => {
+                self.group()?;
+                false
+            }
+            Some(token) => {
+                self.position += 1;
+                let identity = matches!(token, Token::Word(word) if word.eq_ignore_ascii_case("nextval"));
+                if self.is_symbol('(') {
+                    self.group()?;
+                }
+                identity
+            }
+            None => false,
+        };
+
+        // A cast, e.g. ::character varying.
+        while self.is_symbol(':') {
+            self.position += 1;
+            if self.is_symbol(':') {
+                self.position += 1;
+                self.identifier()?;
+                while matches!(self.peek(), Some(Token::Word(word)) if TYPE_WORDS.contains(&word.to_lowercase().as_str())) {
+                    self.position += 1;
+                }
+                if self.is_symbol('(') {
+                    self.group()?;
+                }
+            }
+        }
+
+        Ok(identity)
+    }
+}
+
+/// Splits `tokens` at the commas which are not within parentheses.
+fn split(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Symbol('(') => depth += 1,
+            Token::Symbol(')') => depth -= 1,
+            Token::Symbol(',') if depth == 0 => {
+                parts.push(&tokens[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(&tokens[start..]);
+    parts
+}
+
+/// Returns the string and number literals of `tokens`, e.g. the values of `enum('a', 'b')` or the length of `varchar(255)`.
+fn literals(tokens: &[Token]) -> Vec<Value> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(index, token)| match token {
+            Token::Text(text) => Some(Value::String(text.clone())),
+            Token::Number(number) => {
+                let negative = index > 0 && tokens[index - 1] == Token::Symbol('-');
+                let number = format!("{}{}", if negative { "-" } else { "" }, number);
+                match number.parse::<i64>() {
+                    Ok(integer) => Some(Value::from(integer)),
+                    Err(_) => number.parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number),
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the column and the values of a `CHECK (column IN (...))` constraint, also written by PostgreSQL as
+/// `CHECK ((column)::text = ANY ((ARRAY['a'::character varying, ...])::text[]))`. Any other check has no values.
+fn check_values(tokens: &[Token]) -> (Option<String>, Vec<Value>) {
+    let is_keyword = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.eq_ignore_ascii_case(keyword));
+    let Some(index) = tokens.iter().position(|token| is_keyword(token, "in") || is_keyword(token, "any")) else {
+        return (None, vec![]);
+    };
+    if index > 0 && is_keyword(&tokens[index - 1], "not") {
+        return (None, vec![]);
+    }
+
+    let column = tokens[..index].iter().enumerate().find_map(|(position, token)| match token {
+        Token::Word(name) | Token::Quoted(name) if position == 0 || tokens[position - 1] != Token::Symbol(':') => Some(name.clone()),
+        _ => None,
+    });
+
+    // The values are within the first parentheses or brackets following IN or ANY.
+    let mut depth = 0;
+    let mut end = tokens.len();
+    for (position, token) in tokens.iter().enumerate().skip(index + 1) {
+        match token {
+            Token::Symbol('(') | Token::Symbol('[') => depth += 1,
+            Token::Symbol(')') | Token::Symbol(']') => {
+                depth -= 1;
+                if depth == 0 {
+                    end = position;
+                    break;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    (column, literals(&tokens[index + 1..end]))
+}
+
+fn describe(token: Option<&Token>) -> String {
+    match token {
+        Some(Token::Word(word)) | Some(Token::Number(word)) => word.clone(),
+        Some(Token::Quoted(name)) => format!("\"{}\"", name),
+        Some(Token::Text(text)) => format!("'{}'", text),
+        Some(Token::Symbol(symbol)) => symbol.to_string(),
+        None => "the end of the statement".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Column};
+    use serde_json::json;
+
+    #[test]
+    fn test_parse() {
+        let schema = parse(r#"
+            -- The users
+            CREATE TYPE public.role AS ENUM ('admin', 'member');
+            CREATE TABLE IF NOT EXISTS public.users (
+                id bigserial PRIMARY KEY,
+                "email" character varying(255) NOT NULL UNIQUE,
+                role role DEFAULT 'member'::role,
+                status varchar(10) NOT NULL CHECK (status IN ('active', 'it''s')),
+                balance numeric(10, 2) DEFAULT 0 NOT NULL,
+                created_at timestamp with time zone DEFAULT now() NOT NULL,
+                tags text[]
+            );
+            /* The orders */
+            CREATE TABLE orders (
+                id int NOT NULL AUTO_INCREMENT,
+                user_id bigint NOT NULL,
+                item_no int DEFAULT nextval('orders_item_no_seq'::regclass),
+                rating int,
+                CONSTRAINT pk_orders PRIMARY KEY (id),
+                CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES public.users (id) ON DELETE CASCADE,
+                UNIQUE (user_id, item_no),
+                CHECK (rating IN (1, 2, 3))
+            ) ENGINE=InnoDB;
+            CREATE INDEX orders_user_id ON orders (user_id);
+            ALTER TABLE ONLY orders ADD COLUMN note text, ADD CONSTRAINT rating_check CHECK (((rating)::integer = ANY (ARRAY[4, 5])));
+        "#).unwrap();
+
+        assert_eq!(schema.enums["role"], vec![json!("admin"), json!("member")]);
+        assert_eq!(schema.tables.iter().map(|table| table.name.as_str()).collect::<Vec<_>>(), vec!["users", "orders"]);
+
+        let users = &schema.tables[0];
+        assert_eq!(users.primary_key, vec!["id"]);
+        assert_eq!(users.column("email").unwrap(), &Column { name: "email".to_string(), data_type: "character varying".to_string(), args: vec![json!(255)], not_null: true, unique: true, ..Default::default() });
+        assert_eq!(users.column("role").unwrap().data_type, "role");
+        assert_eq!(users.column("status").unwrap().values, vec![json!("active"), json!("it's")]);
+        assert_eq!(users.column("balance").unwrap().args, vec![json!(10), json!(2)]);
+        assert_eq!(users.column("created_at").unwrap().data_type, "timestamp with time zone");
+        assert!(users.column("created_at").unwrap().not_null, "NOT NULL should be read after the default value");
+        assert!(users.column("tags").unwrap().array);
+
+        let orders = &schema.tables[1];
+        assert_eq!(orders.primary_key, vec!["id"]);
+        assert!(orders.column("id").unwrap().identity && orders.column("item_no").unwrap().identity);
+        assert_eq!(orders.column("user_id").unwrap().references, Some(("users".to_string(), Some("id".to_string()))));
+        assert_eq!(orders.unique, vec![vec!["user_id".to_string(), "item_no".to_string()]]);
+        assert_eq!(orders.column("rating").unwrap().values, vec![json!(4), json!(5)], "A constraint added later should replace the check");
+        assert_eq!(orders.column("note").unwrap().data_type, "text");
+    }
+
+    #[test]
+    fn test_parse_fail() {
+        assert!(parse("CREATE TABLE users (id int").is_err(), "Should return an error for an unclosed parenthesis");
+        assert!(parse("CREATE TABLE users (name 'text')").is_err(), "Should return an error for a column without a type");
+        assert!(parse("INSERT INTO users VALUES ('unclosed)").is_err(), "Should return an error for an unclosed string");
+        assert!(parse("INSERT INTO users VALUES (1); DROP TABLE users;").unwrap().tables.is_empty(), "Other statements should be skipped");
+    }
+}
@@ -0,0 +1,26 @@
+/// The `fake` module provides functionalities for generating fake data based on
+/// a JSON definition.
+pub mod fake;
+
+/// The `generator` module provides the `Generator`, which parses a definition with a `GeneratorBuilder`
+/// and generates one record, `count` records or an iterator of records from it.
+pub mod generator;
+
+/// The `schema` module generates JSON Schemas describing the definition language.
+pub mod schema;
+
+/// The `import` module converts schemas written for other tools into editable definitions.
+pub mod import;
+
+/// This module provides utility functions for operations related to file system,
+/// such as loading the contents of a JSON file.
+mod file;
+
+pub use fake::fake_definition::FakeDefinition;
+pub use fake::fake_definition_element::FakeDefinitionElement;
+pub use fake::fake_type::FakeType;
+pub use fake::fake_type::custom::Custom;
+pub use fake::generation_context::GenerationContext;
+pub use fake::registry::Registry;
+pub use fake::type_info::{OtherKeys, ParamInfo, ParamType, TypeInfo};
+pub use generator::{Generator, GeneratorBuilder};
@@ -5,14 +5,6 @@
 /// If an error occurs during the execution, the error is printed to the standard output.
 mod cli;
 
-/// This module provides utility functions for operations related to file system,
-/// such as loading the contents of a JSON file.
-mod file;
-
-/// The `fake` module provides functionalities for generating fake data based on
-/// input JSON file and command-line options provided by the user.
-mod fake;
-
 /// `main` function, the entry point of the application.
 ///
 /// It executes the `cli::start` function to start the application.
@@ -22,4 +14,4 @@ fn main() {
         Ok(_) => (),
         Err(e) => println!("{}", e),
     }
-}
\ No newline at end of file
+}
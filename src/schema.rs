@@ -0,0 +1,9 @@
+/// The `definition` module generates the JSON Schema of the definition language from the `TypeInfo` of every
+/// registered fake type, so that editors can validate and autocomplete definition files.
+pub mod definition;
+
+/// The `output` module generates the JSON Schema of the data a definition generates, and TypeScript interfaces of it.
+pub mod output;
+
+/// The JSON Schema draft the schemas are written in, which is the draft most editors support.
+pub const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
@@ -0,0 +1,293 @@
+use serde_json::{json, Map, Value};
+use crate::fake::registry::Registry;
+use crate::fake::type_info::{OtherKeys, ParamInfo, ParamType, TypeInfo};
+use crate::schema::JSON_SCHEMA_DRAFT;
+
+const ELEMENT: &str = "#/definitions/element";
+const RECORD: &str = "#/definitions/record";
+const VARIABLE: &str = "#/definitions/variable";
+
+/// Generates the JSON Schema of a definition file whose fake types are registered in `registry`.
+///
+/// A definition file is either a dataset of `entities`, or a record of named fields. Every field is a definition
+/// element, which is either a `$ref` to another definition or a `fake_type` with its parameters. A file included by
+/// a `$ref` may also be a single definition element.
+///
+/// Each fake type has its own schema, `#/definitions/fake_type:<name>`, listing its parameters together with `unique`
+/// and `when`, so that a misspelled or missing parameter is reported by the editor. Integer and boolean parameters also
+/// accept a `${VAR}` variable, which is substituted before the definition is parsed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fake_cli::schema::definition::definition_schema;
+/// # use fake_cli::Registry;
+/// # use anyhow::Result;
+/// # use std::fs;
+/// # fn main() -> Result<()> {
+/// let schema = definition_schema(Registry::builtin());
+/// fs::write("fake-cli.schema.json", serde_json::to_string_pretty(&schema)?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn definition_schema(registry: &Registry) -> Value {
+    let mut definitions = Map::new();
+    definitions.insert("dataset".to_string(), dataset_schema());
+    definitions.insert("record".to_string(), record_schema());
+    definitions.insert("element".to_string(), json!({
+        "description": "A definition element: a fake_type with its parameters, or a $ref to another definition",
+        "anyOf": [{ "$ref": "#/definitions/ref" }, { "$ref": "#/definitions/fake_type" }]
+    }));
+    definitions.insert("ref".to_string(), ref_schema());
+    definitions.insert("fake_type".to_string(), fake_type_schema(registry));
+    definitions.insert("variable".to_string(), json!({
+        "description": "A ${VAR} or ${VAR:-default} variable, substituted with --set or the environment before the definition is parsed",
+        "type": "string",
+        "pattern": "^\\$\\{[A-Za-z0-9_]+(:-[^}]*)?\\}$"
+    }));
+    for info in registry.type_infos() {
+        definitions.insert(type_definition_key(&info.name), type_schema(info));
+    }
+
+    json!({
+        "$schema": JSON_SCHEMA_DRAFT,
+        "title": "fake-cli definition",
+        "description": "A definition of the fake data generated by fake-cli: a record of named fields, a dataset of entities, or a definition element included by a $ref",
+        "anyOf": [{ "$ref": "#/definitions/dataset" }, { "$ref": RECORD }, { "$ref": ELEMENT }],
+        "definitions": definitions
+    })
+}
+
+/// Returns the key of the schema of the fake type `fake_type` under `definitions`.
+fn type_definition_key(fake_type: &str) -> String {
+    format!("fake_type:{}", fake_type)
+}
+
+/// Returns the JSON pointer to the schema of the fake type `fake_type`, escaping `~` and `/` in its name.
+fn type_definition_ref(fake_type: &str) -> String {
+    format!("#/definitions/{}", type_definition_key(fake_type).replace('~', "~0").replace('/', "~1"))
+}
+
+/// The named definitions a `$ref` refers to, which are removed before the definition is parsed.
+fn named_definitions_schema() -> Value {
+    json!({
+        "description": "Named definitions, referred to with { \"$ref\": \"#/definitions/<name>\" }",
+        "type": "object",
+        "additionalProperties": { "$ref": ELEMENT }
+    })
+}
+
+fn dataset_schema() -> Value {
+    json!({
+        "description": "Named entities, each generating count records from its own definition",
+        "type": "object",
+        "required": ["entities"],
+        "properties": {
+            "definitions": named_definitions_schema(),
+            "entities": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "required": ["count", "definition"],
+                    "properties": {
+                        "count": { "description": "The number of records", "anyOf": [{ "type": "integer", "minimum": 0 }, { "$ref": VARIABLE }] },
+                        "definition": { "$ref": RECORD }
+                    },
+                    "additionalProperties": false
+                }
+            }
+        },
+        "additionalProperties": false
+    })
+}
+
+/// The record options are only taken as options when they are not objects, so a field can still be named after them.
+fn record_schema() -> Value {
+    json!({
+        "description": "Named fields, each generated from a definition element",
+        "type": "object",
+        "properties": {
+            "definitions": named_definitions_schema(),
+            "unique_by": {
+                "description": "The lists of fields whose combination is unique across the generated records",
+                "anyOf": [{ "type": "array", "items": { "type": "array", "items": { "type": "string" } } }, { "$ref": ELEMENT }]
+            },
+            "constraints": {
+                "description": "The boolean expressions every generated record satisfies",
+                "anyOf": [{ "type": "array", "items": { "type": "string" } }, { "$ref": ELEMENT }]
+            }
+        },
+        "additionalProperties": { "$ref": ELEMENT }
+    })
+}
+
+/// The other keys next to a `$ref` override the keys of the definition it refers to.
+fn ref_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["$ref"],
+        "properties": {
+            "$ref": { "description": "A named definition such as #/definitions/address, or another definition file such as common/address.json", "type": "string" },
+            "max_depth": { "description": "The number of levels a $ref including itself is nested", "type": "integer", "minimum": 1 },
+            "probability": { "description": "The probability each nested level is generated with", "type": "number", "minimum": 0, "maximum": 1, "default": 1 }
+        }
+    })
+}
+
+/// Selects the schema of the fake type by the value of `fake_type`.
+fn fake_type_schema(registry: &Registry) -> Value {
+    let cases: Vec<Value> = registry
+        .type_infos()
+        .map(|info| json!({
+            "if": { "properties": { "fake_type": { "const": info.name } }, "required": ["fake_type"] },
+            "then": { "$ref": type_definition_ref(&info.name) }
+        }))
+        .collect();
+
+    json!({
+        "type": "object",
+        "required": ["fake_type"],
+        "properties": {
+            "fake_type": { "description": "The fake type generating the value", "enum": registry.fake_types().collect::<Vec<&str>>() }
+        },
+        "allOf": cases
+    })
+}
+
+/// Returns the schema of a definition element of the fake type `info`.
+fn type_schema(info: &TypeInfo) -> Value {
+    let mut properties = Map::new();
+    properties.insert("fake_type".to_string(), json!({ "const": info.name }));
+    for param in &info.params {
+        properties.insert(param.name.clone(), param_schema(param));
+    }
+
+    // A map or an array takes an object under unique or when as a nested element instead of the option.
+    let options = [
+        ("unique", json!({ "description": "Whether the value is never repeated across the generated records", "type": "boolean", "default": false })),
+        ("when", json!({ "description": "The boolean expression on other fields deciding whether the value is generated", "type": "string" })),
+    ];
+    for (name, schema) in options {
+        let schema = match info.other_keys {
+            OtherKeys::Definitions => json!({ "anyOf": [schema, { "$ref": ELEMENT }] }),
+            _ => schema,
+        };
+        properties.entry(name).or_insert(schema);
+    }
+
+    let required: Vec<&str> = std::iter::once("fake_type")
+        .chain(info.params.iter().filter(|param| param.required).map(|param| param.name.as_str()))
+        .collect();
+    let additional_properties = match info.other_keys {
+        OtherKeys::None => json!(false),
+        OtherKeys::Definitions => json!({ "$ref": ELEMENT }),
+        OtherKeys::Any => json!(true),
+    };
+
+    json!({
+        "description": info.description,
+        "type": "object",
+        "required": required,
+        "properties": properties,
+        "additionalProperties": additional_properties,
+        "examples": [info.example]
+    })
+}
+
+/// Returns the schema of the value of a parameter, which also accepts a variable when it is an integer or a boolean.
+fn param_schema(param: &ParamInfo) -> Value {
+    let mut schema = match param.param_type {
+        ParamType::String | ParamType::Expression => json!({ "type": "string" }),
+        ParamType::Integer => json!({ "type": "integer" }),
+        ParamType::Boolean => json!({ "type": "boolean" }),
+        ParamType::Definition => json!({ "allOf": [{ "$ref": ELEMENT }] }),
+        ParamType::Definitions => json!({ "type": "array", "items": { "$ref": ELEMENT } }),
+        ParamType::Fields => json!({ "type": "object", "additionalProperties": { "$ref": ELEMENT } }),
+        ParamType::Any => json!({}),
+    };
+    if let Some(minimum) = param.minimum {
+        schema["minimum"] = json!(minimum);
+    }
+    if let Some(maximum) = param.maximum {
+        schema["maximum"] = json!(maximum);
+    }
+    if !param.values.is_empty() {
+        schema["enum"] = json!(param.values);
+    }
+
+    let mut schema = match param.param_type {
+        ParamType::Integer | ParamType::Boolean => json!({ "anyOf": [schema, { "$ref": VARIABLE }] }),
+        _ => schema,
+    };
+    schema["description"] = json!(param.description);
+    if let Some(default) = &param.default {
+        schema["default"] = default.clone();
+    }
+    if param.deprecated {
+        schema["deprecated"] = json!(true);
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::definition_schema;
+    use crate::fake::registry::Registry;
+    use crate::fake::type_info::TypeInfo;
+    use serde_json::json;
+
+    #[test]
+    fn test_definition_schema() {
+        let schema = definition_schema(Registry::builtin());
+        let definitions = &schema["definitions"];
+
+        assert_eq!(schema["$schema"], json!("http://json-schema.org/draft-07/schema#"));
+        assert_eq!(definitions["fake_type"]["properties"]["fake_type"]["enum"].as_array().unwrap().len(), Registry::builtin().fake_types().count());
+        assert_eq!(definitions["fake_type"]["allOf"][0]["then"], json!({ "$ref": "#/definitions/fake_type:word" }));
+        for fake_type in Registry::builtin().fake_types() {
+            assert!(definitions[format!("fake_type:{}", fake_type)].is_object(), "Every fake type should have a schema: {}", fake_type);
+        }
+
+        let words = &definitions["fake_type:words"];
+        assert_eq!(words["required"], json!(["fake_type", "min", "max"]));
+        assert_eq!(words["additionalProperties"], json!(false), "Unknown parameters should be reported");
+        assert_eq!(words["properties"]["lang"]["default"], json!("EN"));
+        assert!(words["properties"]["lang"]["enum"].as_array().unwrap().contains(&json!("JA_JP")));
+        assert_eq!(words["properties"]["unique"]["type"], json!("boolean"));
+        assert_eq!(words["properties"]["when"]["type"], json!("string"));
+    }
+
+    #[test]
+    fn test_definition_schema_params() {
+        let definitions = &definition_schema(Registry::builtin())["definitions"];
+
+        let ratio = &definitions["fake_type:boolean"]["properties"]["ratio"];
+        assert_eq!(ratio["anyOf"][0], json!({ "type": "integer", "minimum": 0, "maximum": 100 }));
+        assert_eq!(ratio["anyOf"][1], json!({ "$ref": "#/definitions/variable" }), "An integer should also accept a variable");
+        assert_eq!(ratio["default"], json!(50));
+
+        let array = &definitions["fake_type:array"];
+        assert_eq!(array["additionalProperties"], json!({ "$ref": "#/definitions/element" }), "The other keys of an array should be elements");
+        assert_eq!(array["properties"]["unique"]["anyOf"][0]["type"], json!("boolean"), "unique should be the parameter of an array");
+        assert_eq!(array["properties"]["tuple"]["items"], json!({ "$ref": "#/definitions/element" }));
+        assert_eq!(definitions["fake_type:if"]["required"], json!(["fake_type", "if", "then"]));
+        assert_eq!(definitions["fake_type:map"]["properties"]["when"]["anyOf"][1], json!({ "$ref": "#/definitions/element" }), "A field of a map can be named when");
+
+        let parse_type = &definitions["fake_type:digit"]["properties"]["parse_type"];
+        assert_eq!(parse_type["type"], json!("string"), "The deprecated parse_type of a digit should be accepted");
+        assert_eq!(parse_type["deprecated"], json!(true));
+    }
+
+    #[test]
+    fn test_definition_schema_custom() {
+        let mut registry = Registry::new();
+        registry.register("sku", |_, _, _| unimplemented!());
+        registry.register_type(TypeInfo::new("a/b", "custom", "A fake type with a slash"), |_, _, _| unimplemented!());
+
+        let schema = definition_schema(&registry);
+
+        assert_eq!(schema["definitions"]["fake_type"]["properties"]["fake_type"]["enum"], json!(["sku", "a/b"]));
+        assert_eq!(schema["definitions"]["fake_type:sku"]["additionalProperties"], json!(true), "The keys of an undescribed fake type should be allowed");
+        assert_eq!(schema["definitions"]["fake_type"]["allOf"][1]["then"], json!({ "$ref": "#/definitions/fake_type:a~1b" }));
+    }
+}
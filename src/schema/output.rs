@@ -0,0 +1,310 @@
+use std::collections::{HashMap, HashSet};
+use serde_json::{json, Value};
+use crate::generator::Generator;
+use crate::schema::JSON_SCHEMA_DRAFT;
+
+/// Returns the JSON Schema of the data `generator` generates, titled `title`.
+///
+/// A `digit` is an integer, a `boolean` a boolean, an `array` an array of the schema of its element and a `map` an
+/// object with a property per field, where the fields omitted by a `when` condition are optional. The records of each
+/// entity of a dataset are under `definitions`, where the `ref`s refer to the referenced field.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fake_cli::schema::output::output_schema;
+/// # use fake_cli::Generator;
+/// # use anyhow::Result;
+/// # fn main() -> Result<()> {
+/// let generator = Generator::builder().from_path("definition.json")?;
+/// println!("{}", serde_json::to_string_pretty(&output_schema(&generator, "definition"))?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn output_schema(generator: &Generator, title: &str) -> Value {
+    let mut schema = json!({ "$schema": JSON_SCHEMA_DRAFT, "title": title });
+    if let (Some(schema), Value::Object(output_schema)) = (schema.as_object_mut(), generator.output_schema()) {
+        schema.extend(output_schema);
+    }
+    schema
+}
+
+/// Converts an output schema into TypeScript interfaces, where the interface of the root is named after `name`.
+///
+/// Every object becomes an exported interface named after its field, e.g. `address` becomes `Address` and the
+/// objects in `contacts` become `ContactsItem`. The records of each entity of a dataset become an interface named
+/// after the entity, and a `ref` becomes an indexed access type such as `Users["id"]`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fake_cli::schema::output::{output_schema, typescript};
+/// # use fake_cli::Generator;
+/// # use anyhow::Result;
+/// # use std::fs;
+/// # fn main() -> Result<()> {
+/// # let generator = Generator::builder().from_path("customer.json")?;
+/// let schema = output_schema(&generator, "customer");
+/// fs::write("customer.ts", typescript(&schema, "customer"))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn typescript(schema: &Value, name: &str) -> String {
+    let mut generator = TypeScript::default();
+    if let Some(definitions) = schema.get("definitions").and_then(Value::as_object) {
+        for key in definitions.keys() {
+            let interface_name = generator.unique_name(key);
+            generator.definitions.insert(key.clone(), interface_name);
+        }
+        for (key, definition) in definitions {
+            let interface_name = generator.definitions[key].clone();
+            generator.interface(interface_name, definition);
+        }
+    }
+    let root = generator.unique_name(name);
+    generator.interface(root, schema);
+
+    generator
+        .interfaces
+        .iter()
+        .map(|(name, body)| format!("export interface {} {}\n", name, body))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// `TypeScript` collects the interfaces of the objects of a schema while converting it.
+#[derive(Default)]
+struct TypeScript {
+    /// The name and body of every interface, in the order they are found.
+    interfaces: Vec<(String, String)>,
+    names: HashSet<String>,
+    /// The interface name of every schema under `definitions`.
+    definitions: HashMap<String, String>,
+}
+
+impl TypeScript {
+    /// Returns `name` in PascalCase, numbered when an interface already has that name.
+    fn unique_name(&mut self, name: &str) -> String {
+        let base = pascal_case(name);
+        let mut unique = base.clone();
+        let mut number = 2;
+        while self.names.contains(&unique) {
+            unique = format!("{}{}", base, number);
+            number += 1;
+        }
+        self.names.insert(unique.clone());
+        unique
+    }
+
+    /// Adds the interface `name` of the object `schema`. Its position is kept before the interfaces of its fields.
+    fn interface(&mut self, name: String, schema: &Value) {
+        let index = self.interfaces.len();
+        self.interfaces.push((name.clone(), String::new()));
+
+        let required: Vec<&str> = schema.get("required").and_then(Value::as_array).map(|required| required.iter().filter_map(Value::as_str).collect()).unwrap_or_default();
+        let mut lines = vec!["{".to_string()];
+        for (key, property) in schema.get("properties").and_then(Value::as_object).into_iter().flatten() {
+            let optional = if required.contains(&key.as_str()) { "" } else { "?" };
+            let property_type = self.type_of(property, key);
+            lines.push(format!("  {}{}: {};", property_name(key), optional, property_type));
+        }
+        lines.push("}".to_string());
+
+        self.interfaces[index].1 = lines.join("\n");
+    }
+
+    /// Returns the TypeScript type of `schema`, where `hint` names the interface of an object.
+    fn type_of(&mut self, schema: &Value, hint: &str) -> String {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return self.reference_type(reference);
+        }
+        if let Some(value) = schema.get("const") {
+            return literal_type(value);
+        }
+        if let Some(alternatives) = schema.get("anyOf").and_then(Value::as_array) {
+            return alternatives.iter().map(|alternative| self.type_of(alternative, hint)).collect::<Vec<String>>().join(" | ");
+        }
+
+        match schema.get("type").and_then(Value::as_str) {
+            Some("string") => "string".to_string(),
+            Some("integer") | Some("number") => "number".to_string(),
+            Some("boolean") => "boolean".to_string(),
+            Some("null") => "null".to_string(),
+            Some("array") => match schema.get("items") {
+                Some(Value::Array(items)) => format!("[{}]", items.iter().map(|item| self.type_of(item, hint)).collect::<Vec<String>>().join(", ")),
+                Some(items) => match self.type_of(items, &format!("{}_item", hint)) {
+                    item_type if item_type.contains(" | ") => format!("({})[]", item_type),
+                    item_type => format!("{}[]", item_type),
+                },
+                None => "unknown[]".to_string(),
+            },
+            Some("object") if schema.get("properties").is_some() => {
+                let name = self.unique_name(hint);
+                self.interface(name.clone(), schema);
+                name
+            }
+            Some("object") => "Record<string, unknown>".to_string(),
+            _ => "unknown".to_string(),
+        }
+    }
+
+    /// Returns the type a `$ref` to a schema under `definitions` refers to, e.g. `Users["id"]` for
+    /// `#/definitions/users/properties/id`.
+    fn reference_type(&self, reference: &str) -> String {
+        let segments: Vec<String> = reference
+            .trim_start_matches("#/definitions/")
+            .split('/')
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .collect();
+
+        match self.definitions.get(&segments[0]) {
+            Some(name) => segments[1..]
+                .chunks(2)
+                .filter_map(|chunk| chunk.get(1))
+                .fold(name.clone(), |reference_type, key| format!("{}[{}]", reference_type, Value::from(key.as_str()))),
+            None => "unknown".to_string(),
+        }
+    }
+}
+
+/// Returns the literal type of a constant, e.g. `"active"` or `{ status: "active" }`.
+fn literal_type(value: &Value) -> String {
+    match value {
+        Value::Array(values) => format!("[{}]", values.iter().map(literal_type).collect::<Vec<String>>().join(", ")),
+        Value::Object(map) => {
+            let properties: Vec<String> = map.iter().map(|(key, value)| format!("{}: {}", property_name(key), literal_type(value))).collect();
+            format!("{{ {} }}", properties.join("; "))
+        }
+        value => value.to_string(),
+    }
+}
+
+/// Returns `key` as a property name, quoted when it is not an identifier.
+fn property_name(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    match is_identifier {
+        true => key.to_string(),
+        false => Value::from(key).to_string(),
+    }
+}
+
+/// Converts `name` to PascalCase, e.g. `order_items` to `OrderItems`.
+fn pascal_case(name: &str) -> String {
+    let pascal: String = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect();
+
+    match pascal.chars().next() {
+        None => "Record".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("T{}", pascal),
+        Some(_) => pascal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{output_schema, pascal_case, property_name, typescript};
+    use crate::generator::Generator;
+    use serde_json::json;
+
+    fn schema_of(definition: serde_json::Value) -> serde_json::Value {
+        output_schema(&Generator::builder().from_value(&definition).unwrap(), "definition")
+    }
+
+    #[test]
+    fn test_output_schema() {
+        let schema = schema_of(json!({
+            "id": { "fake_type": "sequence" },
+            "digit": { "fake_type": "digit", "unique": true },
+            "active": { "fake_type": "boolean" },
+            "email": { "fake_type": "safe_email" },
+            "tags": { "fake_type": "array", "min_count": 1, "max_count": 3, "items": { "fake_type": "word" } },
+            "address": { "fake_type": "map", "city": { "fake_type": "word" }, "zip": { "fake_type": "number_with_format", "format": "###" } },
+            "nickname": { "fake_type": "word", "when": "active" },
+            "total": { "fake_type": "expr", "expr": "digit * 2" },
+            "status": { "fake_type": "if", "if": "active", "then": { "fake_type": "constant", "value": "on" }, "else": { "fake_type": "constant", "value": null } }
+        }));
+
+        assert_eq!(schema["$schema"], json!("http://json-schema.org/draft-07/schema#"));
+        assert_eq!(schema["title"], json!("definition"));
+        assert_eq!(schema["type"], json!("object"));
+        assert_eq!(schema["additionalProperties"], json!(false));
+        assert_eq!(schema["required"], json!(["id", "digit", "active", "email", "tags", "address", "total", "status"]), "A field with when should be optional");
+
+        let properties = &schema["properties"];
+        assert_eq!(properties["id"], json!({ "type": "integer" }));
+        assert_eq!(properties["digit"], json!({ "type": "integer", "minimum": 0, "maximum": 9 }));
+        assert_eq!(properties["active"], json!({ "type": "boolean" }));
+        assert_eq!(properties["email"], json!({ "type": "string", "format": "email" }));
+        assert_eq!(properties["tags"], json!({ "type": "array", "items": { "type": "string" }, "minItems": 1, "maxItems": 3 }));
+        assert_eq!(properties["address"]["properties"]["zip"], json!({ "type": "string" }));
+        assert_eq!(properties["nickname"], json!({ "type": "string" }));
+        assert_eq!(properties["total"], json!({ "type": "number" }), "The type of an expr should be inferred");
+        assert_eq!(properties["status"], json!({ "anyOf": [{ "const": "on" }, { "const": null }] }));
+    }
+
+    #[test]
+    fn test_output_schema_dataset() {
+        let schema = schema_of(json!({
+            "entities": {
+                "users": { "count": 2, "definition": { "id": { "fake_type": "sequence" } } },
+                "orders": { "count": 3, "definition": { "user_id": { "fake_type": "ref", "ref": "users.id" } } }
+            }
+        }));
+
+        assert_eq!(schema["properties"]["orders"], json!({ "type": "array", "items": { "$ref": "#/definitions/orders" }, "minItems": 3, "maxItems": 3 }));
+        assert_eq!(schema["definitions"]["orders"]["properties"]["user_id"], json!({ "$ref": "#/definitions/users/properties/id" }));
+        assert_eq!(schema["definitions"]["users"]["properties"]["id"], json!({ "type": "integer" }));
+    }
+
+    #[test]
+    fn test_typescript() {
+        let schema = schema_of(json!({
+            "id": { "fake_type": "sequence", "format": "U-###" },
+            "tags": { "fake_type": "array", "count": 2, "items": { "fake_type": "digit" } },
+            "address": { "fake_type": "map", "city": { "fake_type": "word" } },
+            "contacts": { "fake_type": "array", "count": 1, "items": { "fake_type": "map", "e-mail": { "fake_type": "free_email" } } },
+            "location": { "fake_type": "array", "tuple": [{ "fake_type": "digit" }, { "fake_type": "digit" }] },
+            "note": { "fake_type": "if", "if": "true", "then": { "fake_type": "word" } },
+            "flag": { "fake_type": "constant", "value": "on", "when": "true" }
+        }));
+
+        assert_eq!(typescript(&schema, "customer"), [
+            "export interface Customer {\n  id: string;\n  tags: number[];\n  address: Address;\n  contacts: ContactsItem[];\n  location: [number, number];\n  note?: string;\n  flag?: \"on\";\n}\n",
+            "export interface Address {\n  city: string;\n}\n",
+            "export interface ContactsItem {\n  \"e-mail\": string;\n}\n",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_typescript_dataset() {
+        let schema = schema_of(json!({
+            "entities": {
+                "users": { "count": 2, "definition": { "id": { "fake_type": "sequence" } } },
+                "order_items": { "count": 3, "definition": { "user_id": { "fake_type": "ref", "ref": "users.id" } } }
+            }
+        }));
+
+        assert_eq!(typescript(&schema, "shop"), [
+            "export interface Users {\n  id: number;\n}\n",
+            "export interface OrderItems {\n  user_id: Users[\"id\"];\n}\n",
+            "export interface Shop {\n  users: Users[];\n  order_items: OrderItems[];\n}\n",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn test_typescript_names() {
+        assert_eq!(pascal_case("order_items"), "OrderItems");
+        assert_eq!(pascal_case("user-profile.v2"), "UserProfileV2");
+        assert_eq!(pascal_case("2fa"), "T2fa");
+        assert_eq!(property_name("first_name"), "first_name");
+        assert_eq!(property_name("first name"), "\"first name\"");
+    }
+}